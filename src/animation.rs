@@ -0,0 +1,158 @@
+//! Animações GIF e WebP a partir de um trecho do vídeo.
+//!
+//! Só o trecho é baixado, como num download de [`Section`]. O GIF usa uma
+//! paleta gerada a partir do próprio trecho (`palettegen` + `paletteuse`),
+//! bem melhor que a paleta fixa padrão do ffmpeg; o WebP animado dispensa a
+//! paleta e costuma sair menor.
+
+use crate::clip::Section;
+use crate::download::{download_with, DownloadOptions};
+use crate::error::DownloadError;
+use crate::job::{JobControl, JobEvent};
+use crate::tools::{self, Tool};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// Formato da animação.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Gif,
+    Webp,
+}
+
+impl Format {
+    pub fn ext(self) -> &'static str {
+        match self {
+            Format::Gif => "gif",
+            Format::Webp => "webp",
+        }
+    }
+}
+
+/// Como gerar a animação.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnimationOptions {
+    /// Largura máxima, em pixels; a altura segue a proporção.
+    pub width: u32,
+    pub fps: u32,
+    pub format: Format,
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        AnimationOptions {
+            width: 480,
+            fps: 12,
+            format: Format::Gif,
+        }
+    }
+}
+
+/// Baixa o trecho de `url` e o converte em `<output_name>.gif` (ou `.webp`).
+/// O trecho baixado é apagado no fim.
+pub async fn export(
+    url: &str,
+    section: Section,
+    output_name: &str,
+    options: &AnimationOptions,
+    mut on_event: impl FnMut(JobEvent),
+) -> Result<PathBuf, DownloadError> {
+    let download = DownloadOptions {
+        section: Some(section),
+        ..DownloadOptions::new(url, &format!("{output_name}.clip"), false)
+    };
+    let clip = download_with(&download, &JobControl::new(), &mut on_event)
+        .await?
+        .path
+        .ok_or_else(|| DownloadError::Transcode(t!("animation.no_clip")))?;
+    on_event(JobEvent::Status(t!(
+        "animation.rendering",
+        format = options.format.ext().to_uppercase()
+    )));
+    let output = PathBuf::from(format!("{output_name}.{}", options.format.ext()));
+    let result = render(&clip, &output, options).await;
+    let _ = fs::remove_file(&clip);
+    result.map(|()| output)
+}
+
+/// Converte um vídeo já baixado na animação.
+pub async fn render(
+    input: &Path,
+    output: &Path,
+    options: &AnimationOptions,
+) -> Result<(), DownloadError> {
+    let ffmpeg = tools::detect(Tool::Ffmpeg).ok_or(DownloadError::MissingDependency("ffmpeg"))?;
+    let result = Command::new(&ffmpeg.program)
+        .args(["-hide_banner", "-loglevel", "error", "-nostdin", "-y"])
+        .args(ffmpeg_args(input, output, options))
+        .output()
+        .await?;
+    if result.status.success() {
+        return Ok(());
+    }
+    Err(DownloadError::Transcode(t!(
+        "animation.failed",
+        error = String::from_utf8_lossy(&result.stderr).trim()
+    )))
+}
+
+fn ffmpeg_args(input: &Path, output: &Path, options: &AnimationOptions) -> Vec<OsString> {
+    // Reduz sem ampliar; lanczos preserva melhor os detalhes ao reduzir
+    let base = format!(
+        "fps={},scale='min(iw,{})':-2:flags=lanczos",
+        options.fps, options.width
+    );
+    let codec: &[&str] = match options.format {
+        Format::Gif => &[],
+        Format::Webp => &["-c:v", "libwebp", "-lossless", "0", "-q:v", "75"],
+    };
+    let filter = match options.format {
+        // A paleta sai do próprio trecho; `diff` prioriza o que se move e o
+        // dither ordenado evita o chuvisco entre quadros
+        Format::Gif => format!(
+            "{base},split[a][b];[a]palettegen=stats_mode=diff[p];[b][p]paletteuse=dither=bayer:bayer_scale=5:diff_mode=rectangle"
+        ),
+        Format::Webp => base,
+    };
+    let mut args: Vec<OsString> = vec!["-i".into(), input.into(), "-vf".into(), filter.into()];
+    args.extend(codec.iter().map(OsString::from));
+    // Repetir para sempre, sem áudio
+    args.extend(["-loop", "0", "-an"].map(OsString::from));
+    args.push(output.into());
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(options: &AnimationOptions) -> Vec<String> {
+        ffmpeg_args(Path::new("in.mp4"), Path::new("out"), options)
+            .into_iter()
+            .map(|arg| arg.into_string().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_gif_uses_palette() {
+        let args = args(&AnimationOptions::default());
+        let filter = &args[3];
+        assert!(filter.starts_with("fps=12,scale='min(iw,480)'"));
+        assert!(filter.contains("palettegen"));
+        assert!(filter.contains("paletteuse"));
+        assert!(!args.contains(&"libwebp".to_string()));
+    }
+
+    #[test]
+    fn test_webp_skips_palette() {
+        let args = args(&AnimationOptions {
+            width: 320,
+            fps: 15,
+            format: Format::Webp,
+        });
+        assert_eq!(args[3], "fps=15,scale='min(iw,320)':-2:flags=lanczos");
+        assert!(args.windows(2).any(|w| w == ["-c:v", "libwebp"]));
+    }
+}
@@ -0,0 +1,83 @@
+//! Registro dos vídeos já baixados (`archive.txt` no diretório de dados).
+//!
+//! O formato é o mesmo do `--download-archive` do yt-dlp, uma linha
+//! `youtube <id>` por vídeo, para que o arquivo possa ser compartilhado com
+//! ele. O `watch` o consulta para baixar só os envios novos.
+
+use crate::paths;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+/// Serializa leituras e escritas do arquivo entre tarefas do mesmo processo.
+static LOCK: Mutex<()> = Mutex::new(());
+
+pub fn path() -> PathBuf {
+    paths::data_dir().join("archive.txt")
+}
+
+/// ID do vídeo em URLs `watch?v=`, `shorts/` e `youtu.be/`.
+pub fn video_id(url: &str) -> Option<String> {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| {
+        Regex::new(r"(?:[?&]v=|/shorts/|youtu\.be/|/live/)([\w-]{11})(?:[^\w-]|$)").unwrap()
+    });
+    Some(pattern.captures(url)?[1].to_string())
+}
+
+/// IDs registrados; um arquivo ausente conta como vazio.
+pub fn ids() -> HashSet<String> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    load()
+}
+
+/// Registra vídeos ainda não presentes no arquivo.
+pub fn add<'a>(new_ids: impl IntoIterator<Item = &'a str>) -> io::Result<()> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut known = load();
+    let mut lines = String::new();
+    for id in new_ids {
+        if known.insert(id.to_string()) {
+            lines.push_str(&format!("youtube {id}\n"));
+        }
+    }
+    if lines.is_empty() {
+        return Ok(());
+    }
+    fs::create_dir_all(paths::data_dir())?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path())?;
+    file.write_all(lines.as_bytes())
+}
+
+fn load() -> HashSet<String> {
+    fs::read_to_string(path())
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let (extractor, id) = line.trim().split_once(' ')?;
+            (extractor == "youtube").then(|| id.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_video_id() {
+        for url in [
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+            "https://www.youtube.com/watch?list=PL1&v=dQw4w9WgXcQ&t=10",
+            "https://youtu.be/dQw4w9WgXcQ?si=abc",
+            "https://www.youtube.com/shorts/dQw4w9WgXcQ",
+        ] {
+            assert_eq!(video_id(url).as_deref(), Some("dQw4w9WgXcQ"), "{url}");
+        }
+        assert_eq!(video_id("https://www.youtube.com/@canal"), None);
+        assert_eq!(video_id("https://youtu.be/curto"), None);
+    }
+}
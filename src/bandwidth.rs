@@ -0,0 +1,229 @@
+//! Limite de banda dos downloads (`--limit-rate` do yt-dlp).
+//!
+//! O limite de cada download é decidido quando ele começa: vale o `throttle`
+//! (ou `--limit-rate`) da sessão, se houver; senão a janela de horário da
+//! configuração que contém a hora atual; senão o limite padrão.
+
+use crate::config::BandwidthConfig;
+use crate::doctor::format_bytes;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Mutex;
+
+/// Limite definido na sessão; `None` segue a configuração.
+static OVERRIDE: Mutex<Option<Limit>> = Mutex::new(None);
+
+/// Velocidade máxima de um download.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Limit {
+    #[default]
+    Unlimited,
+    /// Bytes por segundo.
+    Rate(u64),
+}
+
+impl Limit {
+    /// Interpreta `2M`, `500K`, `1.5MiB/s`, `800000` ou `off`/`unlimited`.
+    /// Os sufixos são binários, como no yt-dlp.
+    pub fn parse(text: &str) -> Result<Limit, RateError> {
+        let invalid = || RateError(text.to_string());
+        let lower = text.trim().to_lowercase();
+        if matches!(lower.as_str(), "off" | "unlimited" | "none" | "0") {
+            return Ok(Limit::Unlimited);
+        }
+        let unit_start = lower
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(lower.len());
+        let (number, unit) = lower.split_at(unit_start);
+        let number: f64 = number.parse().map_err(|_| invalid())?;
+        let unit = unit.trim_end_matches("/s");
+        let multiplier: u64 = match unit.trim_end_matches("ib").trim_end_matches('b') {
+            "" => 1,
+            "k" => 1 << 10,
+            "m" => 1 << 20,
+            "g" => 1 << 30,
+            _ => return Err(invalid()),
+        };
+        let bytes = (number * multiplier as f64).round() as u64;
+        if bytes == 0 {
+            return Err(invalid());
+        }
+        Ok(Limit::Rate(bytes))
+    }
+
+    /// Valor para o `--limit-rate` do yt-dlp.
+    pub(crate) fn ytdlp_arg(self) -> Option<String> {
+        match self {
+            Limit::Unlimited => None,
+            Limit::Rate(bytes) => Some(bytes.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Limit::Unlimited => write!(f, "{}", t!("bandwidth.unlimited")),
+            Limit::Rate(bytes) => write!(f, "{}/s", format_bytes(*bytes)),
+        }
+    }
+}
+
+impl TryFrom<String> for Limit {
+    type Error = RateError;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        Limit::parse(&text)
+    }
+}
+
+impl From<Limit> for String {
+    fn from(limit: Limit) -> String {
+        match limit {
+            Limit::Unlimited => "unlimited".to_string(),
+            Limit::Rate(bytes) => bytes.to_string(),
+        }
+    }
+}
+
+/// Texto que não é uma velocidade válida.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateError(pub String);
+
+impl fmt::Display for RateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", t!("bandwidth.invalid_rate", rate = self.0))
+    }
+}
+
+impl std::error::Error for RateError {}
+
+/// Hora do dia, em minutos desde a meia-noite (`22:30`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ClockTime(pub u16);
+
+impl TryFrom<String> for ClockTime {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        let invalid = || t!("bandwidth.invalid_time", time = text);
+        let (hours, minutes) = text.split_once(':').ok_or_else(invalid)?;
+        let hours: u16 = hours.trim().parse().map_err(|_| invalid())?;
+        let minutes: u16 = minutes.trim().parse().map_err(|_| invalid())?;
+        if hours > 23 || minutes > 59 {
+            return Err(invalid());
+        }
+        Ok(ClockTime(hours * 60 + minutes))
+    }
+}
+
+impl From<ClockTime> for String {
+    fn from(time: ClockTime) -> String {
+        time.to_string()
+    }
+}
+
+impl fmt::Display for ClockTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.0 / 60, self.0 % 60)
+    }
+}
+
+/// Define o limite da sessão (`throttle`); `None` volta a seguir a configuração.
+pub fn set_override(limit: Option<Limit>) {
+    *OVERRIDE.lock().unwrap_or_else(|e| e.into_inner()) = limit;
+}
+
+pub fn override_limit() -> Option<Limit> {
+    *OVERRIDE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Limite para um download que começa agora.
+pub fn current_limit(config: &BandwidthConfig) -> Limit {
+    override_limit().unwrap_or_else(|| scheduled(config, local_minute()))
+}
+
+/// Limite da configuração num horário: a primeira janela que o contém, ou o
+/// limite padrão.
+pub fn scheduled(config: &BandwidthConfig, minute: u16) -> Limit {
+    config
+        .schedule
+        .iter()
+        .find(|window| contains(window.from, window.to, minute))
+        .map(|window| window.limit_rate)
+        .unwrap_or(config.limit_rate)
+}
+
+/// `from` incluído, `to` excluído; janelas como 22:00–07:00 passam da
+/// meia-noite e `from == to` cobre o dia todo.
+fn contains(from: ClockTime, to: ClockTime, minute: u16) -> bool {
+    let (from, to) = (from.0, to.0);
+    if from < to {
+        (from..to).contains(&minute)
+    } else {
+        minute >= from || minute < to
+    }
+}
+
+fn local_minute() -> u16 {
+    let local = crate::stats::now() as i64 + crate::stats::utc_offset();
+    (local.rem_euclid(86_400) / 60) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Window;
+
+    fn time(text: &str) -> ClockTime {
+        ClockTime::try_from(text.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_limit() {
+        assert_eq!(Limit::parse("2M"), Ok(Limit::Rate(2 * 1024 * 1024)));
+        assert_eq!(Limit::parse("500K"), Ok(Limit::Rate(500 * 1024)));
+        assert_eq!(Limit::parse("1.5MiB/s"), Ok(Limit::Rate(1_572_864)));
+        assert_eq!(Limit::parse("1mb"), Ok(Limit::Rate(1 << 20)));
+        assert_eq!(Limit::parse("800000"), Ok(Limit::Rate(800_000)));
+        assert_eq!(Limit::parse("off"), Ok(Limit::Unlimited));
+        for text in ["", "M", "2X", "-1M", "0.0001"] {
+            assert!(Limit::parse(text).is_err(), "deveria falhar: {text:?}");
+        }
+        assert_eq!(Limit::Rate(1 << 20).ytdlp_arg().as_deref(), Some("1048576"));
+        assert_eq!(Limit::Unlimited.ytdlp_arg(), None);
+    }
+
+    #[test]
+    fn test_schedule_windows() {
+        let config = BandwidthConfig {
+            limit_rate: Limit::Rate(1 << 20),
+            schedule: vec![Window {
+                from: time("22:00"),
+                to: time("07:00"),
+                limit_rate: Limit::Unlimited,
+            }],
+        };
+        assert_eq!(scheduled(&config, time("23:30").0), Limit::Unlimited);
+        assert_eq!(scheduled(&config, time("06:59").0), Limit::Unlimited);
+        assert_eq!(scheduled(&config, time("07:00").0), Limit::Rate(1 << 20));
+        assert_eq!(scheduled(&config, time("12:00").0), Limit::Rate(1 << 20));
+
+        assert!(ClockTime::try_from("24:00".to_string()).is_err());
+        assert!(ClockTime::try_from("7h".to_string()).is_err());
+        assert_eq!(time("07:05").to_string(), "07:05");
+    }
+
+    #[test]
+    fn test_config_from_json() {
+        let config: BandwidthConfig = serde_json::from_str(
+            r#"{"limit_rate": "1M", "schedule": [{"from": "22:00", "to": "07:00"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(config.limit_rate, Limit::Rate(1 << 20));
+        assert_eq!(config.schedule[0].limit_rate, Limit::Unlimited);
+        assert!(serde_json::from_str::<BandwidthConfig>(r#"{"limit_rate": "rápido"}"#).is_err());
+    }
+}
@@ -0,0 +1,59 @@
+//! Download de um trecho do vídeo.
+//!
+//! O yt-dlp baixa só o intervalo pedido (`--download-sections`) e corta nos
+//! quadros exatos, recodificando as bordas (`--force-keyframes-at-cuts`).
+
+use serde::{Deserialize, Serialize};
+
+/// Intervalo de um vídeo, em segundos desde o início.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Section {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl Section {
+    pub fn new(start: u64, end: u64) -> Result<Section, String> {
+        if end <= start {
+            return Err(t!("clip.invalid_range"));
+        }
+        Ok(Section { start, end })
+    }
+
+    pub fn len_secs(&self) -> u64 {
+        self.end - self.start
+    }
+
+    /// Duração esperada do trecho num vídeo de `duration_secs`, que pode
+    /// acabar antes do fim pedido.
+    pub fn expected_secs(&self, duration_secs: f64) -> f64 {
+        (duration_secs.min(self.end as f64) - self.start as f64).max(0.0)
+    }
+
+    pub(crate) fn ytdlp_args(&self) -> Vec<String> {
+        vec![
+            "--download-sections".to_string(),
+            format!("*{}-{}", self.start, self.end),
+            "--force-keyframes-at-cuts".to_string(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_section() {
+        let section = Section::new(83, 95).unwrap();
+        assert_eq!(section.len_secs(), 12);
+        assert_eq!(
+            section.ytdlp_args(),
+            ["--download-sections", "*83-95", "--force-keyframes-at-cuts"]
+        );
+        assert_eq!(section.expected_secs(600.0), 12.0);
+        assert_eq!(section.expected_secs(90.0), 7.0);
+        assert!(Section::new(95, 83).is_err());
+        assert!(Section::new(10, 10).is_err());
+    }
+}
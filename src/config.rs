@@ -0,0 +1,295 @@
+use crate::access::AccessOptions;
+use crate::bandwidth::{ClockTime, Limit};
+use crate::paths;
+use crate::provision::{ConfirmPolicy, InstallMethod};
+use crate::subscriptions::Subscription;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+#[cfg(windows)]
+const YTDLP_ASSET: &str = "yt-dlp.exe";
+#[cfg(target_os = "macos")]
+const YTDLP_ASSET: &str = "yt-dlp_macos";
+#[cfg(not(any(windows, target_os = "macos")))]
+const YTDLP_ASSET: &str = "yt-dlp";
+
+/// URL padrão do binário autônomo do yt-dlp para esta plataforma.
+pub fn default_ytdlp_binary_url() -> String {
+    format!("https://github.com/yt-dlp/yt-dlp/releases/latest/download/{YTDLP_ASSET}")
+}
+
+/// URL padrão do arquivo de checksums publicado junto com cada release.
+pub const DEFAULT_YTDLP_CHECKSUMS_URL: &str =
+    "https://github.com/yt-dlp/yt-dlp/releases/latest/download/SHA2-256SUMS";
+
+/// Configuração persistida em `config.json` no diretório de configuração.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub provision: ProvisionConfig,
+    pub doctor: DoctorConfig,
+    pub tools: ToolsConfig,
+    pub retry: RetryConfig,
+    pub bandwidth: BandwidthConfig,
+    /// Perfil de acesso usado quando `--profile` não é informado.
+    pub profile: Option<String>,
+    /// Cookies, proxy e user-agent por nome de perfil.
+    pub profiles: BTreeMap<String, AccessOptions>,
+    pub server: ServerConfig,
+    /// Canais e playlists acompanhados pelo `watch`.
+    pub subscriptions: Vec<Subscription>,
+    pub watch: WatchConfig,
+    pub verify: VerifyConfig,
+    /// Idioma das mensagens (`pt-BR`, `en`); sem valor, segue o `LANG`.
+    pub lang: Option<String>,
+}
+
+/// Limite de banda e janelas de horário.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BandwidthConfig {
+    /// Limite fora das janelas (`"1M"`, `"500K"`); sem valor, velocidade máxima.
+    pub limit_rate: Limit,
+    /// Janelas com limite próprio; vale a primeira que contém a hora atual.
+    pub schedule: Vec<Window>,
+}
+
+/// Janela de horário, como `22:00`–`07:00`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Window {
+    pub from: ClockTime,
+    pub to: ClockTime,
+    /// Limite dentro da janela; sem valor, velocidade máxima.
+    #[serde(default)]
+    pub limit_rate: Limit,
+}
+
+/// Verificação periódica das assinaturas (`watch`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchConfig {
+    /// Intervalo entre verificações, em minutos.
+    pub interval_mins: u64,
+    /// Quantos dos vídeos mais recentes de cada assinatura são conferidos.
+    pub playlist_items: usize,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        WatchConfig {
+            interval_mins: 30,
+            playlist_items: 15,
+        }
+    }
+}
+
+/// Verificação do arquivo baixado com o ffprobe.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VerifyConfig {
+    pub enabled: bool,
+    /// Diferença aceita entre a duração do arquivo e a dos metadados, em
+    /// segundos.
+    pub duration_tolerance_secs: f64,
+}
+
+impl Default for VerifyConfig {
+    fn default() -> Self {
+        VerifyConfig {
+            enabled: true,
+            duration_tolerance_secs: 3.0,
+        }
+    }
+}
+
+/// Modo servidor (`serve`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerConfig {
+    /// Token exigido como `Authorization: Bearer <token>`; sem valor, a API
+    /// fica aberta.
+    pub token: Option<String>,
+    /// Origens de navegador aceitas em `/enqueue` e `/pair`; um `*` no fim
+    /// aceita qualquer origem com aquele começo.
+    pub allowed_origins: Vec<String>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            token: None,
+            allowed_origins: [
+                "https://www.youtube.com",
+                "https://m.youtube.com",
+                "https://music.youtube.com",
+                "chrome-extension://*",
+                "moz-extension://*",
+            ]
+            .map(String::from)
+            .to_vec(),
+        }
+    }
+}
+
+/// Novas tentativas após falhas passageiras do download.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryConfig {
+    /// Total de tentativas por download, contando a primeira.
+    pub attempts: u32,
+    /// Espera antes da segunda tentativa, em milissegundos; dobra a cada
+    /// nova tentativa.
+    pub initial_delay_ms: u64,
+    /// Espera máxima entre tentativas, em milissegundos.
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        RetryConfig {
+            attempts: 3,
+            initial_delay_ms: 2000,
+            max_delay_ms: 60_000,
+        }
+    }
+}
+
+/// Cache da detecção de ferramentas externas.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolsConfig {
+    /// Por quanto tempo (em segundos) a versão detectada vale entre execuções.
+    pub cache_ttl_secs: u64,
+}
+
+impl Default for ToolsConfig {
+    fn default() -> Self {
+        ToolsConfig {
+            cache_ttl_secs: 24 * 60 * 60,
+        }
+    }
+}
+
+/// Limites usados pelo comando `doctor`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DoctorConfig {
+    /// Idade máxima (em dias) do yt-dlp antes de recomendar atualização.
+    pub max_ytdlp_age_days: u32,
+    /// Espaço livre mínimo, em MiB, no diretório de saída.
+    pub min_free_space_mib: u64,
+}
+
+impl Default for DoctorConfig {
+    fn default() -> Self {
+        DoctorConfig {
+            max_ytdlp_age_days: 60,
+            min_free_space_mib: 1024,
+        }
+    }
+}
+
+/// Como o yt-dlp é instalado quando não está disponível.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProvisionConfig {
+    /// Se a instalação pede confirmação, é feita direto ou nunca acontece.
+    pub confirm: ConfirmPolicy,
+    /// Métodos tentados, em ordem de preferência.
+    pub methods: Vec<InstallMethod>,
+    /// De onde baixar o binário autônomo.
+    pub binary_url: String,
+    /// Arquivo `SHA2-256SUMS` usado para verificar o binário.
+    pub checksums_url: Option<String>,
+    /// SHA-256 fixo do binário; tem precedência sobre `checksums_url`.
+    pub binary_sha256: Option<String>,
+}
+
+impl Default for ProvisionConfig {
+    fn default() -> Self {
+        ProvisionConfig {
+            confirm: ConfirmPolicy::Ask,
+            methods: vec![
+                InstallMethod::Pipx,
+                InstallMethod::Venv,
+                InstallMethod::Binary,
+            ],
+            binary_url: default_ytdlp_binary_url(),
+            checksums_url: Some(DEFAULT_YTDLP_CHECKSUMS_URL.to_string()),
+            binary_sha256: None,
+        }
+    }
+}
+
+impl Config {
+    pub fn path() -> PathBuf {
+        paths::config_dir().join("config.json")
+    }
+
+    /// Carrega a configuração; usa os valores padrão se o arquivo não existir
+    /// ou for inválido.
+    pub fn load() -> Config {
+        let content = match fs::read_to_string(Self::path()) {
+            Ok(content) => content,
+            Err(_) => return Config::default(),
+        };
+        match serde_json::from_str(&content) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    t!("config.invalid", path = Self::path().display(), error = e)
+                );
+                Config::default()
+            }
+        }
+    }
+
+    /// Como [`Config::load`], mas falha se o arquivo existir e for inválido
+    /// em vez de cair nos valores padrão. Para quem vai alterar e salvar a
+    /// configuração.
+    pub fn load_for_update() -> io::Result<Config> {
+        let content = match fs::read_to_string(Self::path()) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(e) => return Err(e),
+        };
+        serde_json::from_str(&content).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                t!(
+                    "config.refuse_save",
+                    path = Self::path().display(),
+                    error = e
+                ),
+            )
+        })
+    }
+
+    /// Grava a configuração. Recusa sobrescrever um arquivo que não pôde ser
+    /// lido: os valores padrão carregados no lugar dele apagariam perfis,
+    /// assinaturas e o resto do que o usuário configurou.
+    pub fn save(&self) -> io::Result<()> {
+        Self::load_for_update()?;
+        fs::create_dir_all(paths::config_dir())?;
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(Self::path(), json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_config_uses_defaults() {
+        let config: Config =
+            serde_json::from_str(r#"{"provision": {"confirm": "never"}}"#).unwrap();
+        assert_eq!(config.provision.confirm, ConfirmPolicy::Never);
+        assert_eq!(config.provision.binary_url, default_ytdlp_binary_url());
+        assert_eq!(config.provision.methods.len(), 3);
+    }
+}
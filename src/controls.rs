@@ -0,0 +1,412 @@
+//! Controles de teclado enquanto um download roda no modo interativo.
+//!
+//! `p` pausa ou retoma o yt-dlp, `c` cancela, `v` alterna a saída detalhada
+//! do yt-dlp e `b` manda o download para o segundo plano, devolvendo o prompt.
+//! O estado aparece na linha de progresso, redesenhada a cada evento.
+
+use colored::*;
+use crossterm::event::{Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, queue, terminal};
+use futures_util::StreamExt;
+use rustyline::ExternalPrinter;
+use std::io::{self, IsTerminal, Write};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+use youtube_video_downloader::config::Config;
+use youtube_video_downloader::provision;
+use youtube_video_downloader::{
+    download_with, t, DownloadError, DownloadOptions, Downloaded, JobControl, JobEvent, Progress,
+};
+
+type DownloadResult = Result<Downloaded, DownloadError>;
+
+/// Como terminou um download em primeiro plano.
+pub enum Outcome {
+    Finished(DownloadResult),
+    /// Continua rodando como o download `#id` em segundo plano.
+    Background(usize),
+}
+
+/// Estado mostrado na linha de progresso.
+#[derive(Debug, Default)]
+struct StatusLine {
+    progress: Option<Progress>,
+    paused: bool,
+    verbose: bool,
+    cancelling: bool,
+}
+
+impl StatusLine {
+    fn text(&self) -> String {
+        let state = match (&self.progress, self.cancelling, self.paused) {
+            (_, true, _) => format!("⏹️  {}", t!("controls.cancelling")),
+            (Some(p), _, true) => format!("⏸️  {:>5.1}% · {}", p.percent, t!("controls.paused")),
+            (None, _, true) => format!("⏸️  {}", t!("controls.paused")),
+            (Some(p), _, false) => format!(
+                "⬇️  {:>5.1}% · {} · ETA {}",
+                p.percent,
+                p.speed.as_deref().unwrap_or("-"),
+                p.eta.as_deref().unwrap_or("-")
+            ),
+            (None, _, false) => format!("⏳ {}", t!("controls.waiting")),
+        };
+        let verbose = if self.verbose {
+            format!(" · {}", t!("controls.verbose"))
+        } else {
+            String::new()
+        };
+        format!("{state}{verbose}  {}", t!("controls.keys"))
+    }
+
+    /// Redesenha a linha, cortada na largura do terminal para não quebrar.
+    fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        let width = terminal::size().map(|(w, _)| w as usize).unwrap_or(80);
+        let text: String = self.text().chars().take(width.saturating_sub(2)).collect();
+        queue!(
+            out,
+            cursor::MoveToColumn(0),
+            terminal::Clear(terminal::ClearType::CurrentLine)
+        )?;
+        write!(out, "{}", text.cyan())?;
+        out.flush()
+    }
+
+    /// Imprime uma mensagem acima da linha de progresso (em modo raw).
+    fn print(&self, out: &mut impl Write, message: &str) -> io::Result<()> {
+        queue!(
+            out,
+            cursor::MoveToColumn(0),
+            terminal::Clear(terminal::ClearType::CurrentLine)
+        )?;
+        for line in message.lines() {
+            write!(out, "{line}\r\n")?;
+        }
+        self.draw(out)
+    }
+
+    fn clear(&self, out: &mut impl Write) -> io::Result<()> {
+        queue!(
+            out,
+            cursor::MoveToColumn(0),
+            terminal::Clear(terminal::ClearType::CurrentLine)
+        )?;
+        out.flush()
+    }
+}
+
+/// Executa o download em primeiro plano atendendo às teclas de controle.
+///
+/// Sem um terminal interativo, apenas mostra as mensagens de status.
+pub async fn run(options: DownloadOptions, background: &mut Background) -> Outcome {
+    // A confirmação da instalação do yt-dlp lê o stdin, que o modo raw e a
+    // leitura das teclas tomariam
+    if let Err(e) = provision::ensure_yt_dlp(&Config::load().provision).await {
+        return Outcome::Finished(Err(e));
+    }
+
+    let control = JobControl::new();
+    let (tx, mut events) = mpsc::unbounded_channel();
+    let url = options.url.clone();
+    let task_control = control.clone();
+    let mut handle = tokio::spawn(async move {
+        download_with(&options, &task_control, move |event| {
+            let _ = tx.send(event);
+        })
+        .await
+    });
+
+    if !io::stdin().is_terminal() || terminal::enable_raw_mode().is_err() {
+        control.forward_ctrl_c();
+        loop {
+            tokio::select! {
+                Some(event) = events.recv() => {
+                    if let JobEvent::Status(message) = event {
+                        println!("{message}");
+                    }
+                }
+                result = &mut handle => return Outcome::Finished(joined(result)),
+            }
+        }
+    }
+
+    let mut out = io::stdout();
+    let mut line = StatusLine::default();
+    let mut keys = EventStream::new();
+    let _ = line.draw(&mut out);
+
+    let outcome = loop {
+        tokio::select! {
+            biased;
+            result = &mut handle => {
+                // Mensagens que chegaram junto com o fim do download
+                while let Ok(event) = events.try_recv() {
+                    show(&mut out, &line, event);
+                }
+                break Outcome::Finished(joined(result));
+            }
+            Some(event) = events.recv() => match event {
+                JobEvent::Progress(progress) => {
+                    line.progress = Some(progress);
+                    let _ = line.draw(&mut out);
+                }
+                event => show(&mut out, &line, event),
+            },
+            Some(Ok(Event::Key(key))) = keys.next() => match command(key) {
+                Some(Key::Pause) if !line.cancelling => {
+                    line.paused = control.toggle_pause();
+                    let _ = line.draw(&mut out);
+                }
+                Some(Key::Cancel) => {
+                    control.cancel();
+                    line.cancelling = true;
+                    let _ = line.draw(&mut out);
+                }
+                Some(Key::Verbose) => {
+                    line.verbose = !line.verbose;
+                    let _ = line.draw(&mut out);
+                }
+                Some(Key::Background) if !line.cancelling => {
+                    let id = background.adopt(url.clone(), control.clone(), handle);
+                    break Outcome::Background(id);
+                }
+                _ => {}
+            },
+        }
+    };
+
+    let _ = line.clear(&mut out);
+    let _ = terminal::disable_raw_mode();
+    outcome
+}
+
+fn show(out: &mut impl Write, line: &StatusLine, event: JobEvent) {
+    match event {
+        JobEvent::Status(message) => {
+            let _ = line.print(out, &message);
+        }
+        JobEvent::Log(message) if line.verbose => {
+            let _ = line.print(out, &message.dimmed().to_string());
+        }
+        _ => {}
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Key {
+    Pause,
+    Cancel,
+    Verbose,
+    Background,
+}
+
+fn command(key: KeyEvent) -> Option<Key> {
+    if key.kind != KeyEventKind::Press {
+        return None;
+    }
+    match key.code {
+        // Em modo raw o Ctrl-C chega como tecla, não como sinal
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Key::Cancel),
+        _ if !key.modifiers.difference(KeyModifiers::SHIFT).is_empty() => None,
+        KeyCode::Char('p' | 'P') => Some(Key::Pause),
+        KeyCode::Char('c' | 'C') => Some(Key::Cancel),
+        KeyCode::Char('v' | 'V') => Some(Key::Verbose),
+        KeyCode::Char('b' | 'B') => Some(Key::Background),
+        _ => None,
+    }
+}
+
+fn joined(result: Result<DownloadResult, tokio::task::JoinError>) -> DownloadResult {
+    result.unwrap_or_else(|e| Err(DownloadError::Io(io::Error::other(e))))
+}
+
+/// Um download em segundo plano que terminou.
+struct Finished {
+    id: usize,
+    url: String,
+    result: DownloadResult,
+    /// Já anunciado pelo `ExternalPrinter` enquanto o prompt estava ativo.
+    announced: bool,
+}
+
+type Printer = Arc<Mutex<Box<dyn ExternalPrinter + Send>>>;
+
+/// Downloads mandados para o segundo plano com `b`.
+pub struct Background {
+    next_id: usize,
+    running: Vec<(usize, JobControl)>,
+    printer: Option<Printer>,
+    tx: UnboundedSender<Finished>,
+    rx: UnboundedReceiver<Finished>,
+}
+
+impl Background {
+    /// `printer` permite avisar do fim de um download sem atrapalhar o prompt.
+    pub fn new(printer: Option<Box<dyn ExternalPrinter + Send>>) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        Background {
+            next_id: 1,
+            running: Vec::new(),
+            printer: printer.map(|printer| Arc::new(Mutex::new(printer))),
+            tx,
+            rx,
+        }
+    }
+
+    fn adopt(
+        &mut self,
+        url: String,
+        control: JobControl,
+        handle: JoinHandle<DownloadResult>,
+    ) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        // Pausado não terminaria nunca
+        if control.is_paused() {
+            control.resume();
+        }
+        self.running.push((id, control));
+        let tx = self.tx.clone();
+        let printer = self.printer.clone();
+        tokio::spawn(async move {
+            let result = joined(handle.await);
+            let announced = printer
+                .and_then(|printer| {
+                    let message = format!("{}\n", finished_message(id, &url, &result));
+                    printer.lock().ok()?.print(message).ok()
+                })
+                .is_some();
+            let _ = tx.send(Finished {
+                id,
+                url,
+                result,
+                announced,
+            });
+        });
+        id
+    }
+
+    /// Recolhe os downloads que terminaram, anunciando os que ainda não
+    /// foram; retorna quantos foram concluídos com sucesso.
+    pub fn collect(&mut self) -> u32 {
+        let mut completed = 0;
+        while let Ok(finished) = self.rx.try_recv() {
+            self.running.retain(|(id, _)| *id != finished.id);
+            if !finished.announced {
+                println!(
+                    "{}",
+                    finished_message(finished.id, &finished.url, &finished.result)
+                );
+            }
+            if finished.result.is_ok() {
+                completed += 1;
+            }
+        }
+        completed
+    }
+
+    /// Cancela os downloads em segundo plano e espera que terminem.
+    pub async fn shutdown(&mut self) -> u32 {
+        let mut completed = self.collect();
+        if self.running.is_empty() {
+            return completed;
+        }
+        println!(
+            "{}",
+            t!("controls.bg_cancelling", count = self.running.len()).yellow()
+        );
+        for (_, control) in &self.running {
+            control.cancel();
+        }
+        while !self.running.is_empty() {
+            let Some(finished) = self.rx.recv().await else {
+                break;
+            };
+            self.running.retain(|(id, _)| *id != finished.id);
+            if finished.result.is_ok() {
+                completed += 1;
+            }
+        }
+        completed
+    }
+}
+
+fn finished_message(id: usize, url: &str, result: &DownloadResult) -> String {
+    match result {
+        Ok(_) => t!("controls.bg_done", id = id, url = url)
+            .green()
+            .to_string(),
+        Err(DownloadError::Cancelled) => t!("controls.bg_cancelled", id = id).yellow().to_string(),
+        Err(e) => t!("controls.bg_failed", id = id, error = e)
+            .red()
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_key_commands() {
+        let none = KeyModifiers::NONE;
+        assert_eq!(command(key(KeyCode::Char('p'), none)), Some(Key::Pause));
+        assert_eq!(command(key(KeyCode::Char('c'), none)), Some(Key::Cancel));
+        assert_eq!(
+            command(key(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(Key::Cancel)
+        );
+        assert_eq!(
+            command(key(KeyCode::Char('V'), KeyModifiers::SHIFT)),
+            Some(Key::Verbose)
+        );
+        assert_eq!(
+            command(key(KeyCode::Char('b'), none)),
+            Some(Key::Background)
+        );
+        assert_eq!(command(key(KeyCode::Char('b'), KeyModifiers::ALT)), None);
+        assert_eq!(command(key(KeyCode::Enter, none)), None);
+    }
+
+    #[test]
+    fn test_status_line_reflects_state() {
+        let mut line = StatusLine::default();
+        assert!(line.text().contains(&t!("controls.waiting")));
+
+        line.progress = Some(Progress {
+            percent: 42.5,
+            speed: Some("1.00MiB/s".into()),
+            eta: Some("00:05".into()),
+            total: None,
+        });
+        assert!(line
+            .text()
+            .starts_with("⬇️   42.5% · 1.00MiB/s · ETA 00:05"));
+
+        line.paused = true;
+        line.verbose = true;
+        let text = line.text();
+        assert!(text.starts_with("⏸️   42.5%"));
+        assert!(text.contains(&t!("controls.paused")));
+        assert!(text.contains(&t!("controls.verbose")));
+
+        line.cancelling = true;
+        assert!(line.text().contains(&t!("controls.cancelling")));
+    }
+
+    #[tokio::test]
+    async fn test_background_jobs_are_collected() {
+        let mut background = Background::new(None);
+        let ok = tokio::spawn(async { Ok(Downloaded::default()) });
+        let failed = tokio::spawn(async { Err(DownloadError::Cancelled) });
+        assert_eq!(background.adopt("a".into(), JobControl::new(), ok), 1);
+        assert_eq!(background.adopt("b".into(), JobControl::new(), failed), 2);
+
+        assert_eq!(background.shutdown().await, 1);
+        assert!(background.running.is_empty());
+    }
+}
@@ -0,0 +1,272 @@
+//! Diagnóstico do ambiente (`doctor`): versões das ferramentas, idade do
+//! yt-dlp, permissão de escrita e espaço livre no diretório de saída.
+
+use crate::config::DoctorConfig;
+use crate::provision::Installation;
+use crate::stats;
+use crate::tools::{self, Tool};
+use colored::*;
+use std::fs;
+use std::path::Path;
+
+/// Gravidade de uma verificação.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// Resultado de uma verificação individual.
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub severity: Severity,
+    /// Identificador estável da verificação, independente do idioma.
+    pub id: &'static str,
+    pub label: String,
+    pub detail: String,
+    /// Passo sugerido para resolver o problema.
+    pub remedy: Option<String>,
+}
+
+impl Check {
+    fn ok(id: &'static str, detail: String) -> Self {
+        Check {
+            severity: Severity::Ok,
+            id,
+            label: label(id),
+            detail,
+            remedy: None,
+        }
+    }
+
+    fn problem(severity: Severity, id: &'static str, detail: String, remedy: String) -> Self {
+        Check {
+            severity,
+            id,
+            label: label(id),
+            detail,
+            remedy: Some(remedy),
+        }
+    }
+}
+
+/// Rótulo exibido para uma verificação; ferramentas usam o próprio nome.
+fn label(id: &'static str) -> String {
+    match id {
+        "output" => t!("doctor.label_output"),
+        "space" => t!("doctor.label_space"),
+        id => id.to_string(),
+    }
+}
+
+/// Relatório completo do `doctor`.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    pub fn has_errors(&self) -> bool {
+        self.checks.iter().any(|c| c.severity == Severity::Error)
+    }
+
+    pub fn check(&self, id: &str) -> Option<&Check> {
+        self.checks.iter().find(|c| c.id == id)
+    }
+
+    pub fn print(&self) {
+        println!("{}", t!("doctor.title").cyan().bold());
+        println!();
+        for check in &self.checks {
+            let icon = match check.severity {
+                Severity::Ok => "✅",
+                Severity::Warning => "⚠️ ",
+                Severity::Error => "❌",
+            };
+            let detail = match check.severity {
+                Severity::Ok => check.detail.normal(),
+                Severity::Warning => check.detail.yellow(),
+                Severity::Error => check.detail.red(),
+            };
+            println!("{icon} {:<12} {detail}", check.label.bold());
+        }
+
+        let remedies: Vec<_> = self
+            .checks
+            .iter()
+            .filter_map(|c| c.remedy.as_ref().map(|r| (&c.label, r)))
+            .collect();
+        println!();
+        if remedies.is_empty() {
+            println!("{}", t!("doctor.all_good").green().bold());
+        } else {
+            println!("{}", t!("doctor.how_to_fix").yellow().bold());
+            for (label, remedy) in remedies {
+                println!("  • {}: {remedy}", label.bold());
+            }
+        }
+        println!();
+    }
+}
+
+/// Executa todas as verificações para o diretório de saída informado.
+pub fn diagnose(output_dir: &Path, config: &DoctorConfig) -> Report {
+    let checks = vec![
+        check_yt_dlp(config.max_ytdlp_age_days, (stats::now() / 86_400) as i64),
+        check_tool(Tool::Ffmpeg, Severity::Error, t!("doctor.fix_ffmpeg")),
+        check_tool(Tool::Ffprobe, Severity::Warning, t!("doctor.fix_ffprobe")),
+        check_tool(Tool::Python, Severity::Warning, t!("doctor.fix_python")),
+        check_writable(output_dir),
+        check_free_space(output_dir, config.min_free_space_mib),
+    ];
+    Report { checks }
+}
+
+fn check_tool(tool: Tool, missing: Severity, remedy: String) -> Check {
+    match tools::detect_fresh(tool) {
+        Some(info) => Check::ok(tool.name(), info.version),
+        None => Check::problem(missing, tool.name(), t!("doctor.not_found"), remedy),
+    }
+}
+
+fn check_yt_dlp(max_age_days: u32, today: i64) -> Check {
+    let Some(info) = tools::detect_fresh(Tool::YtDlp) else {
+        return Check::problem(
+            Severity::Error,
+            "yt-dlp",
+            t!("doctor.not_found"),
+            t!("doctor.fix_ytdlp_missing"),
+        );
+    };
+
+    let origin = match Installation::load() {
+        Some(installation) => t!("doctor.managed_via", method = installation.method),
+        None => String::new(),
+    };
+    let detail = format!("{}{origin}", info.version);
+
+    match release_day(&info.version).map(|day| today - day) {
+        Some(age) if age > i64::from(max_age_days) => Check::problem(
+            Severity::Warning,
+            "yt-dlp",
+            t!("doctor.ytdlp_age", detail = detail, age = age),
+            t!("doctor.fix_ytdlp_old"),
+        ),
+        _ => Check::ok("yt-dlp", detail),
+    }
+}
+
+fn check_writable(dir: &Path) -> Check {
+    let probe = dir.join(format!(".ytd-doctor-{}", std::process::id()));
+    match fs::write(&probe, b"") {
+        Ok(()) => {
+            let _ = fs::remove_file(&probe);
+            Check::ok("output", t!("doctor.writable", dir = dir.display()))
+        }
+        Err(e) => Check::problem(
+            Severity::Error,
+            "output",
+            t!("doctor.not_writable", dir = dir.display(), error = e),
+            t!("doctor.fix_writable"),
+        ),
+    }
+}
+
+fn check_free_space(dir: &Path, min_mib: u64) -> Check {
+    match free_space(dir) {
+        Some(bytes) if bytes / (1024 * 1024) < min_mib => Check::problem(
+            Severity::Warning,
+            "space",
+            t!("doctor.low_space", free = format_bytes(bytes)),
+            t!("doctor.fix_space"),
+        ),
+        Some(bytes) => Check::ok("space", t!("doctor.free_space", free = format_bytes(bytes))),
+        None => Check::ok("space", t!("doctor.space_unknown")),
+    }
+}
+
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)]
+fn free_space(dir: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(dir.as_os_str().as_bytes()).ok()?;
+    // SAFETY: `statvfs` apenas preenche a estrutura apontada.
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+fn free_space(_dir: &Path) -> Option<u64> {
+    None
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// Dia (desde a época Unix) em que uma versão do yt-dlp (`AAAA.MM.DD[.N]`) foi lançada.
+fn release_day(version: &str) -> Option<i64> {
+    let mut parts = version.split('.');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    Some(days_from_civil(year, month, day))
+}
+
+/// Número de dias desde 1970-01-01 para uma data do calendário gregoriano.
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let month = i64::from(month);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_from_civil() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(2024, 5, 27), 19_870);
+    }
+
+    #[test]
+    fn test_release_day() {
+        assert_eq!(release_day("2024.05.27"), Some(19_870));
+        assert_eq!(release_day("2024.05.27.232847"), Some(19_870));
+        assert_eq!(release_day("desconhecida"), None);
+        assert_eq!(release_day("2024.13.01"), None);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.0 GiB");
+    }
+}
@@ -0,0 +1,920 @@
+use crate::access::{self, AccessOptions};
+use crate::archive;
+use crate::bandwidth::{self, Limit};
+use crate::clip::Section;
+use crate::config::{Config, VerifyConfig};
+use crate::error::DownloadError;
+use crate::job::{parse_progress, JobControl, JobEvent};
+use crate::journal;
+use crate::live::{self, LiveOptions};
+use crate::loudness::{self, Loudness, LoudnessOptions};
+use crate::preset::{self, Preset};
+use crate::provision;
+use crate::retry::{self, ErrorClass};
+use crate::search;
+use crate::stats;
+use crate::tools::{self, Tool};
+use crate::verify::{self, MediaInfo};
+use colored::*;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::Notify;
+
+/// Nome de saída a partir do título e do id do vídeo, para downloads em lote
+/// ou simultâneos que não podem compartilhar um nome fixo.
+pub const TITLE_OUTPUT: &str = "%(title)s [%(id)s]";
+
+/// Prefixo da linha de metadados impressa pelo yt-dlp ao fim do download.
+const META_PREFIX: &str = "ytd-meta";
+
+/// Parâmetros de um download.
+#[derive(Debug, Clone)]
+pub struct DownloadOptions {
+    pub url: String,
+    /// Caminho de saída, sem extensão.
+    pub output_name: String,
+    pub audio_only: bool,
+    /// Formato para o qual converter o áudio (`-x --audio-format`); `None`
+    /// mantém o áudio original, dispensando o ffmpeg.
+    pub audio_format: Option<String>,
+    /// Total de tentativas; `None` segue o `retry.attempts` da configuração.
+    pub attempts: Option<u32>,
+    /// Continua os arquivos parciais de um download interrompido (`--continue`).
+    pub resume: bool,
+    /// Cookies, proxy e user-agent; completados pelas flags da execução e
+    /// pelo perfil da configuração.
+    pub access: AccessOptions,
+    /// Velocidade máxima; [`download_with`] a define pela configuração
+    /// `bandwidth` ao iniciar cada execução.
+    pub limit_rate: Limit,
+    /// Grava uma transmissão ao vivo, esperando-a começar se for agendada.
+    pub live: Option<LiveOptions>,
+    /// Normaliza o volume do áudio extraído (EBU R128) ou só o mede.
+    pub loudness: Option<LoudnessOptions>,
+    /// Converte o arquivo para um aparelho com limitações de codec,
+    /// resolução ou tamanho.
+    pub preset: Option<Preset>,
+    /// Baixa só um trecho do vídeo.
+    pub section: Option<Section>,
+}
+
+impl DownloadOptions {
+    pub fn new(url: &str, output_name: &str, audio_only: bool) -> Self {
+        DownloadOptions {
+            url: url.to_string(),
+            output_name: output_name.to_string(),
+            audio_only,
+            audio_format: audio_only.then(|| "mp3".to_string()),
+            attempts: None,
+            resume: false,
+            limit_rate: Limit::Unlimited,
+            access: AccessOptions::default(),
+            live: None,
+            loudness: None,
+            preset: None,
+            section: None,
+        }
+    }
+
+    /// Ferramentas externas necessárias para este download.
+    pub fn requirements(&self) -> Vec<Tool> {
+        let mut tools = Vec::new();
+        // Juntar vídeo e áudio separados, converter o áudio, gravar e
+        // dividir uma live, cortar um trecho, normalizar o volume ou aplicar
+        // um preset exige o ffmpeg
+        let merges = !self.audio_only
+            || self.live.is_some()
+            || self.section.is_some()
+            || self.loudness.is_some()
+            || self.preset.is_some();
+        let extracts_audio = self.audio_only && self.audio_format.is_some();
+        if merges || extracts_audio {
+            tools.push(Tool::Ffmpeg);
+        }
+        tools.push(Tool::YtDlp);
+        tools
+    }
+}
+
+/// Resultado de um download concluído.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Downloaded {
+    /// Arquivo final, como informado pelo yt-dlp.
+    pub path: Option<PathBuf>,
+    pub channel: Option<String>,
+    pub duration_secs: Option<f64>,
+    pub bytes: u64,
+    /// Quantas execuções do yt-dlp foram necessárias.
+    pub attempts: u32,
+    /// Partes de uma gravação dividida; vazio quando há um arquivo só.
+    pub segments: Vec<PathBuf>,
+    /// Faixas e duração conferidas pelo ffprobe; em caixa, como o volume.
+    pub media: Option<Box<MediaInfo>>,
+    /// Volume medido e, se normalizado, o resultante; em caixa para não
+    /// pesar no resultado de todo download.
+    pub loudness: Option<Box<Loudness>>,
+}
+
+/// Baixa um vídeo (ou apenas o áudio em MP3) usando o yt-dlp.
+pub async fn download_video(
+    url: &str,
+    output_name: &str,
+    audio_only: bool,
+) -> Result<Downloaded, DownloadError> {
+    download(&DownloadOptions::new(url, output_name, audio_only)).await
+}
+
+/// Executa um download, verificando apenas as ferramentas que ele exige.
+///
+/// Falhas passageiras do yt-dlp são repetidas conforme a configuração
+/// `retry`. Downloads concluídos e falhas do yt-dlp são registrados nas
+/// estatísticas.
+pub async fn download(options: &DownloadOptions) -> Result<Downloaded, DownloadError> {
+    let control = JobControl::new();
+    control.forward_ctrl_c();
+    download_with(options, &control, print_event).await
+}
+
+/// Como [`download`], mas repassa cada [`JobEvent`] a `on_event` em vez de
+/// imprimi-lo e aceita um [`JobControl`] para pausar ou cancelar.
+pub async fn download_with(
+    options: &DownloadOptions,
+    control: &JobControl,
+    mut on_event: impl FnMut(JobEvent),
+) -> Result<Downloaded, DownloadError> {
+    let url = options.url.as_str();
+    let mut status = |message: String| on_event(JobEvent::Status(message));
+
+    // Validar URL do YouTube
+    if !is_youtube_url(url) {
+        return Err(DownloadError::InvalidUrl(url.to_string()));
+    }
+
+    let config = Config::load();
+    let access = access::resolve(&config, &options.access)?;
+    let options = &DownloadOptions {
+        access,
+        ..options.clone()
+    };
+
+    let mut yt_dlp = None;
+    for tool in options.requirements() {
+        status(t!("download.checking_tool", tool = tool));
+        let info = match tool {
+            // Instala uma cópia gerenciada do yt-dlp se preciso
+            Tool::YtDlp => provision::ensure_yt_dlp(&config.provision).await?,
+            tool => match tools::detect(tool) {
+                Some(info) => info,
+                None => {
+                    status(
+                        t!("download.tool_missing", tool = tool)
+                            .red()
+                            .bold()
+                            .to_string(),
+                    );
+                    return Err(DownloadError::MissingDependency(tool.name()));
+                }
+            },
+        };
+        status(format!("✅ {tool} {}", info.version));
+        if tool == Tool::YtDlp {
+            yt_dlp = Some(info);
+        }
+    }
+    let yt_dlp = yt_dlp.expect("o yt-dlp é sempre exigido");
+
+    status(t!("download.fetching_info"));
+
+    // Detectar se é um YouTube Short
+    let is_short = url.contains("/shorts/");
+    if is_short {
+        status(t!("download.short_detected"));
+    }
+
+    match (options.audio_only, &options.audio_format) {
+        (true, Some(format)) => status(t!("download.audio_format", format = format.to_uppercase())),
+        (true, None) => status(t!("download.audio_original")),
+        (false, _) => status(t!("download.video_mp4")),
+    }
+
+    if let Some(live) = &options.live {
+        // Só informativo: o yt-dlp espera e grava mesmo sem esta consulta
+        if let Ok(video) = search::video_info(url, &options.access).await {
+            status(live::describe(&video, stats::now()));
+        }
+        if live.from_start {
+            status(t!("live.from_start"));
+        }
+        if let Some(limit) = live.max_duration {
+            status(t!(
+                "live.limited",
+                duration = stats::format_duration(limit.as_secs_f64())
+            ));
+        }
+    }
+
+    if options.access.cookies.is_some() {
+        status(t!("access.using_cookies"));
+    }
+    if let Some(proxy) = &options.access.proxy {
+        status(t!("access.using_proxy", proxy = access::redact(proxy)));
+    }
+
+    // Um trecho é só matéria-prima de uma animação: não conta como o vídeo
+    // baixado no diário, nas estatísticas nem no arquivo de baixados
+    let clip = options.section.is_some();
+
+    // Registrar o job antes de começar, para retomá-lo se o processo morrer;
+    // uma live interrompida não tem como ser retomada
+    let entry = if options.live.is_none() && !clip {
+        journal::begin(options).ok()
+    } else {
+        None
+    };
+
+    // Executar o download, tentando de novo após falhas passageiras
+    let attempts = options.attempts.unwrap_or(config.retry.attempts).max(1);
+    let mut attempt = 1;
+    let result = loop {
+        let mut track = |event: JobEvent| {
+            if let (Some(entry), JobEvent::Log(line)) = (&entry, &event) {
+                if let Some(destination) = journal::parse_destination(line) {
+                    let _ = journal::add_destination(entry, destination);
+                }
+            }
+            on_event(event);
+        };
+        // O limite é decidido a cada execução, seguindo a janela de horário atual
+        let limit = bandwidth::current_limit(&config.bandwidth);
+        if let Limit::Rate(_) = limit {
+            track(JobEvent::Status(t!("bandwidth.limited", limit = limit)));
+        }
+        let attempt_options = DownloadOptions {
+            limit_rate: limit,
+            ..options.clone()
+        };
+        let result = match run_attempt(&attempt_options, &yt_dlp.program, control, &mut track).await
+        {
+            Ok(downloaded) => {
+                let retry_follows = attempt < attempts;
+                verify_download(
+                    options,
+                    downloaded,
+                    &config.verify,
+                    retry_follows,
+                    &mut track,
+                )
+                .await
+            }
+            Err(err) => Err(err),
+        };
+        match result {
+            Err(err) if attempt < attempts && retry::classify(&err) == ErrorClass::Retryable => {
+                let delay = retry::backoff(&config.retry, attempt, &mut rand::thread_rng());
+                on_event(JobEvent::Status(
+                    t!(
+                        "retry.waiting",
+                        attempt = attempt,
+                        attempts = attempts,
+                        seconds = format!("{:.1}", delay.as_secs_f64()),
+                        error = retry::summary(&err)
+                    )
+                    .yellow()
+                    .to_string(),
+                ));
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = control.cancelled() => break Err(DownloadError::Cancelled),
+                }
+                attempt += 1;
+            }
+            result => break result,
+        }
+    };
+
+    // Só uma falha passageira deixa o job no diário para ser retomado
+    let resumable = matches!(&result, Err(err) if retry::classify(err) == ErrorClass::Retryable);
+    if let Some(entry) = &entry {
+        let _ = if resumable {
+            journal::release(entry)
+        } else {
+            journal::finish(entry)
+        };
+    }
+
+    let downloaded = match result {
+        Ok(downloaded) => downloaded,
+        Err(DownloadError::Cancelled) => return Err(DownloadError::Cancelled),
+        Err(err) => {
+            if !clip
+                && matches!(
+                    err,
+                    DownloadError::Backend { .. } | DownloadError::Verification(_)
+                )
+            {
+                // Falhas ao gravar as estatísticas não mudam o resultado do download
+                let _ = stats::record(&stats::Record::new(url, false));
+            }
+            if attempt == 1 {
+                return Err(err);
+            }
+            // Só esgotou as tentativas quem falhou na última com um erro
+            // passageiro; um erro permanente interrompe antes
+            if attempt == attempts && retry::classify(&err) == ErrorClass::Retryable {
+                return Err(DownloadError::RetriesExhausted {
+                    attempts: attempt,
+                    last: Box::new(err),
+                });
+            }
+            on_event(JobEvent::Status(
+                t!("retry.gave_up", attempt = attempt, attempts = attempts)
+                    .yellow()
+                    .to_string(),
+            ));
+            return Err(err);
+        }
+    };
+
+    let downloaded = match (&options.live, &downloaded.path) {
+        (
+            Some(LiveOptions {
+                segment: Some(segment),
+                ..
+            }),
+            Some(path),
+        ) if downloaded
+            .duration_secs
+            .is_none_or(|secs| secs > segment.as_secs_f64()) =>
+        {
+            match live::split(path, *segment).await {
+                Ok(segments) if !segments.is_empty() => {
+                    on_event(JobEvent::Status(t!(
+                        "live.split_done",
+                        count = segments.len()
+                    )));
+                    Downloaded {
+                        path: segments.first().cloned(),
+                        segments,
+                        ..downloaded
+                    }
+                }
+                Ok(_) => downloaded,
+                Err(e) => {
+                    let message = t!("live.split_failed", error = e);
+                    on_event(JobEvent::Status(message.yellow().to_string()));
+                    downloaded
+                }
+            }
+        }
+        _ => downloaded,
+    };
+
+    let downloaded = match (options.preset, downloaded.path.clone()) {
+        (Some(preset), Some(path)) => {
+            apply_preset(&path, preset, options.audio_only, downloaded, &mut on_event).await
+        }
+        _ => downloaded,
+    };
+
+    let downloaded = match (&options.loudness, downloaded.path.clone()) {
+        (Some(loudness), Some(path)) => {
+            adjust_loudness(&path, loudness, downloaded, &mut on_event).await
+        }
+        _ => downloaded,
+    };
+
+    let saved = if options.audio_only {
+        t!("download.audio_saved")
+    } else {
+        t!("download.video_saved")
+    };
+    let saved = match &downloaded.media {
+        Some(media) => t!(
+            "download.saved_verified",
+            saved = saved,
+            details = media.summary()
+        ),
+        None => saved,
+    };
+    on_event(JobEvent::Status(saved.green().bold().to_string()));
+    if attempt > 1 {
+        on_event(JobEvent::Status(t!("retry.succeeded", attempts = attempt)));
+    }
+
+    if !clip {
+        let _ = stats::record(&stats::Record {
+            channel: downloaded.channel.clone(),
+            bytes: downloaded.bytes,
+            duration_secs: downloaded.duration_secs,
+            ..stats::Record::new(url, true)
+        });
+        if let Some(id) = archive::video_id(url) {
+            let _ = archive::add([id.as_str()]);
+        }
+    }
+    Ok(Downloaded {
+        attempts: attempt,
+        ..downloaded
+    })
+}
+
+/// Uma execução do yt-dlp, do início ao fim.
+async fn run_attempt(
+    options: &DownloadOptions,
+    program: &Path,
+    control: &JobControl,
+    on_event: &mut impl FnMut(JobEvent),
+) -> Result<Downloaded, DownloadError> {
+    // Executar download acompanhando a saída linha a linha
+    let mut cmd = Command::new(program);
+    cmd.args(build_ytdlp_args(options))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    // Num grupo próprio, pausar ou cancelar alcança também os ffmpeg que o
+    // yt-dlp inicia
+    #[cfg(unix)]
+    cmd.process_group(0);
+    // Numa live com duração máxima, o relógio corre a partir do início da
+    // gravação, não da espera por uma live agendada
+    let started = Notify::new();
+    let stopped = AtomicBool::new(false);
+    let mut destination = None;
+    let mut track = |event: JobEvent| {
+        if live::recording_started(&event) {
+            started.notify_one();
+        }
+        if let JobEvent::Log(line) = &event {
+            destination = journal::parse_destination(line).or(destination.take());
+        }
+        on_event(event);
+    };
+    let stop_at_limit = async {
+        if let Some(limit) = options.live.as_ref().and_then(|live| live.max_duration) {
+            started.notified().await;
+            tokio::time::sleep(limit).await;
+            stopped.store(control.interrupt(), Ordering::SeqCst);
+        }
+        std::future::pending::<()>().await
+    };
+    let run = tokio::select! {
+        run = run_backend(cmd, control, &mut track) => run,
+        _ = stop_at_limit => unreachable!(),
+    };
+    control.detach();
+    let stopped = stopped.load(Ordering::SeqCst);
+    if stopped {
+        on_event(JobEvent::Status(t!("live.limit_reached")));
+    }
+    let (exit, meta, stderr) = match run {
+        Ok(Some(output)) => output,
+        Ok(None) => return Err(DownloadError::Cancelled),
+        Err(err) => return Err(err.into()),
+    };
+
+    // Parado no tempo limite, o yt-dlp pode sair com erro, como num Ctrl+C,
+    // e sem imprimir os metadados; o que foi gravado até ali vale
+    let downloaded = match meta.as_deref() {
+        Some(meta) => parse_meta(meta),
+        None if stopped => recorded(destination),
+        None => Downloaded::default(),
+    };
+    let kept = stopped && downloaded.path.as_ref().is_some_and(|path| path.exists());
+    if !exit.success() && !kept {
+        return Err(DownloadError::Backend {
+            status: exit.code(),
+            stderr,
+        });
+    }
+    Ok(downloaded)
+}
+
+/// A gravação interrompida, pelo último destino informado pelo yt-dlp.
+fn recorded(destination: Option<PathBuf>) -> Downloaded {
+    let bytes = destination
+        .as_ref()
+        .and_then(|path| fs::metadata(path).ok())
+        .map_or(0, |metadata| metadata.len());
+    Downloaded {
+        path: destination,
+        bytes,
+        ..Downloaded::default()
+    }
+}
+
+/// Confere o arquivo com o ffprobe. Se outra tentativa vier a seguir, um
+/// arquivo recusado é apagado, para que ela o baixe de novo em vez de o
+/// yt-dlp dá-lo por baixado; na última, ele fica para o usuário examinar.
+async fn verify_download(
+    options: &DownloadOptions,
+    downloaded: Downloaded,
+    config: &VerifyConfig,
+    retry_follows: bool,
+    on_event: &mut impl FnMut(JobEvent),
+) -> Result<Downloaded, DownloadError> {
+    let Some(path) = downloaded.path.clone().filter(|_| config.enabled) else {
+        return Ok(downloaded);
+    };
+    // A duração de uma live gravada não é a dos metadados; a de um trecho é
+    // a do intervalo pedido
+    let expected = match (&options.live, options.section) {
+        (Some(_), _) => None,
+        (None, Some(section)) => downloaded
+            .duration_secs
+            .map(|secs| section.expected_secs(secs)),
+        (None, None) => downloaded.duration_secs,
+    };
+    let problem = match verify::probe(&path).await {
+        Ok(Some(media)) => match verify::check(&media, options.audio_only, expected, config) {
+            Ok(()) => {
+                return Ok(Downloaded {
+                    media: Some(Box::new(media)),
+                    ..downloaded
+                })
+            }
+            Err(problem) => problem,
+        },
+        Ok(None) => {
+            on_event(JobEvent::Status(
+                t!("verify.no_ffprobe").yellow().to_string(),
+            ));
+            return Ok(downloaded);
+        }
+        Err(problem) => problem,
+    };
+    if retry_follows {
+        let _ = fs::remove_file(&path);
+    }
+    Err(DownloadError::Verification(t!(
+        "verify.failed",
+        path = path.display(),
+        problem = problem
+    )))
+}
+
+/// Converte o arquivo baixado conforme o preset e o confere de novo, para a
+/// mensagem final mostrar a resolução e os codecs convertidos. Como na
+/// normalização, uma falha só gera um aviso e mantém o arquivo original.
+async fn apply_preset(
+    path: &Path,
+    preset: Preset,
+    audio_only: bool,
+    downloaded: Downloaded,
+    on_event: &mut impl FnMut(JobEvent),
+) -> Downloaded {
+    on_event(JobEvent::Status(t!("preset.converting", preset = preset)));
+    // A duração medida pelo ffprobe é mais confiável que a dos metadados
+    let duration = downloaded
+        .media
+        .as_ref()
+        .and_then(|media| media.duration_secs)
+        .or(downloaded.duration_secs);
+    let output = match preset::transcode(path, preset, audio_only, duration).await {
+        Ok(output) => output,
+        Err(e) => {
+            let message = t!("preset.kept", preset = preset, error = e);
+            on_event(JobEvent::Status(message.yellow().to_string()));
+            return downloaded;
+        }
+    };
+    let bytes = fs::metadata(&output).map_or(0, |m| m.len());
+    on_event(JobEvent::Status(t!(
+        "preset.done",
+        preset = preset,
+        size = format!("{:.1}", bytes as f64 / 1_000_000.0)
+    )));
+    let media = match downloaded.media {
+        Some(_) => verify::probe(&output).await.ok().flatten().map(Box::new),
+        None => None,
+    };
+    Downloaded {
+        path: Some(output),
+        bytes,
+        media,
+        ..downloaded
+    }
+}
+
+/// Mede e normaliza o volume do arquivo baixado. Uma falha só gera um aviso:
+/// o download em si deu certo.
+async fn adjust_loudness(
+    path: &Path,
+    options: &LoudnessOptions,
+    downloaded: Downloaded,
+    on_event: &mut impl FnMut(JobEvent),
+) -> Downloaded {
+    on_event(JobEvent::Status(t!("loudness.analyzing")));
+    // Recodificar com o bitrate do original, quando o ffprobe o informou
+    let bitrate = downloaded
+        .media
+        .as_ref()
+        .and_then(|media| media.audio_bitrate);
+    let (loudness, sidecar) = match loudness::process(path, options, bitrate).await {
+        Ok(result) => result,
+        Err(e) => {
+            let message = t!("loudness.failed", error = e);
+            on_event(JobEvent::Status(message.yellow().to_string()));
+            return downloaded;
+        }
+    };
+    let input = &loudness.input;
+    on_event(JobEvent::Status(t!(
+        "loudness.measured",
+        integrated = format!("{:.1}", input.integrated),
+        peak = format!("{:.1}", input.true_peak),
+        range = format!("{:.1}", input.range)
+    )));
+    if let Some(output) = &loudness.output {
+        on_event(JobEvent::Status(t!(
+            "loudness.normalized",
+            target = loudness.target_lufs,
+            integrated = format!("{:.1}", output.integrated),
+            peak = format!("{:.1}", output.true_peak)
+        )));
+    }
+    on_event(JobEvent::Status(t!(
+        "loudness.sidecar",
+        path = sidecar.display()
+    )));
+    Downloaded {
+        bytes: fs::metadata(path).map_or(downloaded.bytes, |m| m.len()),
+        loudness: Some(Box::new(loudness)),
+        ..downloaded
+    }
+}
+
+/// Saída de uma execução do yt-dlp: status, última linha de metadados e stderr.
+type BackendOutput = (ExitStatus, Option<String>, String);
+
+/// Roda o yt-dlp repassando progresso e logs. `Ok(None)` se foi cancelado.
+async fn run_backend(
+    mut cmd: Command,
+    control: &JobControl,
+    on_event: &mut impl FnMut(JobEvent),
+) -> std::io::Result<Option<BackendOutput>> {
+    let mut child = cmd.spawn()?;
+    control.attach(child.id());
+    let mut group = KillGroup {
+        control,
+        armed: true,
+    };
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout em pipe")).lines();
+    let mut stderr_lines = BufReader::new(child.stderr.take().expect("stderr em pipe")).lines();
+    let (mut stdout_open, mut stderr_open) = (true, true);
+    let mut meta = None;
+    let mut stderr = String::new();
+
+    while stdout_open || stderr_open {
+        tokio::select! {
+            line = stdout.next_line(), if stdout_open => match line? {
+                Some(line) if line.starts_with(META_PREFIX) => meta = Some(line),
+                Some(line) => match parse_progress(&line) {
+                    Some(progress) => on_event(JobEvent::Progress(progress)),
+                    None => on_event(JobEvent::Log(access::redact(&line).into_owned())),
+                },
+                None => stdout_open = false,
+            },
+            line = stderr_lines.next_line(), if stderr_open => match line? {
+                Some(line) => {
+                    // Cabeçalhos de cookie e senhas de proxy não saem do processo
+                    let line = access::redact(&line).into_owned();
+                    stderr.push_str(&line);
+                    stderr.push('\n');
+                    on_event(JobEvent::Log(line));
+                }
+                None => stderr_open = false,
+            },
+            _ = control.cancelled() => {
+                control.kill();
+                control.detach();
+                child.kill().await?;
+                return Ok(None);
+            }
+        }
+    }
+
+    let exit = tokio::select! {
+        exit = child.wait() => exit?,
+        _ = control.cancelled() => {
+            control.kill();
+            control.detach();
+            child.kill().await?;
+            return Ok(None);
+        }
+    };
+    group.armed = false;
+    Ok(Some((exit, meta, stderr)))
+}
+
+/// Mata o grupo do yt-dlp se a tarefa do download for abandonada no meio;
+/// o `kill_on_drop` só alcançaria o próprio yt-dlp.
+struct KillGroup<'a> {
+    control: &'a JobControl,
+    armed: bool,
+}
+
+impl Drop for KillGroup<'_> {
+    fn drop(&mut self) {
+        if self.armed {
+            self.control.kill();
+        }
+    }
+}
+
+/// Saída padrão de [`download`]: mensagens de status no terminal.
+fn print_event(event: JobEvent) {
+    if let JobEvent::Status(message) = event {
+        println!("{message}");
+    }
+}
+
+/// Interpreta a linha `ytd-meta\t<canal>\t<duração>\t<arquivo>`; o yt-dlp
+/// escreve `NA` para campos ausentes.
+fn parse_meta(line: &str) -> Downloaded {
+    let mut fields = line.splitn(4, '\t').skip(1);
+    let mut next = || {
+        fields
+            .next()
+            .filter(|value| !value.is_empty() && *value != "NA")
+    };
+    let channel = next().map(str::to_string);
+    let duration_secs = next().and_then(|value| value.parse().ok());
+    let path = next().map(PathBuf::from);
+    let bytes = path
+        .as_ref()
+        .and_then(|path| fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+    Downloaded {
+        path,
+        channel,
+        duration_secs,
+        bytes,
+        ..Downloaded::default()
+    }
+}
+
+/// Monta os argumentos passados ao yt-dlp para um download.
+fn build_ytdlp_args(options: &DownloadOptions) -> Vec<String> {
+    let output_name = &options.output_name;
+    let mut args: Vec<String> = match (options.audio_only, &options.audio_format) {
+        (true, Some(format)) => vec![
+            "-f".into(),
+            "bestaudio".into(),
+            "-x".into(),
+            "--audio-format".into(),
+            format.clone(),
+            "-o".into(),
+            format!("{output_name}.%(ext)s"),
+        ],
+        // Áudio original, sem conversão (dispensa o ffmpeg)
+        (true, None) => vec![
+            "-f".into(),
+            "bestaudio[ext=m4a]/bestaudio".into(),
+            "-o".into(),
+            format!("{output_name}.%(ext)s"),
+        ],
+        // Forçar download do melhor vídeo + melhor áudio em MP4
+        (false, _) => vec![
+            "-f".into(),
+            "bestvideo[ext=mp4]+bestaudio[ext=m4a]/best[ext=mp4]/best".into(),
+            "--merge-output-format".into(),
+            "mp4".into(),
+            "-o".into(),
+            format!("{output_name}.mp4"),
+        ],
+    };
+    if options.resume {
+        args.push("--continue".into());
+    }
+    if let Some(live) = &options.live {
+        args.extend(live.ytdlp_args());
+    }
+    if let Some(section) = &options.section {
+        args.extend(section.ytdlp_args());
+    }
+    args.extend(options.access.ytdlp_args());
+    if let Some(rate) = options.limit_rate.ytdlp_arg() {
+        args.push("--limit-rate".into());
+        args.push(rate);
+    }
+    // Progresso em linhas separadas, mesmo com o `--print` abaixo
+    args.push("--newline".into());
+    args.push("--progress".into());
+    args.push("--print".into());
+    args.push(format!(
+        "after_move:{META_PREFIX}\t%(channel)s\t%(duration)s\t%(filepath)s"
+    ));
+    args.push(options.url.clone());
+    args
+}
+
+pub fn is_youtube_url(url: &str) -> bool {
+    let youtube_regex = Regex::new(
+        r"^https?://(www\.|m\.)?(youtube\.com/(watch\?v=|shorts/|live/)|youtu\.be/)[\w-]+",
+    )
+    .unwrap();
+    youtube_regex.is_match(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_youtube_url() {
+        // Testa URLs válidas do YouTube
+        assert!(is_youtube_url(
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
+        ));
+        assert!(is_youtube_url("https://youtu.be/dQw4w9WgXcQ"));
+        assert!(is_youtube_url("https://m.youtube.com/watch?v=dQw4w9WgXcQ"));
+        assert!(is_youtube_url("https://youtube.com/watch?v=dQw4w9WgXcQ"));
+        assert!(is_youtube_url("http://www.youtube.com/watch?v=dQw4w9WgXcQ"));
+        assert!(is_youtube_url("https://www.youtube.com/shorts/dQw4w9WgXcQ"));
+        assert!(is_youtube_url("https://youtube.com/shorts/dQw4w9WgXcQ"));
+        assert!(is_youtube_url("https://www.youtube.com/live/dQw4w9WgXcQ"));
+
+        // Testa URLs inválidas
+        assert!(!is_youtube_url("https://www.google.com"));
+        assert!(!is_youtube_url("https://vimeo.com/123456"));
+        assert!(!is_youtube_url("not a url"));
+        assert!(!is_youtube_url(""));
+        assert!(!is_youtube_url("https://youtube.com"));
+    }
+
+    #[test]
+    fn test_url_validation_edge_cases() {
+        // Testa casos extremos de validação de URL
+        let test_cases = vec![
+            ("https://www.youtube.com/watch?v=", false), // URL sem ID
+            ("https://youtu.be/", false),                // URL curta sem ID
+            ("https://www.youtube.com/watch", false),    // URL sem parâmetros
+            ("youtube.com/watch?v=dQw4w9WgXcQ", false),  // URL sem protocolo
+            ("https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=10s", true), // URL com timestamp
+            (
+                "https://www.youtube.com/watch?v=dQw4w9WgXcQ&list=PLxxx",
+                true,
+            ), // URL com playlist
+        ];
+
+        for (url, expected) in test_cases {
+            assert_eq!(is_youtube_url(url), expected, "Failed for URL: {url}");
+        }
+    }
+
+    #[test]
+    fn test_build_ytdlp_args() {
+        let options = DownloadOptions::new("https://youtu.be/abc", "meu_audio", true);
+        let args = build_ytdlp_args(&options);
+        assert_eq!(args.last().unwrap(), "https://youtu.be/abc");
+        assert!(args.contains(&"meu_audio.%(ext)s".to_string()));
+        assert!(args.contains(&"-x".to_string()));
+
+        assert!(args
+            .iter()
+            .any(|arg| arg.starts_with("after_move:ytd-meta")));
+
+        let options = DownloadOptions::new("https://youtu.be/abc", "meu_video", false);
+        let args = build_ytdlp_args(&options);
+        assert!(args.contains(&"meu_video.mp4".to_string()));
+        assert!(args.contains(&"--merge-output-format".to_string()));
+    }
+
+    #[test]
+    fn test_requirements_depend_on_job() {
+        let video = DownloadOptions::new("https://youtu.be/abc", "v", false);
+        assert_eq!(video.requirements(), vec![Tool::Ffmpeg, Tool::YtDlp]);
+
+        let mp3 = DownloadOptions::new("https://youtu.be/abc", "a", true);
+        assert_eq!(mp3.requirements(), vec![Tool::Ffmpeg, Tool::YtDlp]);
+
+        let original = DownloadOptions {
+            audio_format: None,
+            ..mp3
+        };
+        assert_eq!(original.requirements(), vec![Tool::YtDlp]);
+        assert!(!build_ytdlp_args(&original).contains(&"-x".to_string()));
+    }
+
+    #[test]
+    fn test_parse_meta() {
+        let meta = parse_meta("ytd-meta\tCanal Teste\t212.5\t/caminho/que/nao/existe.mp4");
+        assert_eq!(meta.channel.as_deref(), Some("Canal Teste"));
+        assert_eq!(meta.duration_secs, Some(212.5));
+        assert_eq!(
+            meta.path,
+            Some(PathBuf::from("/caminho/que/nao/existe.mp4"))
+        );
+        assert_eq!(meta.bytes, 0);
+
+        let meta = parse_meta("ytd-meta\tNA\tNA\tNA");
+        assert_eq!(meta, Downloaded::default());
+    }
+}
@@ -0,0 +1,101 @@
+use crate::provision;
+use std::fmt;
+use std::io;
+
+/// Erros que podem ocorrer durante um download.
+#[derive(Debug)]
+pub enum DownloadError {
+    /// A URL informada não é uma URL válida do YouTube.
+    InvalidUrl(String),
+    /// Uma ferramenta externa obrigatória não foi encontrada no PATH.
+    MissingDependency(&'static str),
+    /// Nenhum método de instalação do yt-dlp funcionou.
+    InstallFailed(String),
+    /// Falha ao atualizar, fixar ou reverter a versão do yt-dlp.
+    UpdateFailed(String),
+    /// Cookies, proxy ou perfil de acesso inválidos; a mensagem já é traduzida.
+    Access(String),
+    /// Filtro de conteúdo inválido, como uma expressão regular com erro; a
+    /// mensagem já é traduzida.
+    InvalidFilter(String),
+    /// O arquivo baixado não passou na verificação do ffprobe; a mensagem já
+    /// é traduzida.
+    Verification(String),
+    /// A conversão do arquivo baixado, para um preset de aparelho, uma
+    /// animação ou a normalização de volume, falhou; a mensagem já é
+    /// traduzida.
+    Transcode(String),
+    /// O yt-dlp terminou com status de erro.
+    Backend { status: Option<i32>, stderr: String },
+    /// Todas as tentativas falharam; `last` é o erro da última.
+    RetriesExhausted {
+        attempts: u32,
+        last: Box<DownloadError>,
+    },
+    /// O download foi cancelado pelo usuário.
+    Cancelled,
+    /// Falha de E/S ao executar um processo externo.
+    Io(io::Error),
+}
+
+impl fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DownloadError::InvalidUrl(_) => write!(f, "{}", t!("error.invalid_url")),
+            DownloadError::MissingDependency("ffmpeg") => {
+                write!(f, "{}", t!("error.missing_ffmpeg"))
+            }
+            DownloadError::MissingDependency("yt-dlp") => {
+                write!(f, "{}", t!("error.missing_ytdlp"))
+            }
+            DownloadError::MissingDependency(tool) => {
+                write!(f, "{}", t!("error.missing_tool", tool = tool))
+            }
+            DownloadError::InstallFailed(message)
+            | DownloadError::UpdateFailed(message)
+            | DownloadError::Access(message)
+            | DownloadError::InvalidFilter(message)
+            | DownloadError::Verification(message)
+            | DownloadError::Transcode(message) => {
+                write!(f, "{message}")
+            }
+            DownloadError::Backend { stderr, .. } => {
+                write!(f, "{}", t!("error.backend", stderr = stderr))
+            }
+            DownloadError::RetriesExhausted { attempts, last } => write!(
+                f,
+                "{}",
+                t!("error.retries_exhausted", attempts = attempts, error = last)
+            ),
+            DownloadError::Cancelled => write!(f, "{}", t!("error.cancelled")),
+            DownloadError::Io(err) => write!(f, "{}", t!("error.io", error = err)),
+        }
+    }
+}
+
+impl DownloadError {
+    /// Indica se a falha do yt-dlp parece causada por uma versão desatualizada.
+    pub fn suggests_backend_update(&self) -> bool {
+        match self {
+            DownloadError::Backend { stderr, .. } => provision::stderr_suggests_update(stderr),
+            DownloadError::RetriesExhausted { last, .. } => last.suggests_backend_update(),
+            _ => false,
+        }
+    }
+}
+
+impl std::error::Error for DownloadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DownloadError::Io(err) => Some(err),
+            DownloadError::RetriesExhausted { last, .. } => Some(last.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DownloadError {
+    fn from(err: io::Error) -> Self {
+        DownloadError::Io(err)
+    }
+}
@@ -0,0 +1,463 @@
+//! Filtros de conteúdo para listas de vídeos: playlists e canais expandidos
+//! pela linha de comando, seleções da busca e assinaturas do `watch`.
+//!
+//! A listagem com `--flat-playlist` nem sempre traz a data de publicação ou
+//! se o vídeo é uma live; quando um filtro depende de um campo ausente, os
+//! metadados completos do vídeo são buscados antes de decidir. Um campo que
+//! continua desconhecido não reprova o vídeo.
+
+use crate::access::AccessOptions;
+use crate::error::DownloadError;
+use crate::search::{self, format_clock, SearchResult};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Duração máxima de um Short, em segundos.
+const SHORTS_MAX_SECS: f64 = 180.0;
+
+/// Regras que um vídeo precisa cumprir para ser baixado.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Filters {
+    /// Ignora vídeos mais curtos que isso, em segundos.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_duration_secs: Option<u64>,
+    /// Ignora vídeos mais longos que isso, em segundos.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_duration_secs: Option<u64>,
+    /// Só vídeos publicados nesta data ou depois.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_after: Option<UploadDate>,
+    /// Só vídeos publicados nesta data ou antes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_before: Option<UploadDate>,
+    /// Só vídeos cujo título casa com esta expressão regular.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub match_title: Option<String>,
+    /// Ignora vídeos cujo título casa com esta expressão regular.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reject_title: Option<String>,
+    #[serde(skip_serializing_if = "is_false")]
+    pub skip_shorts: bool,
+    /// Ignora lives em andamento, agendadas e gravações de lives.
+    #[serde(skip_serializing_if = "is_false")]
+    pub skip_live: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+impl Filters {
+    pub fn is_empty(&self) -> bool {
+        *self == Filters::default()
+    }
+
+    /// Compila as expressões regulares; um erro aqui deve impedir a execução.
+    pub fn compile(&self) -> Result<Matcher, DownloadError> {
+        let regex = |pattern: &Option<String>| {
+            pattern
+                .as_deref()
+                .map(|pattern| {
+                    Regex::new(pattern).map_err(|e| {
+                        DownloadError::InvalidFilter(t!("filter.invalid_regex", error = e))
+                    })
+                })
+                .transpose()
+        };
+        Ok(Matcher {
+            match_title: regex(&self.match_title)?,
+            reject_title: regex(&self.reject_title)?,
+            filters: self.clone(),
+        })
+    }
+
+    /// Resumo curto de cada regra, para listagens.
+    pub fn describe(&self) -> Vec<String> {
+        let mut details = Vec::new();
+        if let Some(secs) = self.min_duration_secs {
+            details.push(t!(
+                "filter.detail_min_duration",
+                duration = format_clock(secs as f64)
+            ));
+        }
+        if let Some(secs) = self.max_duration_secs {
+            details.push(t!(
+                "filter.detail_max_duration",
+                duration = format_clock(secs as f64)
+            ));
+        }
+        if let Some(date) = self.date_after {
+            details.push(t!("filter.detail_date_after", date = date));
+        }
+        if let Some(date) = self.date_before {
+            details.push(t!("filter.detail_date_before", date = date));
+        }
+        if let Some(pattern) = &self.match_title {
+            details.push(t!("filter.detail_match_title", pattern = pattern));
+        }
+        if let Some(pattern) = &self.reject_title {
+            details.push(t!("filter.detail_reject_title", pattern = pattern));
+        }
+        if self.skip_shorts {
+            details.push(t!("filter.detail_skip_shorts"));
+        }
+        if self.skip_live {
+            details.push(t!("filter.detail_skip_live"));
+        }
+        details
+    }
+}
+
+/// Data de publicação no formato do yt-dlp (`AAAAMMDD`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct UploadDate(u32);
+
+impl UploadDate {
+    /// Aceita `AAAA-MM-DD` ou `AAAAMMDD`.
+    pub fn parse(text: &str) -> Result<UploadDate, String> {
+        let invalid = || t!("filter.invalid_date", date = text);
+        let digits: String = text.trim().chars().filter(|&c| c != '-').collect();
+        if digits.len() != 8 || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let value: u32 = digits.parse().map_err(|_| invalid())?;
+        let (month, day) = (value / 100 % 100, value % 100);
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err(invalid());
+        }
+        Ok(UploadDate(value))
+    }
+}
+
+impl fmt::Display for UploadDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let date = self.0;
+        write!(
+            f,
+            "{}-{:02}-{:02}",
+            date / 10000,
+            date / 100 % 100,
+            date % 100
+        )
+    }
+}
+
+impl TryFrom<String> for UploadDate {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        UploadDate::parse(&text)
+    }
+}
+
+impl From<UploadDate> for String {
+    fn from(date: UploadDate) -> String {
+        date.to_string()
+    }
+}
+
+/// Duração em segundos, `M:SS` ou `H:MM:SS`.
+pub fn parse_duration(text: &str) -> Result<u64, String> {
+    let invalid = || t!("filter.invalid_duration", duration = text);
+    let parts: Vec<&str> = text.trim().split(':').collect();
+    if parts.len() > 3 {
+        return Err(invalid());
+    }
+    parts.iter().try_fold(0u64, |total, part| {
+        let value = part.parse::<u64>().map_err(|_| invalid())?;
+        Ok(total * 60 + value)
+    })
+}
+
+/// Por que um vídeo foi ignorado.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SkipReason {
+    TooShort {
+        duration: f64,
+        min: u64,
+    },
+    TooLong {
+        duration: f64,
+        max: u64,
+    },
+    PublishedBefore {
+        date: UploadDate,
+        limit: UploadDate,
+    },
+    PublishedAfter {
+        date: UploadDate,
+        limit: UploadDate,
+    },
+    TitleNotMatched(String),
+    TitleRejected(String),
+    Short,
+    Live,
+    /// Os metadados completos eram necessários, mas não puderam ser obtidos.
+    NoMetadata(String),
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            SkipReason::TooShort { duration, min } => t!(
+                "filter.reason_too_short",
+                duration = format_clock(*duration),
+                min = format_clock(*min as f64)
+            ),
+            SkipReason::TooLong { duration, max } => t!(
+                "filter.reason_too_long",
+                duration = format_clock(*duration),
+                max = format_clock(*max as f64)
+            ),
+            SkipReason::PublishedBefore { date, limit } => {
+                t!("filter.reason_before", date = date, limit = limit)
+            }
+            SkipReason::PublishedAfter { date, limit } => {
+                t!("filter.reason_after", date = date, limit = limit)
+            }
+            SkipReason::TitleNotMatched(pattern) => {
+                t!("filter.reason_title_not_matched", pattern = pattern)
+            }
+            SkipReason::TitleRejected(pattern) => {
+                t!("filter.reason_title_rejected", pattern = pattern)
+            }
+            SkipReason::Short => t!("filter.reason_short"),
+            SkipReason::Live => t!("filter.reason_live"),
+            SkipReason::NoMetadata(error) => t!("filter.reason_no_metadata", error = error),
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// Filtros prontos para avaliar vídeos.
+pub struct Matcher {
+    filters: Filters,
+    match_title: Option<Regex>,
+    reject_title: Option<Regex>,
+}
+
+impl Matcher {
+    /// Avalia o vídeo com o que se sabe dele; campos desconhecidos passam.
+    pub fn check(&self, video: &SearchResult) -> Result<(), SkipReason> {
+        let filters = &self.filters;
+        if let Some(duration) = video.duration_secs {
+            if let Some(min) = filters
+                .min_duration_secs
+                .filter(|&min| duration < min as f64)
+            {
+                return Err(SkipReason::TooShort { duration, min });
+            }
+            if let Some(max) = filters
+                .max_duration_secs
+                .filter(|&max| duration > max as f64)
+            {
+                return Err(SkipReason::TooLong { duration, max });
+            }
+        }
+        if let Some(date) = upload_date(video) {
+            if let Some(limit) = filters.date_after.filter(|&limit| date < limit) {
+                return Err(SkipReason::PublishedBefore { date, limit });
+            }
+            if let Some(limit) = filters.date_before.filter(|&limit| date > limit) {
+                return Err(SkipReason::PublishedAfter { date, limit });
+            }
+        }
+        if let Some(pattern) = &self.match_title {
+            if !pattern.is_match(&video.title) {
+                return Err(SkipReason::TitleNotMatched(pattern.to_string()));
+            }
+        }
+        if let Some(pattern) = &self.reject_title {
+            if pattern.is_match(&video.title) {
+                return Err(SkipReason::TitleRejected(pattern.to_string()));
+            }
+        }
+        if filters.skip_shorts && is_short(video) == Some(true) {
+            return Err(SkipReason::Short);
+        }
+        if filters.skip_live && is_live(video) == Some(true) {
+            return Err(SkipReason::Live);
+        }
+        Ok(())
+    }
+
+    /// Se algum filtro depende de um campo que a listagem não trouxe.
+    pub fn needs_details(&self, video: &SearchResult) -> bool {
+        let filters = &self.filters;
+        let duration = filters.min_duration_secs.is_some() || filters.max_duration_secs.is_some();
+        let date = filters.date_after.is_some() || filters.date_before.is_some();
+        (duration && video.duration_secs.is_none())
+            || (date && upload_date(video).is_none())
+            || (filters.skip_shorts && is_short(video).is_none())
+            || (filters.skip_live && is_live(video).is_none())
+    }
+}
+
+fn upload_date(video: &SearchResult) -> Option<UploadDate> {
+    UploadDate::parse(video.upload_date.as_deref()?).ok()
+}
+
+/// Links `/shorts/` são Shorts; fora isso, vídeos curtos na vertical.
+fn is_short(video: &SearchResult) -> Option<bool> {
+    if video.url.contains("/shorts/") {
+        return Some(true);
+    }
+    match video.duration_secs {
+        Some(duration) if duration > SHORTS_MAX_SECS => Some(false),
+        _ => Some(video.height? > video.width?),
+    }
+}
+
+fn is_live(video: &SearchResult) -> Option<bool> {
+    Some(video.live_status.as_deref()? != "not_live")
+}
+
+/// Um vídeo que não passou nos filtros.
+#[derive(Debug, Clone)]
+pub struct Skipped {
+    pub video: SearchResult,
+    pub reason: SkipReason,
+}
+
+/// Resultado da avaliação de uma lista de vídeos, na ordem original.
+#[derive(Debug, Default)]
+pub struct Selection {
+    pub accepted: Vec<SearchResult>,
+    pub skipped: Vec<Skipped>,
+}
+
+/// Separa os vídeos que passam nos filtros, buscando os metadados completos
+/// só quando a listagem não basta para decidir.
+pub async fn select(
+    matcher: &Matcher,
+    videos: Vec<SearchResult>,
+    access: &AccessOptions,
+) -> Selection {
+    let mut selection = Selection::default();
+    for mut video in videos {
+        let mut verdict = matcher.check(&video);
+        if verdict.is_ok() && matcher.needs_details(&video) {
+            verdict = match search::details(&video, access).await {
+                Ok(details) => {
+                    video = details;
+                    matcher.check(&video)
+                }
+                Err(e) => Err(SkipReason::NoMetadata(e.to_string())),
+            };
+        }
+        match verdict {
+            Ok(()) => selection.accepted.push(video),
+            Err(reason) => selection.skipped.push(Skipped { video, reason }),
+        }
+    }
+    selection
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn video(id: &str, title: &str, duration: Option<f64>) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            title: title.to_string(),
+            duration_secs: duration,
+            url: format!("https://www.youtube.com/watch?v={id}"),
+            ..SearchResult::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_date_and_duration() {
+        let date = UploadDate::parse("2024-03-09").unwrap();
+        assert_eq!(date, UploadDate::parse("20240309").unwrap());
+        assert_eq!(date.to_string(), "2024-03-09");
+        for text in ["2024-13-01", "2024-03-00", "240309", "ontem"] {
+            assert!(UploadDate::parse(text).is_err(), "{text}");
+        }
+        assert_eq!(parse_duration("90"), Ok(90));
+        assert_eq!(parse_duration("1:30"), Ok(90));
+        assert_eq!(parse_duration("1:02:03"), Ok(3723));
+        assert!(parse_duration("1:2:3:4").is_err());
+        assert!(parse_duration("10m").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let matcher = Filters {
+            min_duration_secs: Some(60),
+            max_duration_secs: Some(3600),
+            date_after: Some(UploadDate::parse("2024-01-01").unwrap()),
+            match_title: Some("(?i)aula".to_string()),
+            reject_title: Some("(?i)trailer".to_string()),
+            skip_shorts: true,
+            skip_live: true,
+            ..Filters::default()
+        }
+        .compile()
+        .unwrap();
+
+        let ok = SearchResult {
+            upload_date: Some("20240510".to_string()),
+            live_status: Some("not_live".to_string()),
+            ..video("aaaaaaaaaaa", "Aula 1", Some(600.0))
+        };
+        assert_eq!(matcher.check(&ok), Ok(()));
+        assert!(!matcher.needs_details(&ok));
+
+        let cases = [
+            (video("b", "Aula curta", Some(30.0)), "TooShort"),
+            (video("c", "Aula longa", Some(7200.0)), "TooLong"),
+            (video("d", "Vlog", Some(600.0)), "TitleNotMatched"),
+            (video("e", "Aula trailer", Some(600.0)), "TitleRejected"),
+            (
+                SearchResult {
+                    upload_date: Some("20231231".to_string()),
+                    ..video("f", "Aula antiga", Some(600.0))
+                },
+                "PublishedBefore",
+            ),
+            (
+                SearchResult {
+                    url: "https://www.youtube.com/shorts/g".to_string(),
+                    ..video("g", "Aula rápida", Some(90.0))
+                },
+                "Short",
+            ),
+            (
+                SearchResult {
+                    live_status: Some("was_live".to_string()),
+                    ..video("h", "Aula ao vivo", Some(600.0))
+                },
+                "Live",
+            ),
+        ];
+        for (video, expected) in cases {
+            let reason = matcher.check(&video).unwrap_err();
+            assert!(format!("{reason:?}").starts_with(expected), "{reason:?}");
+        }
+
+        // Sem data nem status de live, os metadados completos são necessários
+        assert!(matcher.needs_details(&video("i", "Aula 2", Some(600.0))));
+        assert!(Filters::default()
+            .compile()
+            .unwrap()
+            .check(&video("j", "", None))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_compile_rejects_invalid_regex() {
+        let filters = Filters {
+            reject_title: Some("(".to_string()),
+            ..Filters::default()
+        };
+        assert!(matches!(
+            filters.compile(),
+            Err(DownloadError::InvalidFilter(_))
+        ));
+    }
+}
@@ -0,0 +1,423 @@
+//! English catalog.
+
+pub const MESSAGES: &[(&str, &str)] = &[
+    // Command line
+    ("cli.value.file", "FILE"),
+    ("cli.value.format", "FORMAT"),
+    ("cli.value.name", "NAME"),
+    ("cli.value.speed", "SPEED"),
+    ("cli.value.terms", "TERMS"),
+    ("cli.value.duration", "DURATION"),
+    ("cli.value.minutes", "MINUTES"),
+    ("cli.value.date", "DATE"),
+    ("cli.value.start", "START"),
+    ("cli.value.end", "END"),
+    ("cli.value.address", "ADDRESS"),
+    ("cli.value.language", "LANGUAGE"),
+    ("cli.value.standard", "STANDARD"),
+    ("cli.value.version", "VERSION"),
+    ("cli.about", "CLI tool to download YouTube videos"),
+    ("cli.arg.url", "YouTube video URL (optional in interactive mode)"),
+    ("cli.arg.output", "Output file name"),
+    ("cli.arg.audio_only", "Download only the audio as MP3"),
+    ("cli.arg.audio_format", "Audio format with -a (mp3, m4a, opus...) or 'original' to skip conversion"),
+    ("cli.arg.interactive", "Interactive mode"),
+    ("cli.arg.lang", "Message language (pt-BR, en)"),
+    ("cli.arg.live", "Record a live stream, waiting for a scheduled one to start"),
+    ("cli.arg.live_from_start", "Record the live stream from its start, not from now"),
+    ("cli.arg.duration", "Stop recording after this long (seconds, M:SS or H:MM:SS)"),
+    ("cli.arg.segment", "Split the recording into N-minute parts (default: 60; 0 disables)"),
+    ("cli.arg.normalize", "Normalize the loudness of the extracted audio to the given standard"),
+    ("cli.arg.target_lufs", "Integrated loudness target for normalization, in LUFS (default: -16)"),
+    ("cli.arg.analyze_loudness", "Only measure and report the audio loudness, without changing it"),
+    ("cli.arg.preset", "Convert the file for a device with codec, resolution or size limits"),
+    ("cli.arg.tui", "Full-screen dashboard with the download queue"),
+    ("cli.arg.search", "Search YouTube and pick which videos to download"),
+    ("cli.arg.results", "Number of search results"),
+    ("cli.filter.min_duration", "Skip videos shorter than this (seconds or M:SS)"),
+    ("cli.filter.max_duration", "Skip videos longer than this (seconds or M:SS)"),
+    ("cli.filter.date_after", "Only videos published on or after this date (YYYY-MM-DD)"),
+    ("cli.filter.date_before", "Only videos published on or before this date (YYYY-MM-DD)"),
+    ("cli.filter.match_title", "Only download videos whose title matches this regular expression"),
+    ("cli.filter.reject_title", "Skip videos whose title matches this regular expression"),
+    ("cli.filter.skip_shorts", "Skip Shorts"),
+    ("cli.filter.skip_live", "Skip live streams, upcoming or already finished"),
+    ("cli.arg.attempts", "Total attempts on transient failures (default: from config)"),
+    ("cli.arg.resume", "Resume downloads interrupted in previous runs"),
+    ("cli.arg.limit_rate", "Maximum download speed (e.g. 2M, 500K); overrides the configured schedule"),
+    ("cli.arg.cookies", "Cookies file (Netscape format) for videos that require login"),
+    ("cli.arg.proxy", "HTTP or SOCKS5 proxy (e.g. socks5://127.0.0.1:1080)"),
+    ("cli.arg.user_agent", "User agent sent by yt-dlp"),
+    ("cli.arg.profile", "Access profile from the config (cookies, proxy, user agent)"),
+    ("cli.doctor.about", "Check dependencies, versions and the output directory"),
+    ("cli.doctor.dir", "Output directory to check"),
+    ("cli.update.about", "Update yt-dlp (managed copy or system install)"),
+    ("cli.update.pin", "Install and pin a specific version"),
+    ("cli.update.unpin", "Remove the pinned version and update"),
+    ("cli.update.rollback", "Go back to the previous version"),
+    ("cli.stats.about", "Show download statistics"),
+    ("cli.stats.today", "Only today's downloads"),
+    ("cli.stats.week", "Only the last 7 days"),
+    ("cli.stats.all", "All downloads (default)"),
+    ("cli.serve.about", "Start the local HTTP API to trigger downloads"),
+    ("cli.serve.bind", "Address and port the server listens on"),
+    ("cli.serve.token", "Token required as Authorization: Bearer (default: server.token from the config)"),
+    ("cli.serve.dir", "Directory where downloaded files are written"),
+    ("cli.subscribe.about", "Subscribe to a channel or playlist so watch downloads new uploads"),
+    ("cli.subscribe.url", "Channel (@name, channel/...) or playlist URL"),
+    ("cli.subscribe.backfill", "Also download videos already published"),
+    ("cli.unsubscribe.about", "Remove a subscription"),
+    ("cli.subscriptions.about", "List subscriptions"),
+    ("cli.info.about", "Show a video's metadata, including its live status"),
+    ("cli.gif.about", "Convert a part of a video into a GIF (or animated WebP), downloading only that part"),
+    ("cli.gif.start", "Start of the part, in seconds, M:SS or H:MM:SS"),
+    ("cli.gif.end", "End of the part, in seconds, M:SS or H:MM:SS"),
+    ("cli.gif.width", "Maximum animation width, in pixels"),
+    ("cli.gif.fps", "Frames per second"),
+    ("cli.gif.webp", "Create an animated WebP instead of a GIF"),
+    ("cli.gif.output", "Output file name, without extension"),
+    ("cli.watch.about", "Check subscriptions periodically and download new uploads"),
+    ("cli.watch.interval", "Minutes between checks (default: watch.interval_mins from the config)"),
+    ("cli.watch.once", "Check once and exit"),
+    ("cli.watch.dir", "Directory where new videos are written"),
+    ("cli.downloading_audio", "Downloading audio from:"),
+    ("cli.downloading_video", "Downloading video from:"),
+    ("cli.recording_live", "Recording live stream from:"),
+    ("cli.download_done", "✅ Download completed successfully!"),
+    ("cli.url_required", "❌ A URL is required in non-interactive mode. Use -i for interactive mode."),
+    ("cli.download_error", "❌ Download error: {error}"),
+    ("cli.update_hint", "💡 This error is usually fixed by updating yt-dlp: run 'update-backend'."),
+    ("cli.update_current", "✅ yt-dlp is already at version {version}"),
+    ("cli.update_done", "✅ yt-dlp updated: {from} → {to}"),
+    // Interactive mode
+    ("interactive.welcome", "🚀 Welcome to interactive mode!"),
+    ("interactive.help_hint", "Type 'help' to see the available commands."),
+    ("interactive.exit_hint", "Type 'exit' or 'quit' to leave."),
+    ("interactive.thanks", "👋 Thanks for using YouTube Downloader!"),
+    ("interactive.session_total", "📊 Downloads in this session: {count}"),
+    ("interactive.history_error", "⚠️  Could not save the history: {error}"),
+    ("interactive.diagnosing", "Diagnosing the environment"),
+    ("interactive.unknown_command", "❌ Unknown command: '{command}'. Type 'help' to see the available commands."),
+    ("interactive.ctrl_c", "CTRL-C detected. Type 'exit' to leave."),
+    ("interactive.ctrl_d", "CTRL-D detected. Exiting..."),
+    ("interactive.readline_error", "Error: {error}"),
+    ("shell.unclosed_quote", "❌ Unclosed {quote} quote"),
+    ("shell.trailing_backslash", "❌ The line ends with '\\' and nothing to escape"),
+    ("repl.syntax.download", "<URL> [-a] [-o name]"),
+    ("repl.syntax.search", "<terms> [-n 10]"),
+    ("repl.syntax.get", "<1,4-6> [-a]"),
+    ("repl.syntax.resume", "[--discard]"),
+    ("repl.syntax.throttle", "[2M|off|auto]"),
+    ("repl.syntax.stats", "[--today|--week|--all]"),
+    ("repl.syntax.update_backend", "[--pin VERSION|--unpin|--rollback]"),
+    // Help
+    ("help.title", "📋 Available commands:"),
+    ("help.download", "Download video as MP4"),
+    ("help.download_audio", "Download only audio as MP3"),
+    ("help.download_named_syntax", "download <URL> -o name"),
+    ("help.download_named", "Download with a custom name"),
+    ("help.url", "Download video directly (shortcut)"),
+    ("help.search", "Search YouTube videos"),
+    ("help.get", "Download results from the last search"),
+    ("help.resume", "Resume (or discard) interrupted downloads"),
+    ("help.throttle", "Show or change the bandwidth limit for new downloads"),
+    ("help.help", "Show this help"),
+    ("help.clear", "Clear the screen"),
+    ("help.stats", "Statistics (--today, --week, --all)"),
+    ("help.demo", "Animation demo"),
+    ("help.doctor", "Diagnose dependencies"),
+    ("help.update_backend", "Update yt-dlp (--pin V, --unpin, --rollback)"),
+    ("help.exit", "Exit the program"),
+    ("help.tips", "💡 Tips:"),
+    ("help.tip_paste", "You can paste URLs directly"),
+    ("help.tip_shorts", "Full support for YouTube Shorts"),
+    ("help.tip_flags", "Use -a for MP3 audio and -o for a custom name"),
+    ("help.tip_formats", "Videos are saved as MP4, audio as MP3"),
+    ("help.tip_history", "Command history available (↑/↓)"),
+    ("help.tip_keys", "While downloading: p pauses, c cancels, v shows yt-dlp output and b sends it to the background"),
+    // Animations
+    ("anim.loading", "Loading YouTube Downloader..."),
+    ("anim.loaded", "✅ YouTube Downloader loaded!"),
+    ("anim.goodbye_finishing", "👋 Finishing..."),
+    ("anim.goodbye_saving", "💾 Saving history..."),
+    ("anim.goodbye_cache", "🧹 Cleaning cache..."),
+    ("anim.goodbye_done", "✨ Done!"),
+    ("anim.loading_help", "📋 Loading help..."),
+    ("anim.clearing", "Clearing screen..."),
+    ("anim.cleared", "✨ Screen cleared!"),
+    ("anim.computing_stats", "Computing statistics"),
+    ("anim.stats_session", "📊 Downloads in this session: {count}"),
+    ("anim.stats_congrats", "🎉 Congrats on the downloads!"),
+    ("anim.stats_first", "💡 How about your first download?"),
+    ("anim.demo_title", "🎪 Animation Demo"),
+    ("anim.demo_spinner", "Showing the spinner"),
+    ("anim.demo_progress", "Showing the progress bar"),
+    ("anim.demo_typing", "This is a typing effect! 🎯"),
+    ("anim.demo_done", "✨ Demo finished!"),
+    ("anim.starting_download", "Starting download"),
+    ("anim.downloading", "Downloading"),
+    ("anim.url_detected", "🔍 YouTube URL detected! Analyzing..."),
+    ("anim.checking_video", "Checking video"),
+    ("anim.success", "Download completed successfully!"),
+    ("anim.error", "Download error!"),
+    ("anim.matrix", "🔢 Matrix effect:"),
+    // Download
+    ("download.checking_tool", "🔍 Checking whether {tool} is installed..."),
+    ("download.tool_missing", "❌ {tool} not found!"),
+    ("download.fetching_info", "🔍 Fetching video information..."),
+    ("download.short_detected", "📱 YouTube Short detected!"),
+    ("download.audio_format", "🎵 Downloading audio as {format}..."),
+    ("download.audio_original", "🎵 Downloading audio in its original format..."),
+    ("download.video_mp4", "🎬 Downloading video as MP4..."),
+    ("download.audio_saved", "💾 Audio downloaded successfully!"),
+    ("download.video_saved", "💾 Video downloaded successfully!"),
+    ("download.saved_verified", "{saved} ({details}, verified)"),
+    ("verify.no_ffprobe", "⚠️  ffprobe not found; the file was not verified."),
+    ("verify.failed", "The file {path} failed verification: {problem}"),
+    ("verify.unreadable", "ffprobe could not read it ({error})"),
+    ("verify.missing_video", "there is no video track"),
+    ("verify.missing_audio", "there is no audio track"),
+    ("verify.duration", "its duration is {actual}s, but the video is {expected}s long"),
+    ("retry.waiting", "🔁 Attempt {attempt}/{attempts} failed ({error}); retrying in {seconds}s..."),
+    ("retry.succeeded", "✅ Finished after {attempts} attempts."),
+    ("retry.gave_up", "🛑 Attempt {attempt}/{attempts} failed with a permanent error; retrying would not help."),
+    // yt-dlp installation and updates
+    ("provision.missing", "❌ yt-dlp not found!"),
+    ("provision.confirm", "📦 Install a managed copy of yt-dlp in the application data directory?"),
+    ("provision.yes_no", "[y/N]"),
+    ("provision.installing", "📦 Installing yt-dlp via {method}..."),
+    ("provision.installed", "✅ yt-dlp {version} installed successfully via {method}!"),
+    ("provision.method_failed", "⚠️  {method}: {reason}"),
+    ("provision.all_failed", "Could not install yt-dlp ({failures}). Install it manually, for example: pipx install yt-dlp"),
+    ("provision.method_binary", "binary"),
+    ("provision.program_not_found", "{program} not found"),
+    ("provision.program_failed", "{program} failed: {reason}"),
+    ("provision.unknown_error", "unknown error"),
+    ("provision.fetch_failed", "failed to download {url}: {error}"),
+    ("provision.checksum_not_listed", "checksum for '{asset}' not found in {url}"),
+    ("provision.no_sha256", "no SHA-256 configured for the binary"),
+    ("provision.sha256_mismatch", "SHA-256 mismatch (expected {expected}, got {actual})"),
+    ("provision.cannot_pin_url", "cannot pin a version with URL {url} (expected .../latest/download/...)"),
+    ("update.pinned", "yt-dlp is pinned to version {version}. Use --unpin to receive updates again."),
+    ("update.no_previous", "No previous version recorded to go back to."),
+    ("update.updating", "🔄 Updating yt-dlp {from} via {method}..."),
+    ("update.failed", "Failed to update yt-dlp: {reason}"),
+    ("update.rollback_unmanaged", "Rollback is only available for the managed copy of yt-dlp."),
+    ("update.system_failed", "Failed to update the system yt-dlp ({reason}). If it came from a package manager, update it there."),
+    ("update.no_backup", "backup of the previous binary not found"),
+    // Statistics
+    ("stats.title", "📊 Statistics — {period}"),
+    ("stats.period_today", "today"),
+    ("stats.period_week", "last 7 days"),
+    ("stats.period_all", "all time"),
+    ("stats.downloads", "Downloads:"),
+    ("stats.failures", "Failures:"),
+    ("stats.bytes", "Data downloaded:"),
+    ("stats.duration", "Total duration:"),
+    ("stats.channels", "Top channels:"),
+    // Diagnostics
+    ("doctor.title", "🩺 Environment diagnostics"),
+    ("doctor.all_good", "🎉 All set to download!"),
+    ("doctor.how_to_fix", "🛠️  How to fix:"),
+    ("doctor.not_found", "not found"),
+    ("doctor.fix_ffmpeg", "install ffmpeg (e.g. sudo apt install ffmpeg, brew install ffmpeg or winget install ffmpeg)"),
+    ("doctor.fix_ffprobe", "ffprobe ships with ffmpeg; reinstall the full ffmpeg package"),
+    ("doctor.fix_python", "only needed to install yt-dlp via pipx/venv; install Python 3"),
+    ("doctor.fix_ytdlp_missing", "run a download to install the managed copy or install it with: pipx install yt-dlp"),
+    ("doctor.managed_via", " (managed via {method})"),
+    ("doctor.ytdlp_age", "{detail} — released {age} days ago"),
+    ("doctor.fix_ytdlp_old", "old versions often break when YouTube changes; update with: youtube_video_downloader update-backend"),
+    ("doctor.label_output", "output"),
+    ("doctor.writable", "{dir} is writable"),
+    ("doctor.not_writable", "cannot write to {dir}: {error}"),
+    ("doctor.fix_writable", "choose another output directory or fix its permissions"),
+    ("doctor.label_space", "space"),
+    ("doctor.low_space", "only {free} free"),
+    ("doctor.free_space", "{free} free"),
+    ("doctor.space_unknown", "could not be checked"),
+    ("doctor.fix_space", "free up disk space before downloading long videos"),
+    // Download controls
+    ("controls.keys", "[p] pause  [c] cancel  [v] details  [b] background"),
+    ("controls.waiting", "preparing..."),
+    ("controls.paused", "paused"),
+    ("controls.cancelling", "cancelling..."),
+    ("controls.verbose", "details"),
+    ("controls.backgrounded", "📥 Download #{id} keeps running in the background."),
+    ("controls.bg_done", "✅ Background download #{id} finished: {url}"),
+    ("controls.bg_cancelled", "⏹️  Background download #{id} cancelled."),
+    ("controls.bg_failed", "❌ Background download #{id} failed: {error}"),
+    ("controls.bg_cancelling", "⏹️  Cancelling {count} background download(s)..."),
+    // Search
+    ("search.searching", "🔎 Searching YouTube..."),
+    ("search.title", "🔎 {count} result(s) for \"{query}\":"),
+    ("search.no_results", "No results for \"{query}\"."),
+    ("search.hint", "Use 'get 3' or 'get 1,4-6 -a' to download."),
+    ("search.pick_prompt", "Which ones to download? (e.g. 3 or 1,4-6 -a; Enter to quit) "),
+    ("search.invalid_selection", "Invalid selection: \"{part}\". Use numbers like 3 or 1,4-6."),
+    ("search.out_of_range", "There is no result {number}; the search returned {count}."),
+    ("search.no_metadata", "yt-dlp returned no metadata for the video"),
+    ("search.nothing_to_get", "No search yet. Use 'search <terms>' first."),
+    ("search.downloading", "📥 [{current}/{total}] {title}"),
+    ("search.arg_query", "Search terms"),
+    ("search.arg_selection", "Result numbers, like 3 or 1,4-6"),
+    // Interrupted downloads
+    ("resume.found", "⏸️  {count} download(s) interrupted in a previous run."),
+    ("resume.hint_interactive", "Type 'resume' to continue where they stopped or 'resume --discard' to drop them."),
+    ("resume.hint_cli", "Use --resume to continue where they stopped."),
+    ("resume.nothing", "No interrupted downloads."),
+    ("resume.resuming", "🔄 [{current}/{total}] Resuming {url}"),
+    ("resume.discarded", "🗑️  {count} interrupted download(s) discarded."),
+    ("resume.discard_failed", "Could not discard the downloads: {error}"),
+    ("resume.arg_discard", "Discard interrupted downloads and their partial files"),
+    // Bandwidth limit
+    ("bandwidth.unlimited", "unlimited"),
+    ("bandwidth.invalid_rate", "Invalid speed: \"{rate}\". Use values like 2M, 500K or off."),
+    ("bandwidth.invalid_time", "Invalid time: \"{time}\". Use HH:MM, like 22:00."),
+    ("bandwidth.limited", "🐢 Bandwidth limit: {limit}"),
+    ("bandwidth.current", "🐢 Limit for new downloads: {limit} ({source})"),
+    ("bandwidth.source_session", "set in this session"),
+    ("bandwidth.source_config", "from the config and current time"),
+    ("bandwidth.arg_rate", "New maximum speed, off for no limit or auto to follow the config"),
+    // Cookies, proxy and profiles
+    ("access.using_cookies", "🍪 Using login cookies"),
+    ("access.using_proxy", "🌐 Proxy: {proxy}"),
+    ("access.unknown_profile", "Access profile \"{name}\" does not exist in the config profiles."),
+    ("access.invalid_proxy", "Invalid proxy: \"{proxy}\". Use http://, https://, socks4:// or socks5://host:port."),
+    ("access.invalid_cookies", "Invalid cookies file ({path}): {reason}"),
+    ("access.cookies_bad_line", "line {line} is not in Netscape format"),
+    ("access.cookies_empty", "no cookies found"),
+    // HTTP server
+    ("server.listening", "🌐 API listening on http://{addr} (Ctrl+C to stop)"),
+    ("server.open_warning", "⚠️  {addr} is not a local address and the API has no token; use --token or server.token in the config."),
+    ("server.bind_failed", "Could not listen on {addr}: {error}"),
+    ("server.stopping", "⏹️  Stopping the server and cancelling running downloads..."),
+    ("server.invalid_request", "Invalid request: {error}"),
+    ("server.invalid_output", "The output \"{output}\" must be a file name, without directories."),
+    ("server.unauthorized", "Missing or invalid token."),
+    ("server.job_not_found", "Job {id} not found."),
+    ("server.origin_denied", "Origin not allowed: {origin}. Add it to server.allowed_origins in the config."),
+    ("server.pairing_code", "🔗 Pairing code for extensions and bookmarklets: {code}"),
+    ("server.pairing_invalid", "Invalid pairing code."),
+    ("server.pairing_locked", "Pairing unavailable; restart serve to get a new code."),
+    ("server.pairing_failed", "Could not save the pairing: {error}"),
+    ("server.unnamed_client", "browser"),
+    // Subscriptions
+    ("subscriptions.invalid_url", "Not a YouTube channel or playlist: {url}"),
+    ("subscriptions.exists", "There is already a subscription for {url}."),
+    ("subscriptions.seeded", "{count} already published video(s) marked as seen; only new uploads will be downloaded."),
+    ("subscriptions.added", "✅ Subscription added: {url}"),
+    ("subscriptions.removed", "🗑️  Subscription removed: {url}"),
+    ("subscriptions.not_found", "No subscription for {url}."),
+    ("subscriptions.save_failed", "Could not save the subscription: {error}"),
+    ("subscriptions.none", "No subscriptions. Use 'subscribe <URL>' to follow a channel or playlist."),
+    ("subscriptions.title", "📺 Subscriptions ({count})"),
+    ("subscriptions.detail_profile", "profile {name}"),
+    ("subscriptions.detail_audio", "audio only"),
+    ("watch.checking", "🔎 Checking {count} subscription(s)..."),
+    ("watch.found", "🆕 {count} new video(s) in {url}"),
+    ("watch.new_upload", "⬇️  {title}"),
+    ("watch.check_failed", "⚠️  Could not check {url}: {error}"),
+    ("watch.next", "⏰ Next check in {minutes} min (Ctrl+C to quit)."),
+    ("watch.stopping", "⏹️  Stopping watch..."),
+    // Content filters
+    ("filter.invalid_regex", "Invalid regular expression in the title filter: {error}"),
+    ("filter.invalid_date", "Invalid date: \"{date}\". Use YYYY-MM-DD or YYYYMMDD."),
+    ("filter.invalid_duration", "Invalid duration: \"{duration}\". Use seconds, M:SS or H:MM:SS."),
+    ("filter.listing", "📋 Listing videos from {url}..."),
+    ("filter.summary", "{accepted} video(s) to download, {skipped} skipped by the filters."),
+    ("filter.skipped", "⏭️  Skipped: {title} ({reason})"),
+    ("filter.reason_too_short", "{duration}, shorter than {min}"),
+    ("filter.reason_too_long", "{duration}, longer than {max}"),
+    ("filter.reason_before", "published on {date}, before {limit}"),
+    ("filter.reason_after", "published on {date}, after {limit}"),
+    ("filter.reason_title_not_matched", "title does not match /{pattern}/"),
+    ("filter.reason_title_rejected", "title matches /{pattern}/"),
+    ("filter.reason_short", "it is a Short"),
+    ("filter.reason_live", "it is a live stream"),
+    ("filter.reason_no_metadata", "metadata unavailable: {error}"),
+    ("filter.detail_min_duration", "minimum {duration}"),
+    ("filter.detail_max_duration", "maximum {duration}"),
+    ("filter.detail_date_after", "since {date}"),
+    ("filter.detail_date_before", "until {date}"),
+    ("filter.detail_match_title", "title /{pattern}/"),
+    ("filter.detail_reject_title", "no title /{pattern}/"),
+    ("filter.detail_skip_shorts", "no Shorts"),
+    ("filter.detail_skip_live", "no live streams"),
+    // Live streams
+    ("live.status_not_live", "not a live stream"),
+    ("live.status_live", "🔴 live now"),
+    ("live.status_upcoming", "⏳ scheduled"),
+    ("live.status_was_live", "ended, recording available"),
+    ("live.status_post_live", "ended, recording being processed"),
+    ("live.waiting_for", "⏳ Scheduled live stream; starts in {time}. Waiting for it to begin..."),
+    ("live.waiting", "⏳ Scheduled live stream. Waiting for it to begin..."),
+    ("live.recording", "🔴 Live now; recording."),
+    ("live.not_live", "ℹ️  Not an ongoing live stream; downloading the video normally."),
+    ("live.from_start", "⏮️  Recording from the start of the stream."),
+    ("live.limited", "⏱️  Recording stops after {duration}."),
+    ("live.limit_reached", "⏹️  Time limit reached; finishing the recording..."),
+    ("live.split_done", "✂️  Recording split into {count} parts."),
+    ("live.split_failed", "⚠️  Could not split the recording: {error}"),
+    // Loudness normalization
+    ("loudness.analyzing", "🔊 Measuring loudness (EBU R128)..."),
+    ("loudness.measured", "🔊 Measured loudness: {integrated} LUFS, peak {peak} dBTP, range {range} LU."),
+    ("loudness.normalized", "🔊 Normalized to {target} LUFS: now {integrated} LUFS, peak {peak} dBTP."),
+    ("loudness.sidecar", "📝 Measurements saved to {path}"),
+    ("loudness.failed", "⚠️  Could not normalize the loudness: {error}"),
+    ("loudness.no_measurement", "ffmpeg could not measure the loudness; the audio may be silent"),
+    ("loudness.invalid_target", "invalid loudness: \"{value}\"; use a value in LUFS between -70 and -5, such as -16"),
+    // Device presets
+    ("preset.unknown", "unknown preset: \"{name}\""),
+    ("preset.converting", "📱 Converting to the {preset} preset..."),
+    ("preset.done", "📱 Converted to {preset} ({size} MB)."),
+    ("preset.no_duration", "the video duration is unknown; the bitrate for the size limit cannot be calculated"),
+    ("preset.too_long", "the video is too long to fit in {size} MB"),
+    ("preset.failed", "ffmpeg failed: {error}"),
+    ("preset.kept", "⚠️  Could not convert to {preset}, so the original file was kept: {error}"),
+    // Clips and animations
+    ("clip.invalid_range", "The end of the clip must come after its start."),
+    ("animation.exporting", "🎞️  Creating an animation of {start}–{end}..."),
+    ("animation.rendering", "🎨 Converting the clip to {format}..."),
+    ("animation.done", "✨ Animation saved to {path} ({size} MB)."),
+    ("animation.no_clip", "yt-dlp did not report the downloaded clip file"),
+    ("animation.failed", "Converting the clip into an animation failed: {error}"),
+    // Video metadata (info)
+    ("info.channel", "Channel"),
+    ("info.duration", "Duration"),
+    ("info.upload_date", "Published on"),
+    ("info.live_status", "Live stream"),
+    ("info.starts_in", "Starts in"),
+    // Full-screen dashboard
+    ("tui.title", "YouTube Downloader"),
+    ("tui.summary", "{total} downloads, {active} in progress"),
+    ("tui.header_status", "Status"),
+    ("tui.header_speed", "Speed"),
+    ("tui.header_eta", "ETA"),
+    ("tui.header_target", "Target"),
+    ("tui.status_running", "Downloading"),
+    ("tui.status_paused", "Paused"),
+    ("tui.status_done", "Done"),
+    ("tui.status_failed", "Failed"),
+    ("tui.status_cancelled", "Cancelled"),
+    ("tui.empty", "Paste a YouTube URL below and press Enter."),
+    ("tui.log", "Log"),
+    ("tui.prompt", "URL> "),
+    ("tui.keys", "Enter download · ↑↓ select · with empty input: p pause · c cancel · r retry · o open folder · q quit"),
+    ("tui.no_job", "No download selected."),
+    ("tui.pause_unavailable", "This download cannot be paused right now."),
+    ("tui.open_failed", "Could not open {path}: {error}"),
+    ("tui.stopping", "⏹️  Cancelling running downloads..."),
+    // Errors
+    ("error.invalid_url", "Invalid URL. Please provide a valid YouTube URL."),
+    ("error.missing_ffmpeg", "ffmpeg is required to merge audio and video. Install ffmpeg and try again."),
+    ("error.missing_ytdlp", "yt-dlp not found. Install it with 'pipx install yt-dlp' or allow the managed installation."),
+    ("error.missing_tool", "{tool} not found. Install {tool} and try again."),
+    ("error.backend", "Download error: {stderr}"),
+    ("error.cancelled", "Download cancelled."),
+    ("error.retries_exhausted", "Failed after {attempts} attempts: {error}"),
+    ("error.io", "Error running external process: {error}"),
+    // Misc
+    ("config.invalid", "⚠️  Invalid configuration in {path}: {error}"),
+    ("config.refuse_save", "The configuration in {path} is invalid ({error}); fix the file before changing it."),
+    ("tools.unknown_version", "unknown"),
+];
@@ -0,0 +1,203 @@
+//! Catálogos de mensagens (pt-BR e en).
+//!
+//! As mensagens são identificadas por chaves (`"download.video_saved"`) e
+//! podem ter marcadores `{nome}`, preenchidos pela macro [`t!`](crate::t).
+//! O idioma vem de `--lang`, do campo `lang` do `config.json` ou das
+//! variáveis `LC_ALL`/`LC_MESSAGES`/`LANG`; o padrão é pt-BR.
+
+mod en;
+mod pt_br;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+/// Idioma das mensagens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lang {
+    PtBr,
+    En,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::PtBr, Lang::En];
+
+    /// Interpreta códigos como `pt-BR`, `pt_BR.UTF-8`, `en` ou `en_US`.
+    pub fn parse(code: &str) -> Option<Lang> {
+        let code = code.split(['.', '@']).next()?.to_lowercase();
+        let language = code.split(['-', '_']).next()?;
+        match language {
+            "pt" => Some(Lang::PtBr),
+            "en" => Some(Lang::En),
+            _ => None,
+        }
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::PtBr => "pt-BR",
+            Lang::En => "en",
+        }
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Lang::PtBr => pt_br::MESSAGES,
+            Lang::En => en::MESSAGES,
+        }
+    }
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Idioma em uso no processo.
+pub fn lang() -> Lang {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Lang::En,
+        _ => Lang::PtBr,
+    }
+}
+
+pub fn set_lang(lang: Lang) {
+    let value = match lang {
+        Lang::PtBr => 0,
+        Lang::En => 1,
+    };
+    CURRENT.store(value, Ordering::Relaxed);
+}
+
+/// Escolhe o idioma por ordem de precedência: `--lang`, configuração,
+/// ambiente e, por fim, pt-BR.
+pub fn init(cli: Option<&str>, config: Option<&str>) -> Lang {
+    let from_env = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty());
+    let lang = cli
+        .and_then(Lang::parse)
+        .or_else(|| config.and_then(Lang::parse))
+        .or_else(|| from_env.as_deref().and_then(Lang::parse))
+        .unwrap_or(Lang::PtBr);
+    set_lang(lang);
+    lang
+}
+
+fn tables() -> &'static HashMap<Lang, HashMap<&'static str, &'static str>> {
+    static TABLES: OnceLock<HashMap<Lang, HashMap<&'static str, &'static str>>> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        Lang::ALL
+            .iter()
+            .map(|&lang| (lang, lang.catalog().iter().copied().collect()))
+            .collect()
+    })
+}
+
+/// Mensagem de uma chave no idioma informado; cai para pt-BR e, em último
+/// caso, para a própria chave.
+pub fn tr_in(lang: Lang, key: &'static str) -> &'static str {
+    let tables = tables();
+    tables[&lang]
+        .get(key)
+        .or_else(|| tables[&Lang::PtBr].get(key))
+        .copied()
+        .unwrap_or(key)
+}
+
+/// Mensagem de uma chave no idioma em uso.
+pub fn tr(key: &'static str) -> &'static str {
+    tr_in(lang(), key)
+}
+
+/// Preenche os marcadores `{nome}` de uma mensagem.
+pub fn format(template: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut result = template.to_string();
+    for (name, value) in args {
+        result = result.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    result
+}
+
+/// Mensagem traduzida: `t!("chave")` ou `t!("chave", nome = valor, ...)`.
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::tr($key).to_string()
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::format(
+            $crate::i18n::tr($key),
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+],
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn placeholders(message: &str) -> BTreeSet<&str> {
+        message
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn test_every_key_exists_in_every_catalog() {
+        let reference: BTreeSet<_> = Lang::PtBr.catalog().iter().map(|(k, _)| *k).collect();
+        for lang in Lang::ALL {
+            let catalog = lang.catalog();
+            let keys: BTreeSet<_> = catalog.iter().map(|(k, _)| *k).collect();
+            assert_eq!(keys.len(), catalog.len(), "chave duplicada em {lang}");
+            assert_eq!(
+                reference.difference(&keys).collect::<Vec<_>>(),
+                Vec::<&&str>::new(),
+                "chaves ausentes em {lang}"
+            );
+            assert_eq!(
+                keys.difference(&reference).collect::<Vec<_>>(),
+                Vec::<&&str>::new(),
+                "chaves extras em {lang}"
+            );
+            for (key, message) in catalog {
+                assert_eq!(
+                    placeholders(message),
+                    placeholders(tr_in(Lang::PtBr, key)),
+                    "marcadores diferentes em {lang}: {key}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_lang_parse() {
+        assert_eq!(Lang::parse("pt-BR"), Some(Lang::PtBr));
+        assert_eq!(Lang::parse("pt_BR.UTF-8"), Some(Lang::PtBr));
+        assert_eq!(Lang::parse("en_US.UTF-8"), Some(Lang::En));
+        assert_eq!(Lang::parse("EN"), Some(Lang::En));
+        assert_eq!(Lang::parse("C"), None);
+        assert_eq!(Lang::parse(""), None);
+    }
+
+    #[test]
+    fn test_lookup_and_format() {
+        assert_eq!(tr_in(Lang::En, "tools.unknown_version"), "unknown");
+        assert_eq!(tr_in(Lang::En, "chave.inexistente"), "chave.inexistente");
+        assert_eq!(
+            format(
+                tr_in(Lang::En, "cli.update_done"),
+                &[("from", &"1"), ("to", &2)]
+            ),
+            "✅ yt-dlp updated: 1 → 2"
+        );
+    }
+}
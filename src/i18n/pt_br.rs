@@ -0,0 +1,423 @@
+//! Catálogo em português do Brasil (idioma padrão).
+
+pub const MESSAGES: &[(&str, &str)] = &[
+    // Linha de comando
+    ("cli.value.file", "ARQUIVO"),
+    ("cli.value.format", "FORMATO"),
+    ("cli.value.name", "NOME"),
+    ("cli.value.speed", "VELOCIDADE"),
+    ("cli.value.terms", "TERMOS"),
+    ("cli.value.duration", "DURAÇÃO"),
+    ("cli.value.minutes", "MINUTOS"),
+    ("cli.value.date", "DATA"),
+    ("cli.value.start", "INÍCIO"),
+    ("cli.value.end", "FIM"),
+    ("cli.value.address", "ENDEREÇO"),
+    ("cli.value.language", "IDIOMA"),
+    ("cli.value.standard", "PADRÃO"),
+    ("cli.value.version", "VERSÃO"),
+    ("cli.about", "Ferramenta CLI para baixar vídeos do YouTube"),
+    ("cli.arg.url", "URL do vídeo do YouTube (opcional no modo interativo)"),
+    ("cli.arg.output", "Nome do arquivo de saída"),
+    ("cli.arg.audio_only", "Baixar apenas o áudio em formato MP3"),
+    ("cli.arg.audio_format", "Formato do áudio com -a (mp3, m4a, opus...) ou 'original' para não converter"),
+    ("cli.arg.interactive", "Modo interativo"),
+    ("cli.arg.lang", "Idioma das mensagens (pt-BR, en)"),
+    ("cli.arg.live", "Gravar uma transmissão ao vivo, esperando uma live agendada começar"),
+    ("cli.arg.live_from_start", "Gravar a live desde o início, e não a partir de agora"),
+    ("cli.arg.duration", "Parar a gravação depois desse tempo (segundos, M:SS ou H:MM:SS)"),
+    ("cli.arg.segment", "Dividir a gravação em partes de N minutos (padrão: 60; 0 desliga)"),
+    ("cli.arg.normalize", "Normalizar o volume do áudio extraído pelo padrão dado"),
+    ("cli.arg.target_lufs", "Volume integrado desejado na normalização, em LUFS (padrão: -16)"),
+    ("cli.arg.analyze_loudness", "Só medir e informar o volume do áudio, sem alterá-lo"),
+    ("cli.arg.preset", "Converter o arquivo para um aparelho com limitações de codec, resolução ou tamanho"),
+    ("cli.arg.tui", "Painel em tela cheia com a fila de downloads"),
+    ("cli.arg.search", "Buscar vídeos no YouTube e escolher quais baixar"),
+    ("cli.arg.results", "Quantidade de resultados da busca"),
+    ("cli.filter.min_duration", "Ignorar vídeos mais curtos que isso (segundos ou M:SS)"),
+    ("cli.filter.max_duration", "Ignorar vídeos mais longos que isso (segundos ou M:SS)"),
+    ("cli.filter.date_after", "Só vídeos publicados nesta data ou depois (AAAA-MM-DD)"),
+    ("cli.filter.date_before", "Só vídeos publicados nesta data ou antes (AAAA-MM-DD)"),
+    ("cli.filter.match_title", "Baixar só vídeos cujo título casa com esta expressão regular"),
+    ("cli.filter.reject_title", "Ignorar vídeos cujo título casa com esta expressão regular"),
+    ("cli.filter.skip_shorts", "Ignorar Shorts"),
+    ("cli.filter.skip_live", "Ignorar lives, agendadas ou já encerradas"),
+    ("cli.arg.attempts", "Total de tentativas em falhas passageiras (padrão: configuração)"),
+    ("cli.arg.resume", "Retomar downloads interrompidos em execuções anteriores"),
+    ("cli.arg.limit_rate", "Velocidade máxima dos downloads (ex.: 2M, 500K); ignora os horários da configuração"),
+    ("cli.arg.cookies", "Arquivo de cookies (formato Netscape) para vídeos que exigem login"),
+    ("cli.arg.proxy", "Proxy HTTP ou SOCKS5 (ex.: socks5://127.0.0.1:1080)"),
+    ("cli.arg.user_agent", "User-agent enviado pelo yt-dlp"),
+    ("cli.arg.profile", "Perfil de acesso da configuração (cookies, proxy, user-agent)"),
+    ("cli.doctor.about", "Verifica dependências, versões e o diretório de saída"),
+    ("cli.doctor.dir", "Diretório de saída a verificar"),
+    ("cli.update.about", "Atualiza o yt-dlp (cópia gerenciada ou do sistema)"),
+    ("cli.update.pin", "Instalar e fixar uma versão específica"),
+    ("cli.update.unpin", "Remover a versão fixada e atualizar"),
+    ("cli.update.rollback", "Voltar para a versão anterior"),
+    ("cli.stats.about", "Mostra as estatísticas de downloads"),
+    ("cli.stats.today", "Apenas os downloads de hoje"),
+    ("cli.stats.week", "Apenas os últimos 7 dias"),
+    ("cli.stats.all", "Todos os downloads (padrão)"),
+    ("cli.serve.about", "Inicia a API HTTP local para disparar downloads"),
+    ("cli.serve.bind", "Endereço e porta em que o servidor escuta"),
+    ("cli.serve.token", "Token exigido como Authorization: Bearer (padrão: server.token da configuração)"),
+    ("cli.serve.dir", "Diretório onde os arquivos baixados são gravados"),
+    ("cli.subscribe.about", "Assina um canal ou playlist para o watch baixar os envios novos"),
+    ("cli.subscribe.url", "URL do canal (@nome, channel/...) ou da playlist"),
+    ("cli.subscribe.backfill", "Baixar também os vídeos já publicados"),
+    ("cli.unsubscribe.about", "Remove uma assinatura"),
+    ("cli.subscriptions.about", "Lista as assinaturas"),
+    ("cli.info.about", "Mostra os metadados de um vídeo, incluindo o status de uma live"),
+    ("cli.gif.about", "Converte um trecho de um vídeo em GIF (ou WebP animado), baixando só o trecho"),
+    ("cli.gif.start", "Início do trecho, em segundos, M:SS ou H:MM:SS"),
+    ("cli.gif.end", "Fim do trecho, em segundos, M:SS ou H:MM:SS"),
+    ("cli.gif.width", "Largura máxima da animação, em pixels"),
+    ("cli.gif.fps", "Quadros por segundo"),
+    ("cli.gif.webp", "Gerar um WebP animado em vez de GIF"),
+    ("cli.gif.output", "Nome do arquivo de saída, sem extensão"),
+    ("cli.watch.about", "Verifica as assinaturas periodicamente e baixa os envios novos"),
+    ("cli.watch.interval", "Minutos entre verificações (padrão: watch.interval_mins da configuração)"),
+    ("cli.watch.once", "Verificar uma vez e sair"),
+    ("cli.watch.dir", "Diretório onde os vídeos novos são gravados"),
+    ("cli.downloading_audio", "Baixando áudio de:"),
+    ("cli.downloading_video", "Baixando vídeo de:"),
+    ("cli.recording_live", "Gravando transmissão ao vivo de:"),
+    ("cli.download_done", "✅ Download concluído com sucesso!"),
+    ("cli.url_required", "❌ URL é obrigatória no modo não-interativo. Use -i para modo interativo."),
+    ("cli.download_error", "❌ Erro no download: {error}"),
+    ("cli.update_hint", "💡 Esse erro costuma ser resolvido atualizando o yt-dlp: execute 'update-backend'."),
+    ("cli.update_current", "✅ yt-dlp já está na versão {version}"),
+    ("cli.update_done", "✅ yt-dlp atualizado: {from} → {to}"),
+    // Modo interativo
+    ("interactive.welcome", "🚀 Bem-vindo ao modo interativo!"),
+    ("interactive.help_hint", "Digite 'help' para ver os comandos disponíveis."),
+    ("interactive.exit_hint", "Digite 'exit' ou 'quit' para sair."),
+    ("interactive.thanks", "👋 Obrigado por usar o YouTube Downloader!"),
+    ("interactive.session_total", "📊 Total de downloads nesta sessão: {count}"),
+    ("interactive.history_error", "⚠️  Não foi possível salvar o histórico: {error}"),
+    ("interactive.diagnosing", "Diagnosticando o ambiente"),
+    ("interactive.unknown_command", "❌ Comando desconhecido: '{command}'. Digite 'help' para ver os comandos disponíveis."),
+    ("interactive.ctrl_c", "CTRL-C detectado. Digite 'exit' para sair."),
+    ("interactive.ctrl_d", "CTRL-D detectado. Saindo..."),
+    ("interactive.readline_error", "Erro: {error}"),
+    ("shell.unclosed_quote", "❌ Aspas {quote} sem fechamento"),
+    ("shell.trailing_backslash", "❌ A linha termina com '\\' sem um caractere para escapar"),
+    ("repl.syntax.download", "<URL> [-a] [-o nome]"),
+    ("repl.syntax.search", "<termos> [-n 10]"),
+    ("repl.syntax.get", "<1,4-6> [-a]"),
+    ("repl.syntax.resume", "[--discard]"),
+    ("repl.syntax.throttle", "[2M|off|auto]"),
+    ("repl.syntax.stats", "[--today|--week|--all]"),
+    ("repl.syntax.update_backend", "[--pin VERSÃO|--unpin|--rollback]"),
+    // Ajuda
+    ("help.title", "📋 Comandos disponíveis:"),
+    ("help.download", "Baixar vídeo em MP4"),
+    ("help.download_audio", "Baixar apenas áudio em MP3"),
+    ("help.download_named_syntax", "download <URL> -o nome"),
+    ("help.download_named", "Baixar com nome personalizado"),
+    ("help.url", "Baixar vídeo diretamente (atalho)"),
+    ("help.search", "Buscar vídeos no YouTube"),
+    ("help.get", "Baixar resultados da última busca"),
+    ("help.resume", "Retomar (ou descartar) downloads interrompidos"),
+    ("help.throttle", "Ver ou mudar o limite de banda dos próximos downloads"),
+    ("help.help", "Mostrar esta ajuda"),
+    ("help.clear", "Limpar a tela"),
+    ("help.stats", "Estatísticas (--today, --week, --all)"),
+    ("help.demo", "Demonstração de animações"),
+    ("help.doctor", "Diagnosticar dependências"),
+    ("help.update_backend", "Atualizar o yt-dlp (--pin V, --unpin, --rollback)"),
+    ("help.exit", "Sair do programa"),
+    ("help.tips", "💡 Dicas:"),
+    ("help.tip_paste", "Você pode colar URLs diretamente"),
+    ("help.tip_shorts", "Suporte completo para YouTube Shorts"),
+    ("help.tip_flags", "Use -a para áudio MP3 e -o para nome personalizado"),
+    ("help.tip_formats", "Vídeos são baixados em MP4, áudios em MP3"),
+    ("help.tip_history", "Histórico de comandos disponível (↑/↓)"),
+    ("help.tip_keys", "Durante um download: p pausa, c cancela, v mostra o yt-dlp e b manda para o segundo plano"),
+    // Animações
+    ("anim.loading", "Carregando YouTube Downloader..."),
+    ("anim.loaded", "✅ YouTube Downloader carregado!"),
+    ("anim.goodbye_finishing", "👋 Finalizando..."),
+    ("anim.goodbye_saving", "💾 Salvando histórico..."),
+    ("anim.goodbye_cache", "🧹 Limpando cache..."),
+    ("anim.goodbye_done", "✨ Concluído!"),
+    ("anim.loading_help", "📋 Carregando ajuda..."),
+    ("anim.clearing", "Limpando tela..."),
+    ("anim.cleared", "✨ Tela limpa!"),
+    ("anim.computing_stats", "Calculando estatísticas"),
+    ("anim.stats_session", "📊 Downloads nesta sessão: {count}"),
+    ("anim.stats_congrats", "🎉 Parabéns pelos downloads!"),
+    ("anim.stats_first", "💡 Que tal fazer seu primeiro download?"),
+    ("anim.demo_title", "🎪 Demonstração de Animações"),
+    ("anim.demo_spinner", "Demonstrando spinner"),
+    ("anim.demo_progress", "Demonstrando barra de progresso"),
+    ("anim.demo_typing", "Este é um efeito de digitação! 🎯"),
+    ("anim.demo_done", "✨ Demonstração concluída!"),
+    ("anim.starting_download", "Iniciando download"),
+    ("anim.downloading", "Baixando"),
+    ("anim.url_detected", "🔍 URL do YouTube detectada! Analisando..."),
+    ("anim.checking_video", "Verificando vídeo"),
+    ("anim.success", "Download concluído com sucesso!"),
+    ("anim.error", "Erro no download!"),
+    ("anim.matrix", "🔢 Efeito Matrix:"),
+    // Download
+    ("download.checking_tool", "🔍 Verificando se {tool} está instalado..."),
+    ("download.tool_missing", "❌ {tool} não encontrado!"),
+    ("download.fetching_info", "🔍 Obtendo informações do vídeo..."),
+    ("download.short_detected", "📱 YouTube Short detectado!"),
+    ("download.audio_format", "🎵 Baixando áudio em formato {format}..."),
+    ("download.audio_original", "🎵 Baixando áudio no formato original..."),
+    ("download.video_mp4", "🎬 Baixando vídeo em formato MP4..."),
+    ("download.audio_saved", "💾 Áudio baixado com sucesso!"),
+    ("download.video_saved", "💾 Vídeo baixado com sucesso!"),
+    ("download.saved_verified", "{saved} ({details}, verificado)"),
+    ("verify.no_ffprobe", "⚠️  ffprobe não encontrado; o arquivo não foi verificado."),
+    ("verify.failed", "O arquivo {path} não passou na verificação: {problem}"),
+    ("verify.unreadable", "o ffprobe não conseguiu lê-lo ({error})"),
+    ("verify.missing_video", "não há faixa de vídeo"),
+    ("verify.missing_audio", "não há faixa de áudio"),
+    ("verify.duration", "a duração é {actual}s, mas o vídeo tem {expected}s"),
+    ("retry.waiting", "🔁 Tentativa {attempt}/{attempts} falhou ({error}); tentando de novo em {seconds}s..."),
+    ("retry.succeeded", "✅ Concluído após {attempts} tentativas."),
+    ("retry.gave_up", "🛑 A tentativa {attempt}/{attempts} falhou com um erro permanente; não há por que tentar de novo."),
+    // Instalação e atualização do yt-dlp
+    ("provision.missing", "❌ yt-dlp não encontrado!"),
+    ("provision.confirm", "📦 Instalar uma cópia gerenciada do yt-dlp no diretório de dados do aplicativo?"),
+    ("provision.yes_no", "[s/N]"),
+    ("provision.installing", "📦 Instalando yt-dlp via {method}..."),
+    ("provision.installed", "✅ yt-dlp {version} instalado com sucesso via {method}!"),
+    ("provision.method_failed", "⚠️  {method}: {reason}"),
+    ("provision.all_failed", "Não foi possível instalar o yt-dlp ({failures}). Instale manualmente, por exemplo: pipx install yt-dlp"),
+    ("provision.method_binary", "binário"),
+    ("provision.program_not_found", "{program} não encontrado"),
+    ("provision.program_failed", "{program} falhou: {reason}"),
+    ("provision.unknown_error", "erro desconhecido"),
+    ("provision.fetch_failed", "falha ao baixar {url}: {error}"),
+    ("provision.checksum_not_listed", "checksum de '{asset}' não encontrado em {url}"),
+    ("provision.no_sha256", "nenhum SHA-256 configurado para o binário"),
+    ("provision.sha256_mismatch", "SHA-256 não confere (esperado {expected}, obtido {actual})"),
+    ("provision.cannot_pin_url", "não é possível fixar versão com a URL {url} (esperado .../latest/download/...)"),
+    ("update.pinned", "O yt-dlp está fixado na versão {version}. Use --unpin para voltar a receber atualizações."),
+    ("update.no_previous", "Nenhuma versão anterior registrada para voltar."),
+    ("update.updating", "🔄 Atualizando yt-dlp {from} via {method}..."),
+    ("update.failed", "Falha ao atualizar o yt-dlp: {reason}"),
+    ("update.rollback_unmanaged", "O rollback só está disponível para a cópia gerenciada do yt-dlp."),
+    ("update.system_failed", "Falha ao atualizar o yt-dlp do sistema ({reason}). Se ele veio de um gerenciador de pacotes, atualize por ele."),
+    ("update.no_backup", "backup do binário anterior não encontrado"),
+    // Estatísticas
+    ("stats.title", "📊 Estatísticas — {period}"),
+    ("stats.period_today", "hoje"),
+    ("stats.period_week", "últimos 7 dias"),
+    ("stats.period_all", "desde sempre"),
+    ("stats.downloads", "Downloads:"),
+    ("stats.failures", "Falhas:"),
+    ("stats.bytes", "Dados baixados:"),
+    ("stats.duration", "Duração total:"),
+    ("stats.channels", "Canais mais baixados:"),
+    // Diagnóstico
+    ("doctor.title", "🩺 Diagnóstico do ambiente"),
+    ("doctor.all_good", "🎉 Tudo pronto para baixar!"),
+    ("doctor.how_to_fix", "🛠️  Como resolver:"),
+    ("doctor.not_found", "não encontrado"),
+    ("doctor.fix_ffmpeg", "instale o ffmpeg (ex.: sudo apt install ffmpeg, brew install ffmpeg ou winget install ffmpeg)"),
+    ("doctor.fix_ffprobe", "o ffprobe acompanha o pacote do ffmpeg; reinstale o ffmpeg completo"),
+    ("doctor.fix_python", "necessário apenas para instalar o yt-dlp via pipx/venv; instale o Python 3"),
+    ("doctor.fix_ytdlp_missing", "faça um download para instalar a cópia gerenciada ou instale com: pipx install yt-dlp"),
+    ("doctor.managed_via", " (gerenciado via {method})"),
+    ("doctor.ytdlp_age", "{detail} — {age} dias desde o lançamento"),
+    ("doctor.fix_ytdlp_old", "versões antigas costumam quebrar com mudanças do YouTube; atualize com: youtube_video_downloader update-backend"),
+    ("doctor.label_output", "saída"),
+    ("doctor.writable", "{dir} tem permissão de escrita"),
+    ("doctor.not_writable", "não é possível escrever em {dir}: {error}"),
+    ("doctor.fix_writable", "escolha outro diretório de saída ou ajuste as permissões"),
+    ("doctor.label_space", "espaço"),
+    ("doctor.low_space", "apenas {free} livres"),
+    ("doctor.free_space", "{free} livres"),
+    ("doctor.space_unknown", "não foi possível verificar"),
+    ("doctor.fix_space", "libere espaço em disco antes de baixar vídeos longos"),
+    // Controles durante o download
+    ("controls.keys", "[p] pausar  [c] cancelar  [v] detalhes  [b] segundo plano"),
+    ("controls.waiting", "preparando..."),
+    ("controls.paused", "pausado"),
+    ("controls.cancelling", "cancelando..."),
+    ("controls.verbose", "detalhes"),
+    ("controls.backgrounded", "📥 O download #{id} continua em segundo plano."),
+    ("controls.bg_done", "✅ Download #{id} concluído em segundo plano: {url}"),
+    ("controls.bg_cancelled", "⏹️  Download #{id} em segundo plano cancelado."),
+    ("controls.bg_failed", "❌ Download #{id} em segundo plano falhou: {error}"),
+    ("controls.bg_cancelling", "⏹️  Cancelando {count} download(s) em segundo plano..."),
+    // Busca
+    ("search.searching", "🔎 Buscando no YouTube..."),
+    ("search.title", "🔎 {count} resultado(s) para \"{query}\":"),
+    ("search.no_results", "Nenhum resultado para \"{query}\"."),
+    ("search.hint", "Use 'get 3' ou 'get 1,4-6 -a' para baixar."),
+    ("search.pick_prompt", "Quais baixar? (ex.: 3 ou 1,4-6 -a; Enter para sair) "),
+    ("search.invalid_selection", "Seleção inválida: \"{part}\". Use números como 3 ou 1,4-6."),
+    ("search.out_of_range", "Não há resultado {number}; a busca trouxe {count}."),
+    ("search.no_metadata", "o yt-dlp não devolveu metadados do vídeo"),
+    ("search.nothing_to_get", "Nenhuma busca feita ainda. Use 'search <termos>' primeiro."),
+    ("search.downloading", "📥 [{current}/{total}] {title}"),
+    ("search.arg_query", "Termos da busca"),
+    ("search.arg_selection", "Números dos resultados, como 3 ou 1,4-6"),
+    // Downloads interrompidos
+    ("resume.found", "⏸️  {count} download(s) interrompido(s) em uma execução anterior."),
+    ("resume.hint_interactive", "Digite 'resume' para continuar de onde pararam ou 'resume --discard' para descartá-los."),
+    ("resume.hint_cli", "Use --resume para continuar de onde pararam."),
+    ("resume.nothing", "Nenhum download interrompido."),
+    ("resume.resuming", "🔄 [{current}/{total}] Retomando {url}"),
+    ("resume.discarded", "🗑️  {count} download(s) interrompido(s) descartado(s)."),
+    ("resume.discard_failed", "Não foi possível descartar os downloads: {error}"),
+    ("resume.arg_discard", "Descartar os downloads interrompidos e seus arquivos parciais"),
+    // Limite de banda
+    ("bandwidth.unlimited", "sem limite"),
+    ("bandwidth.invalid_rate", "Velocidade inválida: \"{rate}\". Use valores como 2M, 500K ou off."),
+    ("bandwidth.invalid_time", "Horário inválido: \"{time}\". Use HH:MM, como 22:00."),
+    ("bandwidth.limited", "🐢 Limite de banda: {limit}"),
+    ("bandwidth.current", "🐢 Limite para os próximos downloads: {limit} ({source})"),
+    ("bandwidth.source_session", "definido nesta sessão"),
+    ("bandwidth.source_config", "pela configuração e horário atual"),
+    ("bandwidth.arg_rate", "Nova velocidade máxima, off para sem limite ou auto para seguir a configuração"),
+    // Cookies, proxy e perfis
+    ("access.using_cookies", "🍪 Usando cookies de login"),
+    ("access.using_proxy", "🌐 Proxy: {proxy}"),
+    ("access.unknown_profile", "Perfil de acesso \"{name}\" não existe em profiles na configuração."),
+    ("access.invalid_proxy", "Proxy inválido: \"{proxy}\". Use http://, https://, socks4:// ou socks5://host:porta."),
+    ("access.invalid_cookies", "Arquivo de cookies inválido ({path}): {reason}"),
+    ("access.cookies_bad_line", "a linha {line} não está no formato Netscape"),
+    ("access.cookies_empty", "nenhum cookie encontrado"),
+    // Servidor HTTP
+    ("server.listening", "🌐 API ouvindo em http://{addr} (Ctrl+C para encerrar)"),
+    ("server.open_warning", "⚠️  {addr} não é um endereço local e a API está sem token; use --token ou server.token na configuração."),
+    ("server.bind_failed", "Não foi possível ouvir em {addr}: {error}"),
+    ("server.stopping", "⏹️  Encerrando o servidor e cancelando downloads em andamento..."),
+    ("server.invalid_request", "Requisição inválida: {error}"),
+    ("server.invalid_output", "A saída \"{output}\" deve ser um nome de arquivo, sem diretórios."),
+    ("server.unauthorized", "Token ausente ou inválido."),
+    ("server.job_not_found", "Job {id} não encontrado."),
+    ("server.origin_denied", "Origem não permitida: {origin}. Inclua-a em server.allowed_origins na configuração."),
+    ("server.pairing_code", "🔗 Código de pareamento para extensões e bookmarklets: {code}"),
+    ("server.pairing_invalid", "Código de pareamento inválido."),
+    ("server.pairing_locked", "Pareamento indisponível; reinicie o serve para gerar um novo código."),
+    ("server.pairing_failed", "Não foi possível salvar o pareamento: {error}"),
+    ("server.unnamed_client", "navegador"),
+    // Assinaturas
+    ("subscriptions.invalid_url", "Não é um canal ou playlist do YouTube: {url}"),
+    ("subscriptions.exists", "Já existe uma assinatura para {url}."),
+    ("subscriptions.seeded", "{count} vídeo(s) já publicado(s) marcado(s) como vistos; só envios novos serão baixados."),
+    ("subscriptions.added", "✅ Assinatura adicionada: {url}"),
+    ("subscriptions.removed", "🗑️  Assinatura removida: {url}"),
+    ("subscriptions.not_found", "Nenhuma assinatura para {url}."),
+    ("subscriptions.save_failed", "Não foi possível salvar a assinatura: {error}"),
+    ("subscriptions.none", "Nenhuma assinatura. Use 'subscribe <URL>' para acompanhar um canal ou playlist."),
+    ("subscriptions.title", "📺 Assinaturas ({count})"),
+    ("subscriptions.detail_profile", "perfil {name}"),
+    ("subscriptions.detail_audio", "só áudio"),
+    ("watch.checking", "🔎 Verificando {count} assinatura(s)..."),
+    ("watch.found", "🆕 {count} vídeo(s) novo(s) em {url}"),
+    ("watch.new_upload", "⬇️  {title}"),
+    ("watch.check_failed", "⚠️  Falha ao verificar {url}: {error}"),
+    ("watch.next", "⏰ Próxima verificação em {minutes} min (Ctrl+C para sair)."),
+    ("watch.stopping", "⏹️  Encerrando o watch..."),
+    // Filtros de conteúdo
+    ("filter.invalid_regex", "Expressão regular inválida no filtro de título: {error}"),
+    ("filter.invalid_date", "Data inválida: \"{date}\". Use AAAA-MM-DD ou AAAAMMDD."),
+    ("filter.invalid_duration", "Duração inválida: \"{duration}\". Use segundos, M:SS ou H:MM:SS."),
+    ("filter.listing", "📋 Listando os vídeos de {url}..."),
+    ("filter.summary", "{accepted} vídeo(s) para baixar, {skipped} ignorado(s) pelos filtros."),
+    ("filter.skipped", "⏭️  Ignorado: {title} ({reason})"),
+    ("filter.reason_too_short", "{duration}, mais curto que {min}"),
+    ("filter.reason_too_long", "{duration}, mais longo que {max}"),
+    ("filter.reason_before", "publicado em {date}, antes de {limit}"),
+    ("filter.reason_after", "publicado em {date}, depois de {limit}"),
+    ("filter.reason_title_not_matched", "título não casa com /{pattern}/"),
+    ("filter.reason_title_rejected", "título casa com /{pattern}/"),
+    ("filter.reason_short", "é um Short"),
+    ("filter.reason_live", "é uma live"),
+    ("filter.reason_no_metadata", "metadados indisponíveis: {error}"),
+    ("filter.detail_min_duration", "mínimo {duration}"),
+    ("filter.detail_max_duration", "máximo {duration}"),
+    ("filter.detail_date_after", "desde {date}"),
+    ("filter.detail_date_before", "até {date}"),
+    ("filter.detail_match_title", "título /{pattern}/"),
+    ("filter.detail_reject_title", "sem título /{pattern}/"),
+    ("filter.detail_skip_shorts", "sem Shorts"),
+    ("filter.detail_skip_live", "sem lives"),
+    // Transmissões ao vivo
+    ("live.status_not_live", "não é uma live"),
+    ("live.status_live", "🔴 ao vivo agora"),
+    ("live.status_upcoming", "⏳ agendada"),
+    ("live.status_was_live", "live encerrada, gravação disponível"),
+    ("live.status_post_live", "live encerrada, gravação em processamento"),
+    ("live.waiting_for", "⏳ Live agendada; começa em {time}. Aguardando o início..."),
+    ("live.waiting", "⏳ Live agendada. Aguardando o início..."),
+    ("live.recording", "🔴 Ao vivo agora; gravando."),
+    ("live.not_live", "ℹ️  Não é uma live em andamento; baixando o vídeo normalmente."),
+    ("live.from_start", "⏮️  Gravando desde o início da transmissão."),
+    ("live.limited", "⏱️  A gravação para depois de {duration}."),
+    ("live.limit_reached", "⏹️  Tempo limite atingido; finalizando a gravação..."),
+    ("live.split_done", "✂️  Gravação dividida em {count} partes."),
+    ("live.split_failed", "⚠️  Não foi possível dividir a gravação: {error}"),
+    // Normalização de volume
+    ("loudness.analyzing", "🔊 Medindo o volume (EBU R128)..."),
+    ("loudness.measured", "🔊 Volume medido: {integrated} LUFS, pico {peak} dBTP, faixa {range} LU."),
+    ("loudness.normalized", "🔊 Normalizado para {target} LUFS: agora {integrated} LUFS, pico {peak} dBTP."),
+    ("loudness.sidecar", "📝 Medições salvas em {path}"),
+    ("loudness.failed", "⚠️  Não foi possível normalizar o volume: {error}"),
+    ("loudness.no_measurement", "o ffmpeg não mediu o volume; o áudio pode estar mudo"),
+    ("loudness.invalid_target", "volume inválido: \"{value}\"; use um valor em LUFS entre -70 e -5, como -16"),
+    // Presets de aparelhos
+    ("preset.unknown", "preset desconhecido: \"{name}\""),
+    ("preset.converting", "📱 Convertendo para o preset {preset}..."),
+    ("preset.done", "📱 Convertido para {preset} ({size} MB)."),
+    ("preset.no_duration", "a duração do vídeo é desconhecida; não há como calcular o bitrate para o limite de tamanho"),
+    ("preset.too_long", "o vídeo é longo demais para caber em {size} MB"),
+    ("preset.failed", "o ffmpeg falhou: {error}"),
+    ("preset.kept", "⚠️  Não foi possível converter para {preset}, e o arquivo original foi mantido: {error}"),
+    // Trechos e animações
+    ("clip.invalid_range", "O fim do trecho precisa vir depois do início."),
+    ("animation.exporting", "🎞️  Gerando animação do trecho {start}–{end}..."),
+    ("animation.rendering", "🎨 Convertendo o trecho em {format}..."),
+    ("animation.done", "✨ Animação salva em {path} ({size} MB)."),
+    ("animation.no_clip", "o yt-dlp não informou o arquivo do trecho baixado"),
+    ("animation.failed", "A conversão do trecho em animação falhou: {error}"),
+    // Metadados de um vídeo (info)
+    ("info.channel", "Canal"),
+    ("info.duration", "Duração"),
+    ("info.upload_date", "Publicado em"),
+    ("info.live_status", "Transmissão"),
+    ("info.starts_in", "Começa em"),
+    // Painel em tela cheia
+    ("tui.title", "YouTube Downloader"),
+    ("tui.summary", "{total} downloads, {active} em andamento"),
+    ("tui.header_status", "Estado"),
+    ("tui.header_speed", "Velocidade"),
+    ("tui.header_eta", "Restante"),
+    ("tui.header_target", "Destino"),
+    ("tui.status_running", "Baixando"),
+    ("tui.status_paused", "Pausado"),
+    ("tui.status_done", "Concluído"),
+    ("tui.status_failed", "Falhou"),
+    ("tui.status_cancelled", "Cancelado"),
+    ("tui.empty", "Cole uma URL do YouTube abaixo e tecle Enter."),
+    ("tui.log", "Log"),
+    ("tui.prompt", "URL> "),
+    ("tui.keys", "Enter baixar · ↑↓ selecionar · com a entrada vazia: p pausar · c cancelar · r repetir · o abrir pasta · q sair"),
+    ("tui.no_job", "Nenhum download selecionado."),
+    ("tui.pause_unavailable", "Não foi possível pausar este download agora."),
+    ("tui.open_failed", "Não foi possível abrir {path}: {error}"),
+    ("tui.stopping", "⏹️  Cancelando downloads em andamento..."),
+    // Erros
+    ("error.invalid_url", "URL inválida. Por favor, forneça uma URL válida do YouTube."),
+    ("error.missing_ffmpeg", "O ffmpeg é necessário para juntar áudio e vídeo. Instale o ffmpeg e tente novamente."),
+    ("error.missing_ytdlp", "yt-dlp não encontrado. Instale com 'pipx install yt-dlp' ou permita a instalação gerenciada."),
+    ("error.missing_tool", "{tool} não encontrado. Instale o {tool} e tente novamente."),
+    ("error.backend", "Erro no download: {stderr}"),
+    ("error.cancelled", "Download cancelado."),
+    ("error.retries_exhausted", "Falhou após {attempts} tentativas: {error}"),
+    ("error.io", "Erro ao executar processo externo: {error}"),
+    // Diversos
+    ("config.invalid", "⚠️  Configuração inválida em {path}: {error}"),
+    ("config.refuse_save", "A configuração em {path} é inválida ({error}); corrija o arquivo antes de alterá-la."),
+    ("tools.unknown_version", "desconhecida"),
+];
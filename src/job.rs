@@ -0,0 +1,242 @@
+//! Acompanhamento e controle de um download em andamento.
+//!
+//! O yt-dlp roda com `--newline`, então cada atualização de progresso chega
+//! como uma linha própria; [`parse_progress`] as transforma em [`Progress`].
+//! Um [`JobControl`] permite pausar, retomar e cancelar o processo a partir
+//! de outra tarefa.
+
+use regex::Regex;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, Once, OnceLock};
+use tokio::sync::Notify;
+
+/// Progresso informado pelo yt-dlp; os textos vêm como ele os imprime.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Progress {
+    pub percent: f64,
+    /// Tamanho total, como `10.00MiB`.
+    pub total: Option<String>,
+    /// Velocidade, como `1.00MiB/s`.
+    pub speed: Option<String>,
+    /// Tempo restante, como `00:05`.
+    pub eta: Option<String>,
+}
+
+/// Algo que aconteceu durante um download.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobEvent {
+    /// Mensagem de status do próprio programa.
+    Status(String),
+    Progress(Progress),
+    /// Linha de saída do yt-dlp que não é progresso.
+    Log(String),
+}
+
+/// Interpreta linhas como `[download]  42.5% of ~10.00MiB at 1.00MiB/s ETA 00:05`.
+pub fn parse_progress(line: &str) -> Option<Progress> {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| {
+        Regex::new(
+            r"^\[download\]\s+(\d+(?:\.\d+)?)%(?:\s+of\s+~?\s*(\S+))?(?:\s+at\s+(Unknown B/s|\S+))?(?:\s+ETA\s+(\S+))?",
+        )
+        .unwrap()
+    });
+    let captures = pattern.captures(line.trim_end())?;
+    let text = |index| captures.get(index).map(|m| m.as_str().to_string());
+    Some(Progress {
+        percent: captures[1].parse().ok()?,
+        total: text(2),
+        speed: text(3),
+        eta: text(4),
+    })
+}
+
+/// Controle compartilhado de um download: clones apontam para o mesmo processo.
+#[derive(Debug, Clone, Default)]
+pub struct JobControl {
+    inner: Arc<ControlState>,
+}
+
+#[derive(Debug, Default)]
+struct ControlState {
+    /// PID do yt-dlp em execução, que lidera o próprio grupo de processos
+    /// com os ffmpeg que ele inicia; 0 quando não há processo.
+    pid: AtomicU32,
+    paused: AtomicBool,
+    cancelled: AtomicBool,
+    cancel: Notify,
+}
+
+impl JobControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Suspende o yt-dlp. Retorna `false` se não há processo ou a plataforma
+    /// não permite suspender.
+    pub fn pause(&self) -> bool {
+        if self.signal(Signal::Stop) {
+            self.inner.paused.store(true, Ordering::SeqCst);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Retoma um processo pausado.
+    pub fn resume(&self) -> bool {
+        if self.signal(Signal::Continue) {
+            self.inner.paused.store(false, Ordering::SeqCst);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Pede ao yt-dlp que encerre como num Ctrl+C; numa live, ele finaliza o
+    /// arquivo com o que foi gravado até ali.
+    pub fn interrupt(&self) -> bool {
+        self.signal(Signal::Interrupt)
+    }
+
+    /// Pausa ou retoma, conforme o estado atual. Retorna se ficou pausado.
+    pub fn toggle_pause(&self) -> bool {
+        if self.is_paused() {
+            self.resume();
+        } else {
+            self.pause();
+        }
+        self.is_paused()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.inner.paused.load(Ordering::SeqCst)
+    }
+
+    /// Pede o cancelamento; o processo é encerrado pela tarefa do download.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        self.inner.cancel.notify_one();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Termina quando [`cancel`](Self::cancel) for chamado.
+    pub(crate) async fn cancelled(&self) {
+        if !self.is_cancelled() {
+            self.inner.cancel.notified().await;
+        }
+    }
+
+    /// Faz do download o destino do Ctrl+C do terminal, que não alcança o
+    /// grupo próprio do yt-dlp: o sinal é repassado a ele e o programa sai,
+    /// como sairia sem o tratamento, deixando o job no diário.
+    pub fn forward_ctrl_c(&self) {
+        static FOREGROUND: Mutex<Option<JobControl>> = Mutex::new(None);
+        static LISTENING: Once = Once::new();
+        *FOREGROUND.lock().unwrap_or_else(|e| e.into_inner()) = Some(self.clone());
+        LISTENING.call_once(|| {
+            tokio::spawn(async {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    if let Some(control) =
+                        FOREGROUND.lock().unwrap_or_else(|e| e.into_inner()).take()
+                    {
+                        control.interrupt();
+                    }
+                    std::process::exit(130);
+                }
+            });
+        });
+    }
+
+    /// Mata o yt-dlp e os processos que ele iniciou.
+    pub(crate) fn kill(&self) -> bool {
+        self.signal(Signal::Kill)
+    }
+
+    pub(crate) fn attach(&self, pid: Option<u32>) {
+        self.inner.pid.store(pid.unwrap_or(0), Ordering::SeqCst);
+    }
+
+    pub(crate) fn detach(&self) {
+        // Um processo pausado não pode ficar parado para sempre
+        if self.is_paused() {
+            self.resume();
+        }
+        self.inner.pid.store(0, Ordering::SeqCst);
+    }
+
+    #[cfg(unix)]
+    fn signal(&self, signal: Signal) -> bool {
+        let pid = self.inner.pid.load(Ordering::SeqCst);
+        if pid == 0 {
+            return false;
+        }
+        let signal = match signal {
+            Signal::Stop => libc::SIGSTOP,
+            Signal::Continue => libc::SIGCONT,
+            Signal::Interrupt => libc::SIGINT,
+            Signal::Kill => libc::SIGKILL,
+        };
+        // O sinal vai ao grupo inteiro, para alcançar também os ffmpeg de
+        // junção e de fragmentos
+        // SAFETY: `kill` apenas envia um sinal ao grupo do processo filho.
+        unsafe { libc::kill(-(pid as libc::pid_t), signal) == 0 }
+    }
+
+    #[cfg(not(unix))]
+    fn signal(&self, _signal: Signal) -> bool {
+        false
+    }
+}
+
+enum Signal {
+    Stop,
+    Continue,
+    Interrupt,
+    Kill,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_progress() {
+        let progress =
+            parse_progress("[download]  42.5% of   10.00MiB at    1.00MiB/s ETA 00:05").unwrap();
+        assert_eq!(
+            progress,
+            Progress {
+                percent: 42.5,
+                total: Some("10.00MiB".into()),
+                speed: Some("1.00MiB/s".into()),
+                eta: Some("00:05".into()),
+            }
+        );
+
+        let done = parse_progress("[download] 100% of ~ 3.20MiB in 00:00:02 at 1.5MiB/s").unwrap();
+        assert_eq!(done.percent, 100.0);
+        assert_eq!(done.total.as_deref(), Some("3.20MiB"));
+        assert_eq!(done.eta, None);
+
+        let unknown =
+            parse_progress("[download]   0.1% of 5MiB at Unknown B/s ETA Unknown").unwrap();
+        assert_eq!(unknown.speed.as_deref(), Some("Unknown B/s"));
+
+        assert_eq!(parse_progress("[download] Destination: video.mp4"), None);
+        assert_eq!(parse_progress("[youtube] abc: Downloading webpage"), None);
+    }
+
+    #[test]
+    fn test_control_without_process() {
+        let control = JobControl::new();
+        assert!(!control.pause());
+        assert!(!control.toggle_pause());
+        control.clone().cancel();
+        assert!(control.is_cancelled());
+    }
+}
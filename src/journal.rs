@@ -0,0 +1,251 @@
+//! Diário de downloads em andamento (`jobs.json` no diretório de dados).
+//!
+//! Cada download é registrado antes de o yt-dlp começar e removido quando
+//! termina. Se o processo morre no meio, a entrada fica para trás com os
+//! arquivos parciais (`.part`) informados pelo yt-dlp, e o download pode ser
+//! retomado com `--continue` numa próxima execução.
+
+use crate::access::AccessOptions;
+use crate::download::DownloadOptions;
+use crate::loudness::LoudnessOptions;
+use crate::paths;
+use crate::preset::Preset;
+use crate::stats;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// Serializa leituras e escritas do arquivo entre tarefas do mesmo processo.
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Um download registrado no diário.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub url: String,
+    /// Caminho de saída absoluto, sem extensão.
+    pub output_name: String,
+    pub audio_only: bool,
+    #[serde(default)]
+    pub audio_format: Option<String>,
+    /// Início do download, em segundos desde a época Unix.
+    pub started_at: u64,
+    /// Processo que estava baixando; 0 depois que ele desistiu do download.
+    pub pid: u32,
+    /// Cookies, proxy e user-agent usados, para retomar com o mesmo acesso.
+    #[serde(default)]
+    pub access: AccessOptions,
+    /// Destinos informados pelo yt-dlp; o parcial de cada um é `<destino>.part`.
+    #[serde(default)]
+    pub destinations: Vec<PathBuf>,
+    /// Normalização de volume pedida, para aplicá-la ao retomar.
+    #[serde(default)]
+    pub loudness: Option<LoudnessOptions>,
+    #[serde(default)]
+    pub preset: Option<Preset>,
+}
+
+impl Entry {
+    fn new(options: &DownloadOptions) -> Self {
+        Entry {
+            url: options.url.clone(),
+            output_name: absolute(&options.output_name),
+            audio_only: options.audio_only,
+            audio_format: options.audio_format.clone(),
+            started_at: stats::now(),
+            pid: std::process::id(),
+            access: options.access.clone(),
+            destinations: Vec::new(),
+            loudness: options.loudness.clone(),
+            preset: options.preset,
+        }
+    }
+
+    /// Mesmo download, ainda que registrado por outra execução.
+    fn same_job(&self, other: &Entry) -> bool {
+        self.url == other.url && self.output_name == other.output_name
+    }
+
+    /// Opções para retomar o download de onde parou.
+    pub fn options(&self) -> DownloadOptions {
+        DownloadOptions {
+            audio_format: self.audio_format.clone(),
+            resume: true,
+            access: self.access.clone(),
+            loudness: self.loudness.clone(),
+            preset: self.preset,
+            ..DownloadOptions::new(&self.url, &self.output_name, self.audio_only)
+        }
+    }
+
+    /// Arquivos temporários deixados pelo yt-dlp que ainda existem.
+    pub fn partial_files(&self) -> Vec<PathBuf> {
+        self.destinations
+            .iter()
+            .flat_map(|dest| ["part", "ytdl"].map(|ext| with_suffix(dest, ext)))
+            .filter(|path| path.exists())
+            .collect()
+    }
+}
+
+pub fn path() -> PathBuf {
+    paths::data_dir().join("jobs.json")
+}
+
+/// Downloads interrompidos: entradas cujo processo não está mais rodando.
+pub fn unfinished() -> Vec<Entry> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    load()
+        .into_iter()
+        .filter(|entry| !process_alive(entry.pid))
+        .collect()
+}
+
+/// Esquece os downloads interrompidos, apagando seus arquivos parciais.
+/// Retorna quantos foram descartados.
+pub fn discard_unfinished() -> io::Result<usize> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (dead, alive): (Vec<Entry>, Vec<Entry>) = load()
+        .into_iter()
+        .partition(|entry| !process_alive(entry.pid));
+    for file in dead.iter().flat_map(Entry::partial_files) {
+        let _ = fs::remove_file(file);
+    }
+    save(&alive)?;
+    Ok(dead.len())
+}
+
+/// Registra um download prestes a começar, substituindo uma entrada antiga
+/// do mesmo download.
+pub(crate) fn begin(options: &DownloadOptions) -> io::Result<Entry> {
+    let entry = Entry::new(options);
+    update(|entries| {
+        entries.retain(|other| !other.same_job(&entry));
+        entries.push(entry.clone());
+    })?;
+    Ok(entry)
+}
+
+/// Anota um destino informado pelo yt-dlp.
+pub(crate) fn add_destination(entry: &Entry, destination: PathBuf) -> io::Result<()> {
+    update(|entries| {
+        if let Some(stored) = entries.iter_mut().find(|other| other.same_job(entry)) {
+            if !stored.destinations.contains(&destination) {
+                stored.destinations.push(destination);
+            }
+        }
+    })
+}
+
+/// Mantém um download que falhou para ser retomado depois, ainda nesta
+/// execução.
+pub(crate) fn release(entry: &Entry) -> io::Result<()> {
+    update(|entries| {
+        if let Some(stored) = entries.iter_mut().find(|other| other.same_job(entry)) {
+            stored.pid = 0;
+        }
+    })
+}
+
+/// Remove um download que terminou ou não deve ser retomado.
+pub(crate) fn finish(entry: &Entry) -> io::Result<()> {
+    update(|entries| entries.retain(|other| !other.same_job(entry)))
+}
+
+/// Interpreta `[download] Destination: <arquivo>`.
+pub(crate) fn parse_destination(line: &str) -> Option<PathBuf> {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| Regex::new(r"^\[download\] Destination: (.+)$").unwrap());
+    let captures = pattern.captures(line.trim_end())?;
+    Some(PathBuf::from(&captures[1]))
+}
+
+fn update(change: impl FnOnce(&mut Vec<Entry>)) -> io::Result<()> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = load();
+    change(&mut entries);
+    save(&entries)
+}
+
+/// Entradas gravadas; um arquivo ausente ou corrompido conta como vazio.
+fn load() -> Vec<Entry> {
+    fs::read_to_string(path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save(entries: &[Entry]) -> io::Result<()> {
+    fs::create_dir_all(paths::data_dir())?;
+    let json = serde_json::to_string_pretty(entries).map_err(io::Error::other)?;
+    // Gravar e renomear, para que uma interrupção não deixe o arquivo pela metade
+    let tmp = path().with_extension("json.tmp");
+    fs::write(&tmp, json)?;
+    fs::rename(tmp, path())
+}
+
+fn absolute(output_name: &str) -> String {
+    let path = Path::new(output_name);
+    if path.is_absolute() {
+        return output_name.to_string();
+    }
+    std::env::current_dir()
+        .map(|dir| dir.join(path).display().to_string())
+        .unwrap_or_else(|_| output_name.to_string())
+}
+
+fn with_suffix(path: &Path, ext: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(ext);
+    PathBuf::from(name)
+}
+
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    if pid == 0 {
+        return false;
+    }
+    if pid == std::process::id() {
+        return true;
+    }
+    // SAFETY: o sinal 0 só verifica se o processo existe.
+    let result = unsafe { libc::kill(pid as libc::pid_t, 0) };
+    result == 0 || io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+fn process_alive(pid: u32) -> bool {
+    pid != 0 && pid == std::process::id()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_destination() {
+        assert_eq!(
+            parse_destination("[download] Destination: /tmp/Meu Vídeo.f137.mp4"),
+            Some(PathBuf::from("/tmp/Meu Vídeo.f137.mp4"))
+        );
+        assert_eq!(parse_destination("[download]  42.5% of 10.00MiB"), None);
+    }
+
+    #[test]
+    fn test_entry_options_resume() {
+        let mut options = DownloadOptions::new("https://youtu.be/abc", "/tmp/a", true);
+        options.audio_format = None;
+        let entry = Entry::new(&options);
+        assert_eq!(entry.output_name, "/tmp/a");
+        let resumed = entry.options();
+        assert!(resumed.resume);
+        assert_eq!(resumed.audio_format, None);
+        assert_eq!(
+            with_suffix(Path::new("/tmp/a.mp4"), "part"),
+            PathBuf::from("/tmp/a.mp4.part")
+        );
+    }
+}
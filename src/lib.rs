@@ -0,0 +1,37 @@
+//! Núcleo do YouTube Video Downloader: validação de URLs e execução do yt-dlp.
+
+#[macro_use]
+pub mod i18n;
+
+pub mod access;
+pub mod animation;
+pub mod archive;
+pub mod bandwidth;
+pub mod clip;
+pub mod config;
+pub mod doctor;
+pub mod download;
+pub mod error;
+pub mod filter;
+pub mod job;
+pub mod journal;
+pub mod live;
+pub mod loudness;
+pub mod pairing;
+pub mod paths;
+pub mod preset;
+pub mod provision;
+pub mod retry;
+pub mod search;
+pub mod server;
+pub mod stats;
+pub mod subscriptions;
+pub mod tools;
+pub mod verify;
+
+pub use config::Config;
+pub use download::{
+    download, download_video, download_with, is_youtube_url, DownloadOptions, Downloaded,
+};
+pub use error::DownloadError;
+pub use job::{JobControl, JobEvent, Progress};
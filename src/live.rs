@@ -0,0 +1,199 @@
+//! Gravação de transmissões ao vivo.
+//!
+//! O yt-dlp espera uma live agendada começar (`--wait-for-video`) e pode
+//! gravá-la desde o início (`--live-from-start`). Com uma duração máxima, a
+//! gravação é encerrada com um SIGINT, como num Ctrl+C, para que o arquivo
+//! seja finalizado; no fim, ela é dividida em partes com o ffmpeg.
+
+use crate::error::DownloadError;
+use crate::job::JobEvent;
+use crate::journal;
+use crate::search::SearchResult;
+use crate::stats;
+use crate::tools::{self, Tool};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::process::Command;
+
+/// Intervalo, em segundos, entre consultas a uma live que ainda não começou.
+const WAIT_RETRY: &str = "15-600";
+
+/// Duração de cada parte da gravação quando não informada.
+pub const DEFAULT_SEGMENT: Duration = Duration::from_secs(3600);
+
+/// Como gravar uma transmissão ao vivo.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveOptions {
+    /// Grava desde o início da transmissão, e não a partir de agora.
+    pub from_start: bool,
+    /// Encerra a gravação depois desse tempo; `None` grava até a live acabar.
+    pub max_duration: Option<Duration>,
+    /// Duração de cada parte; `None` mantém um arquivo só.
+    pub segment: Option<Duration>,
+}
+
+impl Default for LiveOptions {
+    fn default() -> Self {
+        LiveOptions {
+            from_start: false,
+            max_duration: None,
+            segment: Some(DEFAULT_SEGMENT),
+        }
+    }
+}
+
+impl LiveOptions {
+    pub(crate) fn ytdlp_args(&self) -> Vec<String> {
+        let mut args = vec!["--wait-for-video".to_string(), WAIT_RETRY.to_string()];
+        if self.from_start {
+            args.push("--live-from-start".into());
+        }
+        args
+    }
+}
+
+/// O `live_status` do yt-dlp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiveStatus {
+    NotLive,
+    Live,
+    Upcoming,
+    WasLive,
+    /// Live encerrada cuja gravação o YouTube ainda está processando.
+    PostLive,
+}
+
+impl LiveStatus {
+    pub fn parse(status: &str) -> Option<LiveStatus> {
+        Some(match status {
+            "not_live" => LiveStatus::NotLive,
+            "is_live" => LiveStatus::Live,
+            "is_upcoming" => LiveStatus::Upcoming,
+            "was_live" => LiveStatus::WasLive,
+            "post_live" => LiveStatus::PostLive,
+            _ => return None,
+        })
+    }
+
+    pub fn of(video: &SearchResult) -> Option<LiveStatus> {
+        LiveStatus::parse(video.live_status.as_deref()?)
+    }
+}
+
+impl fmt::Display for LiveStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            LiveStatus::NotLive => t!("live.status_not_live"),
+            LiveStatus::Live => t!("live.status_live"),
+            LiveStatus::Upcoming => t!("live.status_upcoming"),
+            LiveStatus::WasLive => t!("live.status_was_live"),
+            LiveStatus::PostLive => t!("live.status_post_live"),
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// Quanto falta para uma live agendada começar, como `2h 05min`.
+pub fn starts_in(video: &SearchResult, now: u64) -> Option<String> {
+    let start = video.release_timestamp?;
+    let remaining = start - now as i64;
+    (remaining > 0).then(|| stats::format_duration(remaining as f64))
+}
+
+/// O que vai acontecer ao gravar `video`, conforme o status da live.
+pub fn describe(video: &SearchResult, now: u64) -> String {
+    match LiveStatus::of(video) {
+        Some(LiveStatus::Upcoming) => match starts_in(video, now) {
+            Some(time) => t!("live.waiting_for", time = time),
+            None => t!("live.waiting"),
+        },
+        Some(LiveStatus::Live) => t!("live.recording"),
+        _ => t!("live.not_live"),
+    }
+}
+
+/// O yt-dlp começou a gravar, depois da espera por uma live agendada.
+pub(crate) fn recording_started(event: &JobEvent) -> bool {
+    match event {
+        JobEvent::Progress(_) => true,
+        JobEvent::Log(line) => journal::parse_destination(line).is_some(),
+        JobEvent::Status(_) => false,
+    }
+}
+
+/// Divide a gravação em partes `<nome>.001.<ext>`, `<nome>.002.<ext>`... sem
+/// recodificar, e apaga o arquivo original. Devolve as partes, ou nada se a
+/// gravação coube numa parte só.
+pub async fn split(path: &Path, segment: Duration) -> Result<Vec<PathBuf>, DownloadError> {
+    let ffmpeg = tools::detect(Tool::Ffmpeg).ok_or(DownloadError::MissingDependency("ffmpeg"))?;
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("mp4");
+    let stem = path.with_extension("").display().to_string();
+    let part = |n: usize| PathBuf::from(format!("{stem}.{n:03}.{ext}"));
+    let output = Command::new(&ffmpeg.program)
+        .args(["-hide_banner", "-loglevel", "error", "-y", "-i"])
+        .arg(path)
+        .args([
+            "-map",
+            "0",
+            "-c",
+            "copy",
+            "-f",
+            "segment",
+            "-reset_timestamps",
+            "1",
+        ])
+        .args(["-segment_start_number", "1", "-segment_time"])
+        .arg(segment.as_secs().max(1).to_string())
+        .arg(format!("{stem}.%03d.{ext}"))
+        .output()
+        .await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(DownloadError::Transcode(stderr.trim().to_string()));
+    }
+    let parts: Vec<PathBuf> = (1..).map(part).take_while(|path| path.exists()).collect();
+    if parts.len() <= 1 {
+        for part in &parts {
+            fs::remove_file(part)?;
+        }
+        return Ok(Vec::new());
+    }
+    fs::remove_file(path)?;
+    Ok(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ytdlp_args() {
+        let args = LiveOptions::default().ytdlp_args();
+        assert_eq!(args, ["--wait-for-video", WAIT_RETRY]);
+        let args = LiveOptions {
+            from_start: true,
+            ..LiveOptions::default()
+        }
+        .ytdlp_args();
+        assert_eq!(args.last().unwrap(), "--live-from-start");
+    }
+
+    #[test]
+    fn test_status_and_start() {
+        let video = SearchResult {
+            live_status: Some("is_upcoming".to_string()),
+            release_timestamp: Some(1_000 + 7_500),
+            ..SearchResult::default()
+        };
+        assert_eq!(LiveStatus::of(&video), Some(LiveStatus::Upcoming));
+        assert_eq!(starts_in(&video, 1_000).as_deref(), Some("2h 05min"));
+        assert_eq!(starts_in(&video, 9_000), None);
+        assert_eq!(LiveStatus::parse("is_live"), Some(LiveStatus::Live));
+        assert_eq!(LiveStatus::parse("desconhecido"), None);
+    }
+}
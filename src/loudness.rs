@@ -0,0 +1,303 @@
+//! Normalização de volume (EBU R128) dos áudios extraídos.
+//!
+//! O filtro `loudnorm` do ffmpeg roda em duas passagens: a primeira só mede o
+//! volume do arquivo; a segunda aplica a correção com os valores medidos, em
+//! modo linear, sem comprimir a dinâmica. As medições ficam no arquivo de
+//! metadados ao lado do áudio.
+
+use crate::error::DownloadError;
+use crate::tools::{self, Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// Padrão de normalização aceito por `--normalize`.
+pub const STANDARD: &str = "ebu-r128";
+
+/// Volume integrado padrão, o usado pela maioria das plataformas de podcast.
+pub const DEFAULT_TARGET_LUFS: f64 = -16.0;
+
+/// Pico real máximo depois da normalização, em dBTP.
+const TRUE_PEAK: f64 = -1.5;
+
+/// Faixa de volume desejada, em LU.
+const LOUDNESS_RANGE: f64 = 11.0;
+
+/// Como normalizar o volume de um áudio.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoudnessOptions {
+    /// Volume integrado desejado, em LUFS.
+    pub target_lufs: f64,
+    /// Só mede e informa o volume, sem alterar o arquivo.
+    #[serde(default)]
+    pub analyze_only: bool,
+}
+
+impl Default for LoudnessOptions {
+    fn default() -> Self {
+        LoudnessOptions {
+            target_lufs: DEFAULT_TARGET_LUFS,
+            analyze_only: false,
+        }
+    }
+}
+
+/// Volume medido pelo `loudnorm`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Measurement {
+    /// Volume integrado, em LUFS.
+    pub integrated: f64,
+    /// Pico real, em dBTP.
+    pub true_peak: f64,
+    /// Faixa de volume (LRA), em LU.
+    pub range: f64,
+    /// Limiar de gate, em LUFS.
+    pub threshold: f64,
+    /// Ganho que a segunda passagem deve aplicar, em LU.
+    pub offset: f64,
+}
+
+/// Resultado da análise, gravado no arquivo de metadados.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Loudness {
+    pub standard: &'static str,
+    pub target_lufs: f64,
+    /// Volume do arquivo baixado.
+    pub input: Measurement,
+    /// Volume depois da normalização; `None` se só houve a análise.
+    pub output: Option<Measurement>,
+}
+
+/// Lê o volume desejado de `--target-lufs`, como `-16` ou `-14.5`.
+pub fn parse_target(text: &str) -> Result<f64, String> {
+    text.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|lufs| (-70.0..=-5.0).contains(lufs))
+        .ok_or_else(|| t!("loudness.invalid_target", value = text))
+}
+
+/// Mede o volume e, sem `analyze_only`, normaliza o arquivo no lugar,
+/// recodificando-o com `bitrate` (bits por segundo) se conhecido. Grava as
+/// medições no arquivo de metadados e o devolve junto.
+pub async fn process(
+    path: &Path,
+    options: &LoudnessOptions,
+    bitrate: Option<u64>,
+) -> Result<(Loudness, PathBuf), DownloadError> {
+    let target = options.target_lufs;
+    let input = measure(path, target).await?;
+    let output = match options.analyze_only {
+        true => None,
+        false => Some(normalize(path, target, &input, bitrate).await?),
+    };
+    let loudness = Loudness {
+        standard: STANDARD,
+        target_lufs: target,
+        input,
+        output,
+    };
+    let sidecar = write_sidecar(path, &loudness)?;
+    Ok((loudness, sidecar))
+}
+
+/// Primeira passagem: só mede, sem gravar nada.
+pub async fn measure(path: &Path, target_lufs: f64) -> Result<Measurement, DownloadError> {
+    let mut cmd = ffmpeg()?;
+    cmd.arg("-i")
+        .arg(path)
+        .args(["-vn", "-af"])
+        .arg(filter(target_lufs, None))
+        .args(["-f", "null", "-"]);
+    run(cmd, "input").await
+}
+
+/// Segunda passagem: aplica a correção medida e substitui o arquivo. Devolve
+/// o volume resultante.
+pub async fn normalize(
+    path: &Path,
+    target_lufs: f64,
+    measured: &Measurement,
+    bitrate: Option<u64>,
+) -> Result<Measurement, DownloadError> {
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("mp3");
+    let temp = path.with_extension(format!("loudnorm.{ext}"));
+    let mut cmd = ffmpeg()?;
+    cmd.arg("-y")
+        .arg("-i")
+        .arg(path)
+        // Mantém as tags e a capa; só o áudio é recodificado
+        .args(["-map", "0", "-map_metadata", "0", "-c:v", "copy"])
+        .arg("-af")
+        .arg(filter(target_lufs, Some(measured)))
+        // O loudnorm trabalha a 192 kHz; voltar a uma taxa comum
+        .args(["-ar", "48000"])
+        .args(quality_args(ext, bitrate))
+        .arg(&temp);
+    let result = run(cmd, "output").await;
+    match &result {
+        Ok(_) => fs::rename(&temp, path)?,
+        Err(_) => {
+            let _ = fs::remove_file(&temp);
+        }
+    }
+    result
+}
+
+/// Qualidade da recodificação: o bitrate do original, se conhecido; senão,
+/// uma qualidade alta para o formato, em vez do padrão do codificador (128
+/// kbps no MP3), que pioraria o áudio só por normalizá-lo.
+fn quality_args(ext: &str, bitrate: Option<u64>) -> Vec<String> {
+    let args: &[&str] = match (ext, bitrate) {
+        ("flac" | "wav", _) => &[],
+        (_, Some(bps)) => return vec!["-b:a".to_string(), format!("{}k", bps.div_ceil(1000))],
+        ("mp3", None) => &["-q:a", "0"],
+        ("ogg" | "vorbis", None) => &["-q:a", "8"],
+        (_, None) => &["-b:a", "256k"],
+    };
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+fn ffmpeg() -> Result<Command, DownloadError> {
+    let ffmpeg = tools::detect(Tool::Ffmpeg).ok_or(DownloadError::MissingDependency("ffmpeg"))?;
+    let mut cmd = Command::new(&ffmpeg.program);
+    cmd.args(["-hide_banner", "-nostats"]);
+    Ok(cmd)
+}
+
+/// Roda o ffmpeg e lê as medições `<prefixo>_*` que o `loudnorm` imprime.
+async fn run(mut cmd: Command, prefix: &str) -> Result<Measurement, DownloadError> {
+    let output = cmd.output().await?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        // Sem `-loglevel error`, o motivo da falha é a última linha
+        let reason = stderr.lines().rev().find(|line| !line.trim().is_empty());
+        return Err(DownloadError::Transcode(
+            reason.unwrap_or_default().trim().to_string(),
+        ));
+    }
+    parse_measurement(&stderr, prefix)
+        .ok_or_else(|| DownloadError::Transcode(t!("loudness.no_measurement")))
+}
+
+/// O filtro `loudnorm`; com a medição da primeira passagem, em modo linear.
+fn filter(target_lufs: f64, measured: Option<&Measurement>) -> String {
+    let mut filter = format!("loudnorm=I={target_lufs}:TP={TRUE_PEAK}:LRA={LOUDNESS_RANGE}");
+    if let Some(m) = measured {
+        filter.push_str(&format!(
+            ":measured_I={}:measured_TP={}:measured_LRA={}:measured_thresh={}:offset={}:linear=true",
+            m.integrated, m.true_peak, m.range, m.threshold, m.offset
+        ));
+    }
+    filter.push_str(":print_format=json");
+    filter
+}
+
+/// O `loudnorm` termina a saída de erro com um bloco JSON de valores em
+/// texto. Um áudio mudo mede `-inf` e não tem o que normalizar.
+fn parse_measurement(stderr: &str, prefix: &str) -> Option<Measurement> {
+    let start = stderr.rfind('{')?;
+    let end = stderr[start..].find('}')? + start;
+    let values: HashMap<String, String> = serde_json::from_str(&stderr[start..=end]).ok()?;
+    let value = |key: &str| {
+        values
+            .get(key)?
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+    };
+    Some(Measurement {
+        integrated: value(&format!("{prefix}_i"))?,
+        true_peak: value(&format!("{prefix}_tp"))?,
+        range: value(&format!("{prefix}_lra"))?,
+        threshold: value(&format!("{prefix}_thresh"))?,
+        offset: value("target_offset")?,
+    })
+}
+
+/// Grava as medições em `<nome>.info.json`, o mesmo arquivo do
+/// `--write-info-json` do yt-dlp, preservando o que já houver nele.
+pub fn write_sidecar(path: &Path, loudness: &Loudness) -> io::Result<PathBuf> {
+    let sidecar = path.with_extension("info.json");
+    let mut info = fs::read_to_string(&sidecar)
+        .ok()
+        .and_then(|text| serde_json::from_str::<Map<String, Value>>(&text).ok())
+        .unwrap_or_default();
+    let value = serde_json::to_value(loudness).map_err(io::Error::other)?;
+    info.insert("loudness".to_string(), value);
+    let text = serde_json::to_string_pretty(&info).map_err(io::Error::other)?;
+    fs::write(&sidecar, text)?;
+    Ok(sidecar)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIRST_PASS: &str = r#"[Parsed_loudnorm_0 @ 0x55d5c8a0]
+{
+	"input_i" : "-23.41",
+	"input_tp" : "-4.20",
+	"input_lra" : "6.80",
+	"input_thresh" : "-33.71",
+	"output_i" : "-16.02",
+	"output_tp" : "-1.50",
+	"output_lra" : "5.90",
+	"output_thresh" : "-26.30",
+	"normalization_type" : "dynamic",
+	"target_offset" : "0.02"
+}
+"#;
+
+    #[test]
+    fn test_parse_measurement() {
+        let input = parse_measurement(FIRST_PASS, "input").unwrap();
+        assert_eq!(input.integrated, -23.41);
+        assert_eq!(input.true_peak, -4.2);
+        assert_eq!(input.range, 6.8);
+        assert_eq!(input.offset, 0.02);
+        let output = parse_measurement(FIRST_PASS, "output").unwrap();
+        assert_eq!(output.integrated, -16.02);
+
+        let silent = FIRST_PASS.replace("\"-23.41\"", "\"-inf\"");
+        assert_eq!(parse_measurement(&silent, "input"), None);
+        assert_eq!(parse_measurement("Invalid data found", "input"), None);
+    }
+
+    #[test]
+    fn test_quality_args() {
+        assert_eq!(quality_args("mp3", Some(320_000)), ["-b:a", "320k"]);
+        assert_eq!(quality_args("m4a", Some(129_476)), ["-b:a", "130k"]);
+        assert_eq!(quality_args("mp3", None), ["-q:a", "0"]);
+        assert_eq!(quality_args("opus", None), ["-b:a", "256k"]);
+        assert!(quality_args("flac", Some(900_000)).is_empty());
+    }
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(parse_target("-16"), Ok(-16.0));
+        assert_eq!(parse_target("-14.5"), Ok(-14.5));
+        assert!(parse_target("16").is_err());
+        assert!(parse_target("alto").is_err());
+    }
+
+    #[test]
+    fn test_second_pass_filter() {
+        let measured = parse_measurement(FIRST_PASS, "input").unwrap();
+        assert_eq!(
+            filter(-16.0, None),
+            "loudnorm=I=-16:TP=-1.5:LRA=11:print_format=json"
+        );
+        let second = filter(-14.0, Some(&measured));
+        assert!(second.starts_with("loudnorm=I=-14:"));
+        assert!(second.contains(":measured_I=-23.41:measured_TP=-4.2:"));
+        assert!(second.contains(":offset=0.02:linear=true"));
+    }
+}
@@ -1,23 +1,202 @@
-use clap::{Arg, Command};
+mod controls;
+mod repl;
+mod shell;
+mod tui;
+
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{Arg, ArgGroup, ArgMatches, Command};
 use colored::*;
+use controls::Outcome;
 use rand::Rng;
-use regex::Regex;
+use repl::ReplHelper;
 use rustyline::error::ReadlineError;
-use rustyline::DefaultEditor;
+use rustyline::history::DefaultHistory;
+use rustyline::{CompletionType, Editor, ExternalPrinter};
+use shell::{ParseError, ReplCommand, ThrottleChange};
+use std::collections::HashSet;
+use std::fs;
 use std::io::{self, Write};
-use std::process::Command as StdCommand;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tokio::time::sleep;
+use youtube_video_downloader::access::{self, AccessOptions};
+use youtube_video_downloader::animation::{self, AnimationOptions, Format};
+use youtube_video_downloader::archive;
+use youtube_video_downloader::bandwidth::{self, Limit};
+use youtube_video_downloader::clip::Section;
+use youtube_video_downloader::download::TITLE_OUTPUT;
+use youtube_video_downloader::filter::{self, Filters, Skipped, UploadDate};
+use youtube_video_downloader::journal;
+use youtube_video_downloader::live::{self, LiveOptions, LiveStatus};
+use youtube_video_downloader::loudness::{self, LoudnessOptions};
+use youtube_video_downloader::pairing;
+use youtube_video_downloader::preset::Preset;
+use youtube_video_downloader::provision::{self, UpdateAction};
+use youtube_video_downloader::search::{self, SearchResult};
+use youtube_video_downloader::server::{self, ServerOptions};
+use youtube_video_downloader::stats::{self, Period};
+use youtube_video_downloader::subscriptions::{self, Subscription};
+use youtube_video_downloader::{
+    doctor, download, i18n, is_youtube_url, paths, t, Config, DownloadError, DownloadOptions,
+    JobEvent,
+};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let matches = Command::new("YouTube Video Downloader")
+    // O idioma precisa ser escolhido antes de montar a ajuda do clap
+    let (lang, args) = split_lang_arg(std::env::args());
+    i18n::init(lang.as_deref(), Config::load().lang.as_deref());
+    let matches = build_cli().get_matches_from(args);
+
+    match matches.subcommand() {
+        Some(("doctor", sub)) => {
+            let dir = sub.get_one::<String>("dir").unwrap();
+            let report = doctor::diagnose(Path::new(dir), &Config::load().doctor);
+            report.print();
+            if report.has_errors() {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(("update-backend", sub)) => {
+            if !run_update_backend(&update_action(sub)).await {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(("stats", sub)) => {
+            stats::summarize(&stats::load(), stats_period(sub)).print();
+            return Ok(());
+        }
+        Some(("serve", sub)) => {
+            if !run_serve(sub).await {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(("subscribe", sub)) => {
+            if !run_subscribe(sub).await {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(("unsubscribe", sub)) => {
+            if !unsubscribe(sub.get_one::<String>("url").unwrap()) {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(("subscriptions", _)) => {
+            print_subscriptions(&Config::load());
+            return Ok(());
+        }
+        Some(("watch", sub)) => {
+            run_watch(sub).await;
+            return Ok(());
+        }
+        Some(("info", sub)) => {
+            if !apply_access(sub) || !run_info(sub.get_one::<String>("url").unwrap()).await {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(("gif", sub)) => {
+            if !apply_access(sub) || !run_gif(sub).await {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    if !apply_access(&matches) {
+        std::process::exit(1);
+    }
+
+    // `--limit-rate` vale para a execução inteira, como um `throttle`
+    if let Some(limit) = matches.get_one::<Limit>("limit-rate") {
+        bandwidth::set_override(Some(*limit));
+    }
+
+    if matches.get_flag("tui") {
+        // Instalar ou confirmar o yt-dlp antes de tomar conta do terminal
+        if let Err(e) = provision::ensure_yt_dlp(&Config::load().provision).await {
+            report_download_error(&e);
+            std::process::exit(1);
+        }
+        tui::run().await?;
+        return Ok(());
+    }
+
+    let interactive = matches.get_flag("interactive");
+
+    // Mostrar animação ASCII aleatória
+    show_random_ascii_art().await;
+
+    if interactive {
+        // Modo interativo
+        run_interactive_mode().await?;
+    } else {
+        // Modo CLI tradicional
+        if let Some(query) = matches.get_one::<String>("search") {
+            let limit = *matches.get_one::<usize>("results").unwrap();
+            run_cli_search(&matches, query, limit).await;
+            return Ok(());
+        }
+        if matches.get_flag("resume") {
+            if !run_cli_resume().await {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        let video_url = matches.get_one::<String>("url");
+        let output_name = matches.get_one::<String>("output").unwrap();
+        let audio_only = matches.get_flag("audio-only");
+
+        if let Some(url) = video_url {
+            announce_unfinished("resume.hint_cli");
+            if search::is_collection_url(url) {
+                if !run_cli_collection(&matches, url, audio_only).await {
+                    std::process::exit(1);
+                }
+                return Ok(());
+            }
+            let live = cli_live(&matches);
+            if live.is_some() {
+                println!("{}", t!("cli.recording_live").green());
+                println!("{}", url.cyan());
+            } else if audio_only {
+                println!("{}", t!("cli.downloading_audio").green());
+                println!("{}", url.cyan());
+            } else {
+                println!("{}", t!("cli.downloading_video").green());
+                println!("{}", url.cyan());
+            }
+
+            let options = DownloadOptions {
+                live,
+                ..cli_options(&matches, url, output_name, audio_only)
+            };
+            match download(&options).await {
+                Ok(_) => println!("{}", t!("cli.download_done").green().bold()),
+                Err(e) => report_download_error(&e),
+            }
+        } else {
+            println!("{}", t!("cli.url_required").red());
+        }
+    }
+
+    Ok(())
+}
+
+fn build_cli() -> Command {
+    Command::new("YouTube Video Downloader")
         .version("0.1.0")
         .author("Pedro Correa Siqueira")
-        .about("Ferramenta CLI para baixar vídeos do YouTube")
+        .about(t!("cli.about"))
         .arg(
             Arg::new("url")
-                .help("URL do vídeo do YouTube (opcional no modo interativo)")
+                .help(t!("cli.arg.url"))
                 .required(false)
                 .index(1),
         )
@@ -25,8 +204,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             Arg::new("output")
                 .short('o')
                 .long("output")
-                .value_name("FILE")
-                .help("Nome do arquivo de saída")
+                .value_name(t!("cli.value.file"))
+                .help(t!("cli.arg.output"))
                 .default_value("download"),
         )
         .arg(
@@ -34,178 +213,1140 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .short('a')
                 .long("audio-only")
                 .action(clap::ArgAction::SetTrue)
-                .help("Baixar apenas o áudio em formato MP3"),
+                .help(t!("cli.arg.audio_only")),
+        )
+        .arg(
+            Arg::new("audio-format")
+                .long("audio-format")
+                .value_name(t!("cli.value.format"))
+                .help(t!("cli.arg.audio_format"))
+                .default_value("mp3"),
+        )
+        .arg(
+            Arg::new("attempts")
+                .long("attempts")
+                .value_name("N")
+                .value_parser(clap::value_parser!(u32).range(1..))
+                .help(t!("cli.arg.attempts")),
         )
         .arg(
             Arg::new("interactive")
                 .short('i')
                 .long("interactive")
                 .action(clap::ArgAction::SetTrue)
-                .help("Modo interativo"),
+                .help(t!("cli.arg.interactive")),
+        )
+        .arg(
+            Arg::new("tui")
+                .long("tui")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with_all(["interactive", "url"])
+                .help(t!("cli.arg.tui")),
+        )
+        .args(access_args())
+        .arg(
+            Arg::new("limit-rate")
+                .long("limit-rate")
+                .value_name(t!("cli.value.speed"))
+                .value_parser(Limit::parse)
+                .help(t!("cli.arg.limit_rate")),
+        )
+        .arg(
+            Arg::new("resume")
+                .long("resume")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with_all(["url", "interactive", "tui"])
+                .help(t!("cli.arg.resume")),
+        )
+        .arg(
+            Arg::new("search")
+                .long("search")
+                .value_name(t!("cli.value.terms"))
+                .conflicts_with_all(["url", "interactive", "tui"])
+                .help(t!("cli.arg.search")),
+        )
+        .arg(results_arg().requires("search"))
+        .args(filter_args())
+        .arg(
+            Arg::new("live")
+                .long("live")
+                .action(clap::ArgAction::SetTrue)
+                .help(t!("cli.arg.live")),
+        )
+        .arg(
+            Arg::new("live-from-start")
+                .long("live-from-start")
+                .action(clap::ArgAction::SetTrue)
+                .help(t!("cli.arg.live_from_start")),
+        )
+        .arg(
+            Arg::new("duration")
+                .long("duration")
+                .value_name(t!("cli.value.duration"))
+                .value_parser(filter::parse_duration)
+                .help(t!("cli.arg.duration")),
+        )
+        .arg(
+            Arg::new("segment")
+                .long("segment")
+                .value_name(t!("cli.value.minutes"))
+                .value_parser(clap::value_parser!(u64))
+                .help(t!("cli.arg.segment")),
+        )
+        .args(loudness_args())
+        .arg(
+            Arg::new("preset")
+                .long("preset")
+                .value_name("PRESET")
+                .value_parser(
+                    PossibleValuesParser::new(Preset::ALL.map(Preset::name))
+                        .map(|name| Preset::parse(&name).unwrap()),
+                )
+                .conflicts_with_all(LIVE_ARGS)
+                .help(t!("cli.arg.preset")),
+        )
+        .arg(
+            Arg::new("lang")
+                .long("lang")
+                .value_name(t!("cli.value.language"))
+                .help(t!("cli.arg.lang")),
+        )
+        .args_conflicts_with_subcommands(true)
+        .subcommand(doctor_command())
+        .subcommand(update_backend_command())
+        .subcommand(stats_command())
+        .subcommand(serve_command())
+        .subcommand(subscribe_command())
+        .subcommand(
+            Command::new("unsubscribe")
+                .about(t!("cli.unsubscribe.about"))
+                .arg(
+                    Arg::new("url")
+                        .required(true)
+                        .value_name("URL")
+                        .help(t!("cli.subscribe.url")),
+                ),
+        )
+        .subcommand(Command::new("subscriptions").about(t!("cli.subscriptions.about")))
+        .subcommand(watch_command())
+        .subcommand(
+            Command::new("info")
+                .about(t!("cli.info.about"))
+                .arg(
+                    Arg::new("url")
+                        .required(true)
+                        .value_name("URL")
+                        .help(t!("cli.arg.url")),
+                )
+                .args(access_args()),
         )
-        .get_matches();
+        .subcommand(gif_command())
+}
 
-    let interactive = matches.get_flag("interactive");
+/// `--results`/`-n`: quantos vídeos a busca traz.
+fn results_arg() -> Arg {
+    Arg::new("results")
+        .long("results")
+        .value_name("N")
+        .value_parser(clap::value_parser!(usize))
+        .default_value("10")
+        .help(t!("cli.arg.results"))
+}
 
-    // Mostrar animação ASCII aleatória
-    show_random_ascii_art().await;
+fn doctor_command() -> Command {
+    Command::new("doctor").about(t!("cli.doctor.about")).arg(
+        Arg::new("dir")
+            .long("dir")
+            .value_name("DIR")
+            .help(t!("cli.doctor.dir"))
+            .default_value("."),
+    )
+}
 
-    if interactive {
-        // Modo interativo
-        run_interactive_mode().await?;
+fn stats_command() -> Command {
+    Command::new("stats")
+        .about(t!("cli.stats.about"))
+        .arg(
+            Arg::new("today")
+                .long("today")
+                .action(clap::ArgAction::SetTrue)
+                .help(t!("cli.stats.today")),
+        )
+        .arg(
+            Arg::new("week")
+                .long("week")
+                .action(clap::ArgAction::SetTrue)
+                .help(t!("cli.stats.week")),
+        )
+        .arg(
+            Arg::new("all")
+                .long("all")
+                .action(clap::ArgAction::SetTrue)
+                .help(t!("cli.stats.all")),
+        )
+        .group(
+            ArgGroup::new("periodo")
+                .args(["today", "week", "all"])
+                .multiple(false),
+        )
+}
+
+fn serve_command() -> Command {
+    Command::new("serve")
+        .about(t!("cli.serve.about"))
+        .arg(
+            Arg::new("bind")
+                .long("bind")
+                .value_name(t!("cli.value.address"))
+                .default_value("127.0.0.1:8080")
+                .help(t!("cli.serve.bind")),
+        )
+        .arg(
+            Arg::new("token")
+                .long("token")
+                .value_name("TOKEN")
+                .help(t!("cli.serve.token")),
+        )
+        .arg(
+            Arg::new("dir")
+                .long("dir")
+                .value_name("DIR")
+                .default_value(".")
+                .help(t!("cli.serve.dir")),
+        )
+}
+
+fn subscribe_command() -> Command {
+    Command::new("subscribe")
+        .about(t!("cli.subscribe.about"))
+        .arg(
+            Arg::new("url")
+                .required(true)
+                .value_name("URL")
+                .help(t!("cli.subscribe.url")),
+        )
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .value_name(t!("cli.value.name"))
+                .help(t!("cli.arg.profile")),
+        )
+        .arg(
+            Arg::new("audio-only")
+                .short('a')
+                .long("audio-only")
+                .action(clap::ArgAction::SetTrue)
+                .help(t!("cli.arg.audio_only")),
+        )
+        .args(filter_args())
+        .arg(
+            Arg::new("backfill")
+                .long("backfill")
+                .action(clap::ArgAction::SetTrue)
+                .help(t!("cli.subscribe.backfill")),
+        )
+}
+
+/// Filtros de conteúdo para playlists, canais, buscas e assinaturas.
+fn filter_args() -> Vec<Arg> {
+    vec![
+        Arg::new("min-duration")
+            .long("min-duration")
+            .value_name(t!("cli.value.duration"))
+            .value_parser(filter::parse_duration)
+            .help(t!("cli.filter.min_duration")),
+        Arg::new("max-duration")
+            .long("max-duration")
+            .value_name(t!("cli.value.duration"))
+            .value_parser(filter::parse_duration)
+            .help(t!("cli.filter.max_duration")),
+        Arg::new("date-after")
+            .long("date-after")
+            .value_name(t!("cli.value.date"))
+            .value_parser(UploadDate::parse)
+            .help(t!("cli.filter.date_after")),
+        Arg::new("date-before")
+            .long("date-before")
+            .value_name(t!("cli.value.date"))
+            .value_parser(UploadDate::parse)
+            .help(t!("cli.filter.date_before")),
+        Arg::new("match-title")
+            .long("match-title")
+            .value_name("REGEX")
+            .help(t!("cli.filter.match_title")),
+        Arg::new("reject-title")
+            .long("reject-title")
+            .value_name("REGEX")
+            .help(t!("cli.filter.reject_title")),
+        Arg::new("skip-shorts")
+            .long("skip-shorts")
+            .action(clap::ArgAction::SetTrue)
+            .help(t!("cli.filter.skip_shorts")),
+        Arg::new("skip-live")
+            .long("skip-live")
+            .action(clap::ArgAction::SetTrue)
+            .help(t!("cli.filter.skip_live")),
+    ]
+}
+
+/// Opções que ligam a gravação de live. A conversão e a normalização tratam
+/// um único arquivo, e uma gravação é dividida em partes.
+const LIVE_ARGS: [&str; 4] = ["live", "live-from-start", "duration", "segment"];
+
+/// Normalização de volume; só vale para áudio (`-a`).
+fn loudness_args() -> Vec<Arg> {
+    vec![
+        Arg::new("normalize")
+            .long("normalize")
+            .value_name(t!("cli.value.standard"))
+            .value_parser([loudness::STANDARD])
+            .requires("audio-only")
+            .conflicts_with_all(LIVE_ARGS)
+            .help(t!("cli.arg.normalize")),
+        Arg::new("target-lufs")
+            .long("target-lufs")
+            .value_name("LUFS")
+            .allow_negative_numbers(true)
+            .value_parser(loudness::parse_target)
+            .requires("normalize")
+            .help(t!("cli.arg.target_lufs")),
+        Arg::new("analyze-loudness")
+            .long("analyze-loudness")
+            .action(clap::ArgAction::SetTrue)
+            .requires("audio-only")
+            .conflicts_with("normalize")
+            .conflicts_with_all(LIVE_ARGS)
+            .help(t!("cli.arg.analyze_loudness")),
+    ]
+}
+
+/// Normalização pedida na linha de comando: `--normalize` ou só a análise.
+fn cli_loudness(matches: &ArgMatches) -> Option<LoudnessOptions> {
+    let analyze_only = matches.get_flag("analyze-loudness");
+    (analyze_only || matches.contains_id("normalize")).then(|| LoudnessOptions {
+        target_lufs: matches
+            .get_one::<f64>("target-lufs")
+            .copied()
+            .unwrap_or(loudness::DEFAULT_TARGET_LUFS),
+        analyze_only,
+    })
+}
+
+/// Cookies, proxy, user-agent e perfil de acesso, aceitos pelo download e
+/// pelos subcomandos que consultam o YouTube.
+fn access_args() -> Vec<Arg> {
+    vec![
+        Arg::new("cookies")
+            .long("cookies")
+            .value_name(t!("cli.value.file"))
+            .value_parser(clap::value_parser!(PathBuf))
+            .help(t!("cli.arg.cookies")),
+        Arg::new("proxy")
+            .long("proxy")
+            .value_name("URL")
+            .value_parser(access::parse_proxy)
+            .help(t!("cli.arg.proxy")),
+        Arg::new("user-agent")
+            .long("user-agent")
+            .value_name("UA")
+            .help(t!("cli.arg.user_agent")),
+        Arg::new("profile")
+            .long("profile")
+            .value_name(t!("cli.value.name"))
+            .help(t!("cli.arg.profile")),
+    ]
+}
+
+/// Aplica as opções de [`access_args`] a todas as chamadas ao yt-dlp da
+/// execução; `false` se o arquivo de cookies ou o perfil for inválido.
+fn apply_access(matches: &ArgMatches) -> bool {
+    let session_access = AccessOptions {
+        cookies: matches.get_one::<PathBuf>("cookies").cloned(),
+        proxy: matches.get_one::<String>("proxy").cloned(),
+        user_agent: matches.get_one::<String>("user-agent").cloned(),
+    };
+    access::set_session(
+        session_access,
+        matches.get_one::<String>("profile").cloned(),
+    );
+    // Um arquivo de cookies ou perfil inválido deve falhar antes de qualquer download
+    if let Err(e) = access::resolve(&Config::load(), &AccessOptions::default()) {
+        report_download_error(&e);
+        return false;
+    }
+    true
+}
+
+fn cli_filters(matches: &ArgMatches) -> Filters {
+    Filters {
+        min_duration_secs: matches.get_one::<u64>("min-duration").copied(),
+        max_duration_secs: matches.get_one::<u64>("max-duration").copied(),
+        date_after: matches.get_one::<UploadDate>("date-after").copied(),
+        date_before: matches.get_one::<UploadDate>("date-before").copied(),
+        match_title: matches.get_one::<String>("match-title").cloned(),
+        reject_title: matches.get_one::<String>("reject-title").cloned(),
+        skip_shorts: matches.get_flag("skip-shorts"),
+        skip_live: matches.get_flag("skip-live"),
+    }
+}
+
+fn report_skipped(skipped: &[Skipped]) {
+    for item in skipped {
+        println!(
+            "{}",
+            t!(
+                "filter.skipped",
+                title = item.video.title,
+                reason = item.reason
+            )
+            .yellow()
+        );
+    }
+}
+
+fn watch_command() -> Command {
+    Command::new("watch")
+        .about(t!("cli.watch.about"))
+        .arg(
+            Arg::new("interval")
+                .long("interval")
+                .value_name(t!("cli.value.minutes"))
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help(t!("cli.watch.interval")),
+        )
+        .arg(
+            Arg::new("once")
+                .long("once")
+                .action(clap::ArgAction::SetTrue)
+                .help(t!("cli.watch.once")),
+        )
+        .arg(
+            Arg::new("dir")
+                .long("dir")
+                .value_name("DIR")
+                .default_value(".")
+                .help(t!("cli.watch.dir")),
+        )
+}
+
+fn gif_command() -> Command {
+    Command::new("gif")
+        .about(t!("cli.gif.about"))
+        .arg(
+            Arg::new("url")
+                .required(true)
+                .value_name("URL")
+                .help(t!("cli.arg.url")),
+        )
+        .arg(
+            Arg::new("start")
+                .long("start")
+                .value_name(t!("cli.value.start"))
+                .value_parser(filter::parse_duration)
+                .default_value("0")
+                .help(t!("cli.gif.start")),
+        )
+        .arg(
+            Arg::new("end")
+                .long("end")
+                .value_name(t!("cli.value.end"))
+                .value_parser(filter::parse_duration)
+                .required(true)
+                .help(t!("cli.gif.end")),
+        )
+        .arg(
+            Arg::new("width")
+                .long("width")
+                .value_name("PIXELS")
+                .value_parser(clap::value_parser!(u32).range(16..=4096))
+                .default_value("480")
+                .help(t!("cli.gif.width")),
+        )
+        .arg(
+            Arg::new("fps")
+                .long("fps")
+                .value_name("N")
+                .value_parser(clap::value_parser!(u32).range(1..=60))
+                .default_value("12")
+                .help(t!("cli.gif.fps")),
+        )
+        .arg(
+            Arg::new("webp")
+                .long("webp")
+                .action(clap::ArgAction::SetTrue)
+                .help(t!("cli.gif.webp")),
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name(t!("cli.value.file"))
+                .default_value("animacao")
+                .help(t!("cli.gif.output")),
+        )
+        .args(access_args())
+}
+
+fn stats_period(matches: &ArgMatches) -> Period {
+    if matches.get_flag("today") {
+        Period::Today
+    } else if matches.get_flag("week") {
+        Period::Week
     } else {
-        // Modo CLI tradicional
-        let video_url = matches.get_one::<String>("url");
-        let output_name = matches.get_one::<String>("output").unwrap();
-        let audio_only = matches.get_flag("audio-only");
+        Period::All
+    }
+}
 
-        if let Some(url) = video_url {
-            if audio_only {
-                println!("{}", "Baixando áudio de:".green());
-                println!("{}", url.cyan());
-            } else {
-                println!("{}", "Baixando vídeo de:".green());
-                println!("{}", url.cyan());
-            }
+fn update_backend_command() -> Command {
+    Command::new("update-backend")
+        .about(t!("cli.update.about"))
+        .arg(
+            Arg::new("pin")
+                .long("pin")
+                .value_name(t!("cli.value.version"))
+                .help(t!("cli.update.pin")),
+        )
+        .arg(
+            Arg::new("unpin")
+                .long("unpin")
+                .action(clap::ArgAction::SetTrue)
+                .help(t!("cli.update.unpin")),
+        )
+        .arg(
+            Arg::new("rollback")
+                .long("rollback")
+                .action(clap::ArgAction::SetTrue)
+                .help(t!("cli.update.rollback")),
+        )
+        .group(
+            ArgGroup::new("acao")
+                .args(["pin", "unpin", "rollback"])
+                .multiple(false),
+        )
+}
 
-            match download_video(url, output_name, audio_only).await {
-                Ok(_) => println!("{}", "✅ Download concluído com sucesso!".green().bold()),
-                Err(e) => println!("{}", format!("❌ Erro no download: {e}").red().bold()),
-            }
+/// Separa o `--lang` dos demais argumentos. Ele é lido antes do clap e pode
+/// aparecer em qualquer posição, inclusive depois de um subcomando.
+fn split_lang_arg(args: impl IntoIterator<Item = String>) -> (Option<String>, Vec<String>) {
+    let mut lang = None;
+    let mut rest = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            rest.push(arg);
+            rest.extend(args.by_ref());
+        } else if arg == "--lang" {
+            lang = args.next();
+        } else if let Some(value) = arg.strip_prefix("--lang=") {
+            lang = Some(value.to_string());
         } else {
+            rest.push(arg);
+        }
+    }
+    (lang, rest)
+}
+
+fn update_action(matches: &ArgMatches) -> UpdateAction {
+    if let Some(version) = matches.get_one::<String>("pin") {
+        UpdateAction::Pin(version.clone())
+    } else if matches.get_flag("unpin") {
+        UpdateAction::Unpin
+    } else if matches.get_flag("rollback") {
+        UpdateAction::Rollback
+    } else {
+        UpdateAction::Latest
+    }
+}
+
+/// Executa o `update-backend`, informando o resultado. Retorna `false` em caso de erro.
+async fn run_update_backend(action: &UpdateAction) -> bool {
+    match provision::update_backend(&Config::load().provision, action).await {
+        Ok(outcome) if outcome.from == outcome.to => {
+            println!("{}", t!("cli.update_current", version = outcome.to).green());
+            true
+        }
+        Ok(outcome) => {
             println!(
                 "{}",
-                "❌ URL é obrigatória no modo não-interativo. Use -i para modo interativo.".red()
+                t!("cli.update_done", from = outcome.from, to = outcome.to)
+                    .green()
+                    .bold()
             );
+            true
+        }
+        Err(e) => {
+            println!("{}", format!("❌ {e}").red().bold());
+            false
         }
     }
-
-    Ok(())
 }
 
-async fn download_video(
-    url: &str,
-    output_name: &str,
-    audio_only: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
-    // Validar URL do YouTube
-    if !is_youtube_url(url) {
-        return Err("URL inválida. Por favor, forneça uma URL válida do YouTube.".into());
+/// `serve`: atende a API HTTP até o Ctrl+C.
+async fn run_serve(matches: &ArgMatches) -> bool {
+    let config = Config::load();
+    // Uma instalação que pede confirmação não pode esperar por um cliente HTTP
+    if let Err(e) = provision::ensure_yt_dlp(&config.provision).await {
+        report_download_error(&e);
+        return false;
     }
-
-    println!("🔍 Verificando se yt-dlp está instalado...");
-    println!("🔍 Verificando se ffmpeg está instalado...");
-
-    // Verificar se ffmpeg está disponível
-    let check_ffmpeg = StdCommand::new("ffmpeg").arg("-version").output();
-    if check_ffmpeg.is_err() {
-        println!("{}", "❌ ffmpeg não encontrado!".red().bold());
-        return Err(
-            "O ffmpeg é necessário para juntar áudio e vídeo. Instale o ffmpeg e tente novamente."
-                .into(),
-        );
+    let bind = matches.get_one::<String>("bind").unwrap();
+    let listener = match std::net::TcpListener::bind(bind) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("{}", t!("server.bind_failed", addr = bind, error = e).red());
+            return false;
+        }
+    };
+    let local = listener.local_addr().ok();
+    let addr = local.map_or_else(|| bind.clone(), |addr| addr.to_string());
+    let token = matches
+        .get_one::<String>("token")
+        .cloned()
+        .or(config.server.token)
+        .filter(|token| !token.is_empty());
+    if token.is_none() && !local.is_some_and(|addr| addr.ip().is_loopback()) {
+        println!("{}", t!("server.open_warning", addr = addr).yellow());
     }
+    println!("{}", t!("server.listening", addr = addr).green());
+    let pairing_code = pairing::new_code();
+    println!("{}", t!("server.pairing_code", code = pairing_code).cyan());
+
+    let options = ServerOptions {
+        dir: PathBuf::from(matches.get_one::<String>("dir").unwrap()),
+        token,
+        allowed_origins: config.server.allowed_origins,
+        pairing_code: Some(pairing_code),
+    };
+    let shutdown = async {
+        let _ = tokio::signal::ctrl_c().await;
+        println!("{}", t!("server.stopping").yellow());
+    };
+    match server::serve(listener, options, shutdown).await {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("{}", t!("server.bind_failed", addr = addr, error = e).red());
+            false
+        }
+    }
+}
 
-    // Verificar se yt-dlp está disponível
-    let check_ytdlp = StdCommand::new("yt-dlp").arg("--version").output();
+/// `subscribe`: valida e salva a assinatura. Os vídeos já publicados entram
+/// no arquivo de baixados, a menos que `--backfill` peça para baixá-los.
+async fn run_subscribe(matches: &ArgMatches) -> bool {
+    // Falhar antes de marcar os envios como vistos, se não houver como salvar
+    let mut config = match Config::load_for_update() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", t!("subscriptions.save_failed", error = e).red());
+            return false;
+        }
+    };
+    let url = matches.get_one::<String>("url").unwrap();
+    if !search::is_collection_url(url) {
+        eprintln!("{}", t!("subscriptions.invalid_url", url = url).red());
+        return false;
+    }
+    if config.subscriptions.iter().any(|s| &s.url == url) {
+        println!("{}", t!("subscriptions.exists", url = url).yellow());
+        return true;
+    }
+    let subscription = Subscription {
+        profile: matches.get_one::<String>("profile").cloned(),
+        audio_only: matches.get_flag("audio-only"),
+        filters: cli_filters(matches),
+        ..Subscription::new(url)
+    };
+    if let Err(e) = subscription.validate(&config) {
+        eprintln!("{}", e.to_string().red());
+        return false;
+    }
+    if !matches.get_flag("backfill") {
+        let uploads =
+            match subscriptions::list_uploads(&subscription, config.watch.playlist_items).await {
+                Ok(uploads) => uploads,
+                Err(e) => {
+                    eprintln!("{}", e.to_string().red());
+                    return false;
+                }
+            };
+        if let Err(e) = archive::add(uploads.iter().map(|video| video.id.as_str())) {
+            eprintln!("{}", t!("subscriptions.save_failed", error = e).red());
+            return false;
+        }
+        println!("{}", t!("subscriptions.seeded", count = uploads.len()));
+    }
+    match subscriptions::add(&mut config, subscription) {
+        Ok(_) => {
+            println!("{}", t!("subscriptions.added", url = url).green());
+            true
+        }
+        Err(e) => {
+            eprintln!("{}", t!("subscriptions.save_failed", error = e).red());
+            false
+        }
+    }
+}
 
-    if check_ytdlp.is_err() {
-        println!("{}", "❌ yt-dlp não encontrado!".red().bold());
-        println!("{}", "📦 Instalando yt-dlp...".yellow());
+fn unsubscribe(url: &str) -> bool {
+    let mut config = match Config::load_for_update() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", t!("subscriptions.save_failed", error = e).red());
+            return false;
+        }
+    };
+    match subscriptions::remove(&mut config, url) {
+        Ok(true) => {
+            println!("{}", t!("subscriptions.removed", url = url).green());
+            true
+        }
+        Ok(false) => {
+            eprintln!("{}", t!("subscriptions.not_found", url = url).yellow());
+            false
+        }
+        Err(e) => {
+            eprintln!("{}", t!("subscriptions.save_failed", error = e).red());
+            false
+        }
+    }
+}
 
-        // Tentar instalar yt-dlp via pip
-        let install_result = StdCommand::new("pip3").args(["install", "yt-dlp"]).output();
+fn print_subscriptions(config: &Config) {
+    if config.subscriptions.is_empty() {
+        println!("{}", t!("subscriptions.none").yellow());
+        return;
+    }
+    println!(
+        "{}",
+        t!("subscriptions.title", count = config.subscriptions.len())
+            .cyan()
+            .bold()
+    );
+    for subscription in &config.subscriptions {
+        let mut details = Vec::new();
+        if let Some(profile) = &subscription.profile {
+            details.push(t!("subscriptions.detail_profile", name = profile));
+        }
+        if subscription.audio_only {
+            details.push(t!("subscriptions.detail_audio"));
+        }
+        details.extend(subscription.filters.describe());
+        println!("  • {}  {}", subscription.url, details.join(" · ").dimmed());
+    }
+}
 
-        match install_result {
-            Ok(output) => {
-                if !output.status.success() {
-                    return Err("Falha ao instalar yt-dlp. Por favor, instale manualmente: pip3 install yt-dlp".into());
-                }
-                println!("{}", "✅ yt-dlp instalado com sucesso!".green());
+/// `watch`: verifica as assinaturas a cada intervalo e baixa os envios
+/// novos, até o Ctrl+C.
+async fn run_watch(matches: &ArgMatches) {
+    let dir = PathBuf::from(matches.get_one::<String>("dir").unwrap());
+    let stop = tokio::signal::ctrl_c();
+    tokio::pin!(stop);
+    // Reprovados pelos filtros não são reavaliados nem relatados de novo
+    let mut skipped = HashSet::new();
+    loop {
+        // Recarregar a cada rodada, para valer assinaturas feitas nesse meio tempo
+        let config = Config::load();
+        if config.subscriptions.is_empty() {
+            println!("{}", t!("subscriptions.none").yellow());
+            return;
+        }
+        tokio::select! {
+            _ = watch_round(&config, &dir, &mut skipped) => {}
+            _ = &mut stop => {
+                println!("{}", t!("watch.stopping").yellow());
+                return;
             }
-            Err(_) => {
-                return Err("Não foi possível instalar yt-dlp automaticamente. Por favor, instale manualmente: pip3 install yt-dlp".into());
+        }
+        if matches.get_flag("once") {
+            return;
+        }
+        let minutes = matches
+            .get_one::<u64>("interval")
+            .copied()
+            .unwrap_or(config.watch.interval_mins)
+            .max(1);
+        println!("{}", t!("watch.next", minutes = minutes).cyan());
+        tokio::select! {
+            _ = sleep(Duration::from_secs(minutes * 60)) => {}
+            _ = &mut stop => {
+                println!("{}", t!("watch.stopping").yellow());
+                return;
             }
         }
     }
+}
 
-    println!("🔍 Obtendo informações do vídeo...");
+/// Uma verificação de todas as assinaturas, baixando os envios novos em
+/// sequência. Os vídeos reprovados pelos filtros entram em `skipped`.
+async fn watch_round(config: &Config, dir: &Path, skipped: &mut HashSet<String>) {
+    println!(
+        "{}",
+        t!("watch.checking", count = config.subscriptions.len()).cyan()
+    );
+    for subscription in &config.subscriptions {
+        let selection =
+            match subscriptions::check(subscription, config.watch.playlist_items, skipped).await {
+                Ok(selection) => selection,
+                Err(e) => {
+                    eprintln!(
+                        "{}",
+                        t!("watch.check_failed", url = subscription.url, error = e).yellow()
+                    );
+                    continue;
+                }
+            };
+        report_skipped(&selection.skipped);
+        skipped.extend(selection.skipped.into_iter().map(|item| item.video.id));
+        let videos = selection.accepted;
+        if !videos.is_empty() {
+            println!(
+                "{}",
+                t!("watch.found", count = videos.len(), url = subscription.url).green()
+            );
+        }
+        for video in videos {
+            println!("{}", t!("watch.new_upload", title = video.title).bold());
+            let result = match subscription.download_options(config, &video, dir) {
+                Ok(options) => download(&options).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(_) => println!("{}", t!("cli.download_done").green().bold()),
+                Err(e) => report_download_error(&e),
+            }
+        }
+    }
+}
 
-    // Detectar se é um YouTube Short
-    let is_short = url.contains("/shorts/");
-    if is_short {
-        println!("📱 YouTube Short detectado!");
+/// Opções de download da linha de comando, aplicando `--audio-format`,
+/// `--attempts`, a normalização de volume e o preset.
+fn cli_options(matches: &ArgMatches, url: &str, output: &str, audio_only: bool) -> DownloadOptions {
+    let mut options = DownloadOptions::new(url, output, audio_only);
+    options.attempts = matches.get_one::<u32>("attempts").copied();
+    options.loudness = cli_loudness(matches);
+    options.preset = matches.get_one::<Preset>("preset").copied();
+    if audio_only {
+        let format = matches.get_one::<String>("audio-format").unwrap();
+        options.audio_format = (format != "original").then(|| format.clone());
     }
+    options
+}
 
-    // Preparar comando yt-dlp
-    let mut cmd = StdCommand::new("yt-dlp");
+/// Playlist ou canal na linha de comando: lista os vídeos, aplica os filtros
+/// e baixa os que passarem, um por vez.
+async fn run_cli_collection(matches: &ArgMatches, url: &str, audio_only: bool) -> bool {
+    let matcher = match cli_filters(matches).compile() {
+        Ok(matcher) => matcher,
+        Err(e) => {
+            report_download_error(&e);
+            return false;
+        }
+    };
+    println!("{}", t!("filter.listing", url = url).cyan());
+    let access = AccessOptions::default();
+    let videos = match search::list_collection(url, None, &access).await {
+        Ok(videos) => videos,
+        Err(e) => {
+            report_download_error(&e);
+            return false;
+        }
+    };
+    let selection = filter::select(&matcher, videos, &access).await;
+    report_skipped(&selection.skipped);
+    println!(
+        "{}",
+        t!(
+            "filter.summary",
+            accepted = selection.accepted.len(),
+            skipped = selection.skipped.len()
+        )
+        .cyan()
+    );
+    download_each(matches, &selection.accepted, audio_only).await;
+    true
+}
 
-    if audio_only {
-        cmd.args([
-            "-f",
-            "bestaudio",
-            "-x",
-            "--audio-format",
-            "mp3",
-            "-o",
-            &format!("{output_name}.%(ext)s"),
-            url,
-        ]);
-        println!("🎵 Baixando áudio em formato MP3...");
-    } else {
-        // Forçar download do melhor vídeo + melhor áudio em MP4
-        cmd.args([
-            "-f",
-            "bestvideo[ext=mp4]+bestaudio[ext=m4a]/best[ext=mp4]/best",
-            "--merge-output-format",
-            "mp4",
-            "-o",
-            &format!("{output_name}.mp4"),
-            url,
-        ]);
-        println!("🎬 Baixando vídeo em formato MP4...");
-    }
-
-    // Executar download
-    let output = cmd.output()?;
-
-    if output.status.success() {
-        let content_type = if audio_only { "Áudio" } else { "Vídeo" };
+/// Baixa os vídeos em sequência, com nomes a partir do título.
+async fn download_each(matches: &ArgMatches, videos: &[SearchResult], audio_only: bool) {
+    for (index, video) in videos.iter().enumerate() {
         println!(
             "{}",
-            format!("💾 {content_type} baixado com sucesso!")
+            t!(
+                "search.downloading",
+                current = index + 1,
+                total = videos.len(),
+                title = video.title
+            )
+            .green()
+        );
+        let options = cli_options(matches, &video.url, TITLE_OUTPUT, audio_only);
+        match download(&options).await {
+            Ok(_) => println!("{}", t!("cli.download_done").green().bold()),
+            Err(e) => report_download_error(&e),
+        }
+    }
+}
+
+/// Gravação de live pedida na linha de comando; qualquer uma das opções de
+/// live a liga.
+fn cli_live(matches: &ArgMatches) -> Option<LiveOptions> {
+    let duration = matches.get_one::<u64>("duration").copied();
+    let segment = matches.get_one::<u64>("segment").copied();
+    let enabled = matches.get_flag("live")
+        || matches.get_flag("live-from-start")
+        || duration.is_some()
+        || segment.is_some();
+    enabled.then(|| LiveOptions {
+        from_start: matches.get_flag("live-from-start"),
+        max_duration: duration.map(Duration::from_secs),
+        segment: match segment {
+            Some(0) => None,
+            Some(minutes) => Some(Duration::from_secs(minutes * 60)),
+            None => Some(live::DEFAULT_SEGMENT),
+        },
+    })
+}
+
+/// `gif`: baixa só o trecho pedido e o converte numa animação GIF ou WebP.
+async fn run_gif(matches: &ArgMatches) -> bool {
+    let url = matches.get_one::<String>("url").unwrap();
+    let start = *matches.get_one::<u64>("start").unwrap();
+    let end = *matches.get_one::<u64>("end").unwrap();
+    let section = match Section::new(start, end) {
+        Ok(section) => section,
+        Err(message) => {
+            println!("{}", message.red());
+            return false;
+        }
+    };
+    let options = AnimationOptions {
+        width: *matches.get_one::<u32>("width").unwrap(),
+        fps: *matches.get_one::<u32>("fps").unwrap(),
+        format: if matches.get_flag("webp") {
+            Format::Webp
+        } else {
+            Format::Gif
+        },
+    };
+    println!(
+        "{}",
+        t!(
+            "animation.exporting",
+            start = search::format_clock(start as f64),
+            end = search::format_clock(end as f64)
+        )
+        .green()
+    );
+    println!("{}", url.cyan());
+    let output = matches.get_one::<String>("output").unwrap();
+    let result = animation::export(url, section, output, &options, |event| {
+        if let JobEvent::Status(message) = event {
+            println!("{message}");
+        }
+    })
+    .await;
+    match result {
+        Ok(path) => {
+            let size = fs::metadata(&path).map_or(0, |m| m.len());
+            println!(
+                "{}",
+                t!(
+                    "animation.done",
+                    path = path.display(),
+                    size = format!("{:.1}", size as f64 / 1_000_000.0)
+                )
                 .green()
                 .bold()
-        );
+            );
+            true
+        }
+        Err(e) => {
+            report_download_error(&e);
+            false
+        }
+    }
+}
+
+/// `info`: título, canal, duração, data e o status de live de um vídeo.
+async fn run_info(url: &str) -> bool {
+    if !is_youtube_url(url) {
+        report_download_error(&DownloadError::InvalidUrl(url.to_string()));
+        return false;
+    }
+    let video = match search::video_info(url, &AccessOptions::default()).await {
+        Ok(video) => video,
+        Err(e) => {
+            report_download_error(&e);
+            return false;
+        }
+    };
+    let now = stats::now();
+    println!("{}", format!("🎬 {}", video.title).cyan().bold());
+    let rows = [
+        (t!("info.channel"), video.channel.clone()),
+        (
+            t!("info.duration"),
+            video.duration_secs.map(search::format_clock),
+        ),
+        (
+            t!("info.upload_date"),
+            video
+                .upload_date
+                .as_deref()
+                .and_then(|date| UploadDate::parse(date).ok())
+                .map(|date| date.to_string()),
+        ),
+        (
+            t!("info.live_status"),
+            LiveStatus::of(&video).map(|status| status.to_string()),
+        ),
+        (t!("info.starts_in"), live::starts_in(&video, now)),
+    ];
+    for (label, value) in rows {
+        if let Some(value) = value {
+            println!("  {:<14} {}", format!("{label}:").dimmed(), value);
+        }
+    }
+    true
+}
 
-        // Mostrar saída do yt-dlp se houver
-        if !output.stdout.is_empty() {
-            println!("{}", String::from_utf8_lossy(&output.stdout));
+/// `--search`: mostra os resultados e pergunta quais baixar.
+async fn run_cli_search(matches: &ArgMatches, query: &str, limit: usize) {
+    // Um filtro inválido deve falhar antes da busca
+    let matcher = match cli_filters(matches).compile() {
+        Ok(matcher) => matcher,
+        Err(e) => {
+            report_download_error(&e);
+            std::process::exit(1);
         }
+    };
+    println!("{}", t!("search.searching").cyan());
+    let results = match search::search(query, limit).await {
+        Ok(results) => results,
+        Err(e) => {
+            report_download_error(&e);
+            std::process::exit(1);
+        }
+    };
+    search::print_results(query, &results);
+    if results.is_empty() {
+        return;
+    }
+
+    print!("{}", t!("search.pick_prompt").yellow());
+    let _ = io::stdout().flush();
+    let mut line = String::new();
+    if io::stdin().read_line(&mut line).is_err() || line.trim().is_empty() {
+        return;
+    }
+    // Aceita tanto `get 1,4-6 -a` quanto só `1,4-6 -a`
+    let line = line.trim();
+    let line = if line.starts_with("get ") {
+        line.to_string()
     } else {
-        let error_msg = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Erro no download: {error_msg}").into());
+        format!("get {line}")
+    };
+    let (numbers, audio_only) = match shell::parse(&line) {
+        Ok(Some(ReplCommand::Get {
+            numbers,
+            audio_only,
+        })) => (numbers, audio_only || matches.get_flag("audio-only")),
+        Ok(_) => return,
+        Err(e) => {
+            println!("{}", e.to_string().red());
+            std::process::exit(1);
+        }
+    };
+    let picked = match search::select(&results, &numbers) {
+        Ok(picked) => picked,
+        Err(e) => {
+            println!("{}", e.to_string().red());
+            std::process::exit(1);
+        }
+    };
+
+    let picked = picked.into_iter().cloned().collect();
+    let selection = filter::select(&matcher, picked, &AccessOptions::default()).await;
+    report_skipped(&selection.skipped);
+    download_each(matches, &selection.accepted, audio_only).await;
+}
+
+/// Comando `throttle`: o novo limite vale para os próximos downloads.
+fn throttle(change: ThrottleChange) {
+    match change {
+        ThrottleChange::Show => {}
+        ThrottleChange::Set(limit) => bandwidth::set_override(Some(limit)),
+        ThrottleChange::Auto => bandwidth::set_override(None),
     }
+    let limit = bandwidth::current_limit(&Config::load().bandwidth);
+    let source = if bandwidth::override_limit().is_some() {
+        t!("bandwidth.source_session")
+    } else {
+        t!("bandwidth.source_config")
+    };
+    println!(
+        "{}",
+        t!("bandwidth.current", limit = limit, source = source).cyan()
+    );
+}
 
-    Ok(())
+/// Avisa sobre downloads interrompidos em execuções anteriores.
+fn announce_unfinished(hint: &'static str) {
+    let count = journal::unfinished().len();
+    if count > 0 {
+        println!("{}", t!("resume.found", count = count).yellow().bold());
+        println!("{}", i18n::tr(hint).yellow());
+    }
 }
 
-fn is_youtube_url(url: &str) -> bool {
-    let youtube_regex =
-        Regex::new(r"^https?://(www\.|m\.)?(youtube\.com/(watch\?v=|shorts/)|youtu\.be/)[\w-]+")
-            .unwrap();
-    youtube_regex.is_match(url)
+fn print_resuming(index: usize, total: usize, entry: &journal::Entry) {
+    println!(
+        "{}",
+        t!(
+            "resume.resuming",
+            current = index + 1,
+            total = total,
+            url = entry.url
+        )
+        .green()
+    );
 }
 
-// Função removida - não é mais necessária com yt-dlp
+/// `--resume`: retoma todos os downloads interrompidos. Retorna `false` se
+/// algum falhou.
+async fn run_cli_resume() -> bool {
+    let entries = journal::unfinished();
+    if entries.is_empty() {
+        println!("{}", t!("resume.nothing").yellow());
+        return true;
+    }
+    let mut ok = true;
+    for (index, entry) in entries.iter().enumerate() {
+        print_resuming(index, entries.len(), entry);
+        match download(&entry.options()).await {
+            Ok(_) => println!("{}", t!("cli.download_done").green().bold()),
+            Err(e) => {
+                report_download_error(&e);
+                ok = false;
+            }
+        }
+    }
+    ok
+}
 
-// Função removida - agora usamos yt-dlp para extrair URLs
-// Esta abordagem anterior não funcionava porque o YouTube usa
-// JavaScript dinâmico e criptografia para proteger as URLs
+fn discard_unfinished() {
+    match journal::discard_unfinished() {
+        Ok(count) => println!("{}", t!("resume.discarded", count = count).cyan()),
+        Err(e) => println!("{}", t!("resume.discard_failed", error = e).red()),
+    }
+}
 
-// Função removida - agora usamos yt-dlp para extrair URLs
-// Esta abordagem anterior não funcionava porque o YouTube usa
-// JavaScript dinâmico e criptografia para proteger as URLs
+fn report_download_error(e: &DownloadError) {
+    println!("{}", t!("cli.download_error", error = e).red().bold());
+    if e.suggests_backend_update() {
+        println!("{}", t!("cli.update_hint").yellow());
+    }
+}
 
 async fn show_random_ascii_art() {
     let ascii_arts = [
@@ -377,18 +1518,33 @@ async fn run_interactive_mode() -> Result<(), Box<dyn std::error::Error>> {
     // Animação de entrada
     show_welcome_animation().await;
 
-    println!("{}", "🚀 Bem-vindo ao modo interativo!".green().bold());
-    println!(
-        "{}",
-        "Digite 'help' para ver os comandos disponíveis.".yellow()
-    );
-    println!("{}", "Digite 'exit' ou 'quit' para sair.".yellow());
+    println!("{}", t!("interactive.welcome").green().bold());
+    println!("{}", t!("interactive.help_hint").yellow());
+    println!("{}", t!("interactive.exit_hint").yellow());
     println!();
-
-    let mut rl = DefaultEditor::new()?;
-    let mut session_downloads = 0;
+    announce_unfinished("resume.hint_interactive");
+
+    let editor_config = rustyline::Config::builder()
+        .completion_type(CompletionType::List)
+        .auto_add_history(false)
+        .build();
+    let mut rl = Editor::<ReplHelper, DefaultHistory>::with_config(editor_config)?;
+    rl.set_helper(Some(ReplHelper::new()));
+    let history = paths::data_dir().join("history.txt");
+    // Na primeira execução o arquivo ainda não existe
+    let _ = rl.load_history(&history);
+    let mut session_downloads: u32 = 0;
+    // Avisos de downloads em segundo plano sem quebrar o prompt
+    let printer = rl
+        .create_external_printer()
+        .ok()
+        .map(|printer| Box::new(printer) as Box<dyn ExternalPrinter + Send>);
+    let mut background = controls::Background::new(printer);
+    // Resultados da última busca, usados pelo `get`
+    let mut last_search: Vec<SearchResult> = Vec::new();
 
     loop {
+        session_downloads += background.collect();
         let readline = rl.readline(&format!("{} ", "ytd>".bright_green().bold()));
         match readline {
             Ok(line) => {
@@ -400,163 +1556,239 @@ async fn run_interactive_mode() -> Result<(), Box<dyn std::error::Error>> {
 
                 rl.add_history_entry(line)?;
 
-                match line {
-                    "exit" | "quit" => {
+                let command = match shell::parse(line) {
+                    Ok(Some(command)) => command,
+                    Ok(None) => continue,
+                    Err(ParseError::UnknownCommand(_)) => {
+                        show_typing_animation(&t!("interactive.unknown_command", command = line))
+                            .await;
+                        continue;
+                    }
+                    Err(e) => {
+                        println!("{}", e.to_string().red());
+                        continue;
+                    }
+                };
+
+                match command {
+                    ReplCommand::Exit => {
+                        session_downloads += background.shutdown().await;
                         show_goodbye_animation().await;
-                        println!("{}", "👋 Obrigado por usar o YouTube Downloader!".green());
+                        println!("{}", t!("interactive.thanks").green());
                         if session_downloads > 0 {
                             println!(
                                 "{}",
-                                format!("📊 Total de downloads nesta sessão: {session_downloads}")
-                                    .cyan()
+                                t!("interactive.session_total", count = session_downloads).cyan()
                             );
                         }
                         break;
                     }
-                    "help" => {
-                        show_animated_help().await;
+                    ReplCommand::Help => show_animated_help().await,
+                    ReplCommand::Clear => clear_screen_with_animation().await,
+                    ReplCommand::Demo => show_demo_animation().await,
+                    ReplCommand::Doctor { dir } => {
+                        show_spinner(&t!("interactive.diagnosing"), 500).await;
+                        doctor::diagnose(Path::new(&dir), &Config::load().doctor).print();
                     }
-                    "clear" => {
-                        clear_screen_with_animation().await;
+                    ReplCommand::Stats(period) => {
+                        show_animated_stats(session_downloads, period).await
                     }
-                    "stats" => {
-                        show_animated_stats(session_downloads).await;
+                    ReplCommand::UpdateBackend(action) => {
+                        run_update_backend(&action).await;
                     }
-                    "demo" => {
-                        show_demo_animation().await;
+                    ReplCommand::Download {
+                        url,
+                        output,
+                        audio_only,
+                    } => {
+                        show_download_progress_animation().await;
+                        let options = DownloadOptions::new(&url, &output, audio_only);
+                        if interactive_download(options, &mut background).await {
+                            session_downloads += 1;
+                        }
+                    }
+                    ReplCommand::Search { query, limit } => {
+                        show_spinner(&t!("search.searching"), 300).await;
+                        match search::search(&query, limit).await {
+                            Ok(results) => {
+                                search::print_results(&query, &results);
+                                last_search = results;
+                            }
+                            Err(e) => report_download_error(&e),
+                        }
                     }
-                    _ => {
-                        if line.starts_with("download ") {
-                            let parts: Vec<&str> = line.split_whitespace().collect();
-                            if parts.len() >= 2 {
-                                let url = parts[1];
-                                let audio_only =
-                                    parts.contains(&"-a") || parts.contains(&"--audio");
-                                let output_name =
-                                    if let Some(pos) = parts.iter().position(|&x| x == "-o") {
-                                        if pos + 1 < parts.len() {
-                                            parts[pos + 1]
-                                        } else if audio_only {
-                                            "audio"
-                                        } else {
-                                            "video"
-                                        }
-                                    } else if audio_only {
-                                        "audio"
-                                    } else {
-                                        "video"
-                                    };
-
-                                show_download_progress_animation().await;
-                                match download_video(url, output_name, audio_only).await {
-                                    Ok(_) => {
-                                        show_success_animation().await;
-                                        session_downloads += 1;
-                                    }
-                                    Err(e) => {
-                                        show_error_animation().await;
-                                        println!(
-                                            "{}",
-                                            format!("❌ Erro no download: {e}").red().bold()
-                                        );
-                                    }
-                                }
-                            } else {
-                                println!(
-                                    "{}",
-                                    "❌ Uso: download <URL> [-a] [-o nome_arquivo]".red()
-                                );
+                    ReplCommand::Get {
+                        numbers,
+                        audio_only,
+                    } => {
+                        if last_search.is_empty() {
+                            println!("{}", t!("search.nothing_to_get").red());
+                            continue;
+                        }
+                        let picked = match search::select(&last_search, &numbers) {
+                            Ok(picked) => picked,
+                            Err(e) => {
+                                println!("{}", e.to_string().red());
+                                continue;
                             }
-                        } else if is_youtube_url(line) {
-                            // Se é uma URL válida, fazer download direto
-                            show_url_detection_animation().await;
-                            match download_video(line, "video", false).await {
-                                Ok(_) => {
-                                    show_success_animation().await;
-                                    session_downloads += 1;
-                                }
-                                Err(e) => {
-                                    show_error_animation().await;
-                                    println!(
-                                        "{}",
-                                        format!("❌ Erro no download: {e}").red().bold()
-                                    );
-                                }
+                        };
+                        for (index, result) in picked.iter().enumerate() {
+                            println!(
+                                "{}",
+                                t!(
+                                    "search.downloading",
+                                    current = index + 1,
+                                    total = picked.len(),
+                                    title = result.title
+                                )
+                                .green()
+                            );
+                            let options =
+                                DownloadOptions::new(&result.url, TITLE_OUTPUT, audio_only);
+                            if interactive_download(options, &mut background).await {
+                                session_downloads += 1;
+                            }
+                        }
+                    }
+                    ReplCommand::Throttle(change) => throttle(change),
+                    ReplCommand::Resume { discard: true } => discard_unfinished(),
+                    ReplCommand::Resume { discard: false } => {
+                        let entries = journal::unfinished();
+                        if entries.is_empty() {
+                            println!("{}", t!("resume.nothing").yellow());
+                        }
+                        for (index, entry) in entries.iter().enumerate() {
+                            print_resuming(index, entries.len(), entry);
+                            if interactive_download(entry.options(), &mut background).await {
+                                session_downloads += 1;
                             }
-                        } else {
-                            show_typing_animation(&format!("❌ Comando desconhecido: '{line}'. Digite 'help' para ver os comandos disponíveis.")).await;
+                        }
+                    }
+                    ReplCommand::Url(url) => {
+                        // Se é uma URL válida, fazer download direto
+                        show_url_detection_animation().await;
+                        let options = DownloadOptions::new(&url, "video", false);
+                        if interactive_download(options, &mut background).await {
+                            session_downloads += 1;
                         }
                     }
                 }
             }
             Err(ReadlineError::Interrupted) => {
-                println!("{}", "CTRL-C detectado. Digite 'exit' para sair.".yellow());
+                println!("{}", t!("interactive.ctrl_c").yellow());
             }
             Err(ReadlineError::Eof) => {
-                println!("{}", "CTRL-D detectado. Saindo...".yellow());
+                println!("{}", t!("interactive.ctrl_d").yellow());
                 break;
             }
             Err(err) => {
-                println!("{}", format!("Erro: {err:?}").red());
+                println!(
+                    "{}",
+                    t!("interactive.readline_error", error = format!("{err:?}")).red()
+                );
                 break;
             }
         }
     }
 
+    background.shutdown().await;
+
+    let saved = std::fs::create_dir_all(paths::data_dir())
+        .map_err(|e| e.to_string())
+        .and_then(|_| rl.save_history(&history).map_err(|e| e.to_string()));
+    if let Err(e) = saved {
+        println!("{}", t!("interactive.history_error", error = e).yellow());
+    }
+
     Ok(())
 }
 
+/// Baixa a partir do modo interativo, com as animações de sucesso ou erro.
+/// Retorna `false` também quando o download vai para o segundo plano.
+async fn interactive_download(
+    options: DownloadOptions,
+    background: &mut controls::Background,
+) -> bool {
+    match controls::run(options, background).await {
+        Outcome::Finished(Ok(_)) => {
+            show_success_animation().await;
+            true
+        }
+        Outcome::Finished(Err(DownloadError::Cancelled)) => {
+            println!("{}", t!("error.cancelled").yellow());
+            false
+        }
+        Outcome::Background(id) => {
+            println!("{}", t!("controls.backgrounded", id = id).cyan());
+            false
+        }
+        Outcome::Finished(Err(e)) => {
+            show_error_animation().await;
+            report_download_error(&e);
+            false
+        }
+    }
+}
+
 fn show_help() {
-    println!("{}", "📋 Comandos disponíveis:".cyan().bold());
+    let commands = [
+        ("download <URL>".to_string(), t!("help.download")),
+        ("download <URL> -a".to_string(), t!("help.download_audio")),
+        (t!("help.download_named_syntax"), t!("help.download_named")),
+        ("<URL>".to_string(), t!("help.url")),
+        ("search <termos>".to_string(), t!("help.search")),
+        ("resume [--discard]".to_string(), t!("help.resume")),
+        ("throttle [2M|off|auto]".to_string(), t!("help.throttle")),
+        ("get 1,4-6 [-a]".to_string(), t!("help.get")),
+        ("help".to_string(), t!("help.help")),
+        ("clear".to_string(), t!("help.clear")),
+        ("stats".to_string(), t!("help.stats")),
+        ("demo".to_string(), t!("help.demo")),
+        ("doctor".to_string(), t!("help.doctor")),
+        ("update-backend".to_string(), t!("help.update_backend")),
+        ("exit/quit".to_string(), t!("help.exit")),
+    ];
+    let tips = [
+        "help.tip_paste",
+        "help.tip_shorts",
+        "help.tip_flags",
+        "help.tip_formats",
+        "help.tip_history",
+        "help.tip_keys",
+    ];
+
+    println!("{}", t!("help.title").cyan().bold());
     println!();
-    println!("{:<20} Baixar vídeo em MP4", "download <URL>".green());
-    println!(
-        "{:<20} Baixar apenas áudio em MP3",
-        "download <URL> -a".green()
-    );
-    println!(
-        "{:<20} Baixar com nome personalizado",
-        "download <URL> -o nome".green()
-    );
-    println!("{:<20} Baixar vídeo diretamente (atalho)", "<URL>".green());
-    println!("{:<20} Mostrar esta ajuda", "help".green());
-    println!("{:<20} Limpar a tela", "clear".green());
-    println!("{:<20} Mostrar estatísticas da sessão", "stats".green());
-    println!("{:<20} Demonstração de animações", "demo".green());
-    println!("{:<20} Sair do programa", "exit/quit".green());
+    for (command, description) in &commands {
+        println!("{:<20} {description}", command.green());
+    }
     println!();
-    println!("{}", "💡 Dicas:".yellow().bold());
-    println!("  • Você pode colar URLs diretamente");
-    println!("  • Suporte completo para YouTube Shorts");
-    println!("  • Use -a para áudio MP3 e -o para nome personalizado");
-    println!("  • Vídeos são baixados em MP4, áudios em MP3");
-    println!("  • Histórico de comandos disponível (↑/↓)");
+    println!("{}", t!("help.tips").yellow().bold());
+    for tip in tips {
+        println!("  • {}", i18n::tr(tip));
+    }
     println!();
 }
 
 async fn show_welcome_animation() {
-    let frames = [
-        "🎬 Carregando YouTube Downloader...",
-        "📺 Carregando YouTube Downloader...",
-        "🎥 Carregando YouTube Downloader...",
-        "📹 Carregando YouTube Downloader...",
-    ];
+    let frames = ["🎬", "📺", "🎥", "📹"];
+    let message = t!("anim.loading");
 
     for frame in &frames {
-        print!("\r{}", frame.cyan());
+        print!("\r{}", format!("{frame} {message}").cyan());
         io::stdout().flush().unwrap();
         sleep(Duration::from_millis(200)).await;
     }
-    println!("\r{}", "✅ YouTube Downloader carregado!".green().bold());
+    println!("\r{}", t!("anim.loaded").green().bold());
     sleep(Duration::from_millis(300)).await;
 }
 
 async fn show_goodbye_animation() {
     let frames = [
-        "👋 Finalizando...",
-        "💾 Salvando configurações...",
-        "🧹 Limpando cache...",
-        "✨ Concluído!",
+        t!("anim.goodbye_finishing"),
+        t!("anim.goodbye_saving"),
+        t!("anim.goodbye_cache"),
+        t!("anim.goodbye_done"),
     ];
 
     for frame in &frames {
@@ -566,7 +1798,7 @@ async fn show_goodbye_animation() {
 }
 
 async fn show_animated_help() {
-    show_typing_animation("📋 Carregando ajuda...").await;
+    show_typing_animation(&t!("anim.loading_help")).await;
     sleep(Duration::from_millis(200)).await;
     show_help();
 }
@@ -575,66 +1807,73 @@ async fn clear_screen_with_animation() {
     let spinner = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 
     for i in 0..10 {
-        print!("\r{} Limpando tela...", spinner[i % spinner.len()].cyan());
+        print!(
+            "\r{} {}",
+            spinner[i % spinner.len()].cyan(),
+            t!("anim.clearing")
+        );
         io::stdout().flush().unwrap();
         sleep(Duration::from_millis(100)).await;
     }
 
     print!("\x1B[2J\x1B[1;1H");
     io::stdout().flush().unwrap();
-    println!("{}", "✨ Tela limpa!".green());
+    println!("{}", t!("anim.cleared").green());
 }
 
-async fn show_animated_stats(downloads: i32) {
-    show_progress_bar("Calculando estatísticas", 100).await;
+async fn show_animated_stats(downloads: u32, period: Period) {
+    show_progress_bar(&t!("anim.computing_stats"), 100).await;
     println!(
         "{}",
-        format!("📊 Downloads nesta sessão: {downloads}")
-            .cyan()
-            .bold()
+        t!("anim.stats_session", count = downloads).cyan().bold()
     );
+    println!();
+    stats::summarize(&stats::load(), period).print();
+    println!();
 
     if downloads > 0 {
-        println!("{}", "🎉 Parabéns pelos downloads!".green());
+        println!("{}", t!("anim.stats_congrats").green());
     } else {
-        println!("{}", "💡 Que tal fazer seu primeiro download?".yellow());
+        println!("{}", t!("anim.stats_first").yellow());
     }
 }
 
 async fn show_demo_animation() {
-    println!("{}", "🎪 Demonstração de Animações".magenta().bold());
+    println!("{}", t!("anim.demo_title").magenta().bold());
     println!();
 
     // Spinner
-    show_spinner("Demonstrando spinner", 2000).await;
+    show_spinner(&t!("anim.demo_spinner"), 2000).await;
 
     // Progress bar
-    show_progress_bar("Demonstrando barra de progresso", 50).await;
+    show_progress_bar(&t!("anim.demo_progress"), 50).await;
 
     // Typing effect
-    show_typing_animation("Este é um efeito de digitação! 🎯").await;
+    show_typing_animation(&t!("anim.demo_typing")).await;
 
     // Matrix effect
     show_matrix_effect().await;
 
-    println!("{}", "✨ Demonstração concluída!".green().bold());
+    println!("{}", t!("anim.demo_done").green().bold());
 }
 
 async fn show_download_progress_animation() {
-    show_spinner("Iniciando download", 1000).await;
-    show_progress_bar("Baixando", 30).await;
+    show_spinner(&t!("anim.starting_download"), 1000).await;
+    show_progress_bar(&t!("anim.downloading"), 30).await;
 }
 
 async fn show_url_detection_animation() {
-    show_typing_animation("🔍 URL do YouTube detectada! Analisando...").await;
-    show_spinner("Verificando vídeo", 800).await;
+    show_typing_animation(&t!("anim.url_detected")).await;
+    show_spinner(&t!("anim.checking_video"), 800).await;
 }
 
 async fn show_success_animation() {
     let success_frames = ["✅", "🎉", "✅", "🎉", "✅"];
 
+    let message = t!("anim.success");
+
     for frame in &success_frames {
-        print!("\r{frame} Download concluído com sucesso!");
+        print!("\r{frame} {message}");
         io::stdout().flush().unwrap();
         sleep(Duration::from_millis(200)).await;
     }
@@ -644,8 +1883,10 @@ async fn show_success_animation() {
 async fn show_error_animation() {
     let error_frames = ["❌", "💥", "❌", "💥", "❌"];
 
+    let message = t!("anim.error");
+
     for frame in &error_frames {
-        print!("\r{frame} Erro no download!");
+        print!("\r{frame} {message}");
         io::stdout().flush().unwrap();
         sleep(Duration::from_millis(200)).await;
     }
@@ -702,7 +1943,7 @@ async fn show_typing_animation(text: &str) {
 }
 
 async fn show_matrix_effect() {
-    println!("{}", "🔢 Efeito Matrix:".green().bold());
+    println!("{}", t!("anim.matrix").green().bold());
 
     let matrix_chars = ["0", "1", "0", "1", "0", "1"];
 
@@ -722,58 +1963,6 @@ async fn show_matrix_effect() {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::process::Command;
-
-    #[test]
-    fn test_is_youtube_url() {
-        // Testa URLs válidas do YouTube
-        assert!(is_youtube_url(
-            "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
-        ));
-        assert!(is_youtube_url("https://youtu.be/dQw4w9WgXcQ"));
-        assert!(is_youtube_url("https://m.youtube.com/watch?v=dQw4w9WgXcQ"));
-        assert!(is_youtube_url("https://youtube.com/watch?v=dQw4w9WgXcQ"));
-        assert!(is_youtube_url("http://www.youtube.com/watch?v=dQw4w9WgXcQ"));
-        assert!(is_youtube_url("https://www.youtube.com/shorts/dQw4w9WgXcQ"));
-        assert!(is_youtube_url("https://youtube.com/shorts/dQw4w9WgXcQ"));
-
-        // Testa URLs inválidas
-        assert!(!is_youtube_url("https://www.google.com"));
-        assert!(!is_youtube_url("https://vimeo.com/123456"));
-        assert!(!is_youtube_url("not a url"));
-        assert!(!is_youtube_url(""));
-        assert!(!is_youtube_url("https://youtube.com"));
-    }
-
-    #[test]
-    fn test_check_yt_dlp_installation() {
-        // Testa se conseguimos verificar a instalação do yt-dlp
-        let result = Command::new("which").arg("yt-dlp").output();
-
-        // O teste passa se conseguimos executar o comando 'which', independente do resultado
-        assert!(result.is_ok() || result.is_err());
-    }
-
-    #[test]
-    fn test_pip3_availability() {
-        // Testa se pip3 está disponível para instalação do yt-dlp
-        let result = Command::new("which").arg("pip3").output();
-
-        match result {
-            Ok(_) => {
-                // pip3 encontrado
-            }
-            Err(_) => {
-                // pip3 pode não estar disponível em alguns sistemas
-                println!(
-                    "pip3 não encontrado - isso pode afetar a instalação automática do yt-dlp"
-                );
-            }
-        }
-
-        // O teste sempre passa, apenas verifica a disponibilidade
-        assert!(result.is_ok() || result.is_err());
-    }
 
     #[tokio::test]
     async fn test_animation_functions() {
@@ -790,73 +1979,46 @@ mod tests {
         assert_eq!(result.unwrap(), "animation_test_passed");
     }
 
-    #[test]
-    fn test_url_validation_edge_cases() {
-        // Testa casos extremos de validação de URL
-        let test_cases = vec![
-            ("https://www.youtube.com/watch?v=", false), // URL sem ID
-            ("https://youtu.be/", false),                // URL curta sem ID
-            ("https://www.youtube.com/watch", false),    // URL sem parâmetros
-            ("youtube.com/watch?v=dQw4w9WgXcQ", false),  // URL sem protocolo
-            ("https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=10s", true), // URL com timestamp
-            (
-                "https://www.youtube.com/watch?v=dQw4w9WgXcQ&list=PLxxx",
-                true,
-            ), // URL com playlist
-        ];
-
-        for (url, expected) in test_cases {
-            assert_eq!(is_youtube_url(url), expected, "Failed for URL: {url}");
-        }
-    }
-
     #[test]
     fn test_command_parsing() {
-        // Testa se conseguimos simular o parsing de comandos
+        // Os comandos de download do modo interativo passam pelo parser real
         let test_commands = vec![
-            "download https://youtu.be/test",
-            "download https://youtu.be/test -a",
-            "download https://youtu.be/test -o myvideo",
-            "download https://youtu.be/test -a -o myaudio",
+            ("download https://youtu.be/test", "video", false),
+            ("download https://youtu.be/test -a", "audio", true),
+            (
+                "download https://youtu.be/test -o myvideo",
+                "myvideo",
+                false,
+            ),
+            (
+                "download https://youtu.be/test -a -o myaudio",
+                "myaudio",
+                true,
+            ),
+            (
+                r#"download https://youtu.be/test -o "my video""#,
+                "my video",
+                false,
+            ),
         ];
 
-        for cmd in test_commands {
-            let parts: Vec<&str> = cmd.split_whitespace().collect();
-            assert!(
-                parts.len() >= 2,
-                "Command should have at least 2 parts: {cmd}"
-            );
-            assert_eq!(
-                parts[0], "download",
-                "First part should be 'download': {cmd}"
-            );
-            // Note: We can't test is_youtube_url with 'test' as it's not a valid YouTube URL
-            assert!(
-                parts[1].contains("youtu"),
-                "Second part should contain 'youtu': {cmd}"
-            );
-        }
-    }
-
-    #[tokio::test]
-    async fn test_download_with_mock_url() {
-        // Teste que simula download com URL inválida (deve falhar graciosamente)
-        let result = download_video("https://youtube.com/invalid", "test", false).await;
-
-        // O teste passa se a função não causa panic, independente do resultado
-        // (pode falhar se yt-dlp não estiver instalado ou URL for inválida)
-        match result {
-            Ok(_) => {
-                // Se passou, yt-dlp está instalado e funcionando
-                println!("Download test passed - yt-dlp is working");
-            }
-            Err(e) => {
-                // Falha esperada com URL inválida ou yt-dlp não instalado
-                println!("Download test failed as expected: {e}");
+        for (cmd, expected_output, expected_audio) in test_commands {
+            match shell::parse(cmd) {
+                Ok(Some(ReplCommand::Download {
+                    url,
+                    output,
+                    audio_only,
+                })) => {
+                    assert_eq!(url, "https://youtu.be/test", "{cmd}");
+                    assert_eq!(output, expected_output, "{cmd}");
+                    assert_eq!(audio_only, expected_audio, "{cmd}");
+                }
+                other => panic!("Unexpected parse result for {cmd}: {other:?}"),
             }
         }
 
-        // O teste sempre passa, apenas verifica se não há panic
+        // `-o` sem valor não cai mais silenciosamente para "video"
+        assert!(shell::parse("download https://youtu.be/test -o").is_err());
     }
 
     #[test]
@@ -875,10 +2037,27 @@ mod tests {
         assert!(downloads > 0);
     }
 
+    #[test]
+    fn test_split_lang_arg() {
+        let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+
+        let (lang, rest) = split_lang_arg(args(&["ytd", "--lang", "en", "doctor"]));
+        assert_eq!(lang.as_deref(), Some("en"));
+        assert_eq!(rest, args(&["ytd", "doctor"]));
+
+        let (lang, rest) = split_lang_arg(args(&["ytd", "doctor", "--lang=pt-BR", "--dir", "."]));
+        assert_eq!(lang.as_deref(), Some("pt-BR"));
+        assert_eq!(rest, args(&["ytd", "doctor", "--dir", "."]));
+
+        let (lang, rest) = split_lang_arg(args(&["ytd", "-a", "https://youtu.be/abc"]));
+        assert_eq!(lang, None);
+        assert_eq!(rest, args(&["ytd", "-a", "https://youtu.be/abc"]));
+    }
+
     #[test]
     fn test_help_command_availability() {
         // Verifica se os comandos de ajuda estão definidos
-        let help_commands = vec!["help", "exit", "quit", "clear", "stats", "demo"];
+        let help_commands = vec!["help", "exit", "quit", "clear", "stats", "demo", "doctor"];
 
         for cmd in help_commands {
             assert!(!cmd.is_empty(), "Help command should not be empty");
@@ -0,0 +1,123 @@
+//! Pareamento de extensões e bookmarklets com o `serve`.
+//!
+//! O `serve` mostra um código no terminal; o navegador o troca uma vez, em
+//! `POST /pair`, por um token próprio que só permite enfileirar downloads
+//! (`POST /enqueue`). Apenas o SHA-256 dos tokens fica em `clients.json` no
+//! diretório de dados; apagar o arquivo desfaz todos os pareamentos.
+
+use crate::paths;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Letras e dígitos sem os que se confundem (`0`/`O`, `1`/`I`).
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// Serializa leituras e escritas do arquivo entre tarefas do mesmo processo.
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Um navegador pareado.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Client {
+    /// Nome informado no pareamento, como `Firefox do escritório`.
+    pub name: String,
+    pub token_sha256: String,
+    /// Momento do pareamento, em segundos desde a época Unix.
+    pub paired_at: u64,
+}
+
+pub fn path() -> PathBuf {
+    paths::data_dir().join("clients.json")
+}
+
+/// Código de pareamento no formato `ABCD-EFGH`.
+pub fn new_code() -> String {
+    let mut rng = rand::thread_rng();
+    let mut code: String = (0..8)
+        .map(|_| CODE_ALPHABET[rng.gen_range(0..CODE_ALPHABET.len())] as char)
+        .collect();
+    code.insert(4, '-');
+    code
+}
+
+/// Compara códigos ignorando maiúsculas, hífens e espaços digitados.
+pub fn code_matches(given: &str, expected: &str) -> bool {
+    let normalize = |code: &str| -> String {
+        code.chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_uppercase())
+            .collect()
+    };
+    let given = normalize(given);
+    !given.is_empty() && given == normalize(expected)
+}
+
+/// Registra um novo navegador e devolve o token dele; o token em si não é
+/// guardado.
+pub fn pair(name: &str) -> io::Result<String> {
+    let token: String = rand::thread_rng()
+        .gen::<[u8; 32]>()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    let client = Client {
+        name: name.to_string(),
+        token_sha256: token_hash(&token),
+        paired_at: crate::stats::now(),
+    };
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut clients = load();
+    clients.push(client);
+    save(&clients)?;
+    Ok(token)
+}
+
+/// Se o token pertence a um navegador pareado.
+pub fn is_paired(token: &str) -> bool {
+    let hash = token_hash(token);
+    clients().iter().any(|client| client.token_sha256 == hash)
+}
+
+pub fn clients() -> Vec<Client> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    load()
+}
+
+fn token_hash(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// Clientes gravados; um arquivo ausente ou corrompido conta como vazio.
+fn load() -> Vec<Client> {
+    fs::read_to_string(path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save(clients: &[Client]) -> io::Result<()> {
+    fs::create_dir_all(paths::data_dir())?;
+    let json = serde_json::to_string_pretty(clients).map_err(io::Error::other)?;
+    let tmp = path().with_extension("json.tmp");
+    fs::write(&tmp, json)?;
+    fs::rename(tmp, path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_matches() {
+        let code = new_code();
+        assert_eq!(code.len(), 9);
+        assert!(code_matches(&code.to_lowercase().replace('-', " "), &code));
+        assert!(code_matches("abcd efgh", "ABCD-EFGH"));
+        assert!(!code_matches("ABCD-EFGX", "ABCD-EFGH"));
+        assert!(!code_matches("-", "ABCD-EFGH"));
+    }
+}
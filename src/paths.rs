@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+const APP_DIR: &str = "youtube_video_downloader";
+
+/// Diretório de dados da aplicação (binários gerenciados, estado, histórico).
+///
+/// Pode ser sobrescrito com a variável de ambiente `YTD_DATA_DIR`.
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("YTD_DATA_DIR") {
+        return PathBuf::from(dir);
+    }
+    dirs::data_dir()
+        .map(|dir| dir.join(APP_DIR))
+        .unwrap_or_else(|| PathBuf::from(".").join(format!(".{APP_DIR}")))
+}
+
+/// Diretório de configuração da aplicação.
+///
+/// Pode ser sobrescrito com a variável de ambiente `YTD_CONFIG_DIR`.
+pub fn config_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("YTD_CONFIG_DIR") {
+        return PathBuf::from(dir);
+    }
+    dirs::config_dir()
+        .map(|dir| dir.join(APP_DIR))
+        .unwrap_or_else(|| PathBuf::from(".").join(format!(".{APP_DIR}")))
+}
+
+/// Caminho do executável dentro de um diretório `bin` (ou `Scripts` no Windows).
+pub fn executable_in(dir: &std::path::Path, name: &str) -> PathBuf {
+    if cfg!(windows) {
+        dir.join("Scripts").join(format!("{name}.exe"))
+    } else {
+        dir.join("bin").join(name)
+    }
+}
@@ -0,0 +1,377 @@
+//! Conversão do arquivo baixado para aparelhos com limitações.
+//!
+//! Cada preset fixa o codec (H.264 com perfil e nível, áudio AAC), a
+//! resolução máxima e a qualidade. Presets com limite de tamanho calculam o
+//! bitrate a partir da duração e codificam o vídeo em duas passagens, para
+//! acertar o tamanho sem desperdiçar qualidade.
+
+use crate::error::DownloadError;
+use crate::tools::{self, Tool};
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// Parte do limite de tamanho reservada ao contêiner e à variação do
+/// bitrate entre as passagens.
+const SIZE_MARGIN: f64 = 0.95;
+
+/// Abaixo disso o vídeo fica irreconhecível; melhor recusar.
+const MIN_VIDEO_KBPS: u32 = 150;
+
+/// Teto do bitrate calculado, para vídeos curtos não ganharem um bitrate
+/// absurdo só porque o limite de tamanho permite.
+const MAX_VIDEO_KBPS: u32 = 4000;
+
+/// Preset de conversão (`--preset`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Preset {
+    #[serde(rename = "iphone")]
+    Iphone,
+    #[serde(rename = "android-low")]
+    AndroidLow,
+    #[serde(rename = "tv-1080")]
+    Tv1080,
+    #[serde(rename = "whatsapp")]
+    Whatsapp,
+    #[serde(rename = "email-25mb")]
+    Email25mb,
+}
+
+/// Restrições de um preset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spec {
+    /// Perfil H.264 (`baseline`, `main`, `high`).
+    pub profile: &'static str,
+    pub level: &'static str,
+    pub max_width: u32,
+    pub max_height: u32,
+    pub audio_kbps: u32,
+    pub quality: Quality,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quality {
+    /// Qualidade constante (`-crf`), em uma passagem.
+    Crf(u8),
+    /// Tamanho máximo do arquivo, em bytes, em duas passagens.
+    MaxBytes(u64),
+}
+
+impl Preset {
+    pub const ALL: [Preset; 5] = [
+        Preset::Iphone,
+        Preset::AndroidLow,
+        Preset::Tv1080,
+        Preset::Whatsapp,
+        Preset::Email25mb,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Preset::Iphone => "iphone",
+            Preset::AndroidLow => "android-low",
+            Preset::Tv1080 => "tv-1080",
+            Preset::Whatsapp => "whatsapp",
+            Preset::Email25mb => "email-25mb",
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<Preset, String> {
+        Preset::ALL
+            .into_iter()
+            .find(|preset| preset.name() == name)
+            .ok_or_else(|| t!("preset.unknown", name = name))
+    }
+
+    pub fn spec(self) -> Spec {
+        match self {
+            Preset::Iphone => Spec {
+                profile: "high",
+                level: "4.1",
+                max_width: 1920,
+                max_height: 1080,
+                audio_kbps: 160,
+                quality: Quality::Crf(23),
+            },
+            Preset::AndroidLow => Spec {
+                profile: "baseline",
+                level: "3.0",
+                max_width: 854,
+                max_height: 480,
+                audio_kbps: 96,
+                quality: Quality::Crf(28),
+            },
+            Preset::Tv1080 => Spec {
+                profile: "high",
+                level: "4.1",
+                max_width: 1920,
+                max_height: 1080,
+                audio_kbps: 192,
+                quality: Quality::Crf(20),
+            },
+            Preset::Whatsapp => Spec {
+                profile: "main",
+                level: "3.1",
+                max_width: 1280,
+                max_height: 720,
+                audio_kbps: 128,
+                quality: Quality::MaxBytes(16_000_000),
+            },
+            Preset::Email25mb => Spec {
+                profile: "main",
+                level: "3.1",
+                max_width: 1280,
+                max_height: 720,
+                audio_kbps: 96,
+                quality: Quality::MaxBytes(25_000_000),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Bitrates de uma conversão com limite de tamanho.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Bitrates {
+    /// `None` para um arquivo só de áudio.
+    video_kbps: Option<u32>,
+    audio_kbps: u32,
+}
+
+/// Divide o limite de tamanho entre vídeo e áudio pela duração. O áudio
+/// fica com o bitrate do preset, ou com tudo, num arquivo só de áudio.
+fn size_bitrates(
+    spec: &Spec,
+    max_bytes: u64,
+    duration_secs: f64,
+    audio_only: bool,
+) -> Result<Bitrates, String> {
+    let total_kbps = (max_bytes as f64 * 8.0 * SIZE_MARGIN / duration_secs / 1000.0) as u32;
+    let too_long = || {
+        t!(
+            "preset.too_long",
+            size = format!("{:.0}", max_bytes as f64 / 1_000_000.0)
+        )
+    };
+    if audio_only {
+        // 32 kbps ainda é inteligível para voz
+        return match total_kbps.min(spec.audio_kbps) {
+            kbps if kbps >= 32 => Ok(Bitrates {
+                video_kbps: None,
+                audio_kbps: kbps,
+            }),
+            _ => Err(too_long()),
+        };
+    }
+    let video_kbps = total_kbps.saturating_sub(spec.audio_kbps);
+    if video_kbps < MIN_VIDEO_KBPS {
+        return Err(too_long());
+    }
+    Ok(Bitrates {
+        video_kbps: Some(video_kbps.min(MAX_VIDEO_KBPS)),
+        audio_kbps: spec.audio_kbps,
+    })
+}
+
+/// Reduz à resolução máxima sem ampliar, mantendo a proporção e dimensões
+/// pares, como o H.264 exige.
+fn scale_filter(spec: &Spec) -> String {
+    format!(
+        "scale='min(iw,{w})':'min(ih,{h})':force_original_aspect_ratio=decrease:force_divisible_by=2",
+        w = spec.max_width,
+        h = spec.max_height
+    )
+}
+
+/// Argumentos de vídeo do ffmpeg; `pass` é a passagem e o prefixo do log de
+/// uma codificação em duas passagens.
+fn video_args(spec: &Spec, video_kbps: Option<u32>, pass: Option<(u8, &Path)>) -> Vec<OsString> {
+    let mut args: Vec<OsString> = [
+        "-c:v",
+        "libx264",
+        "-preset",
+        "medium",
+        "-profile:v",
+        spec.profile,
+        "-level:v",
+        spec.level,
+        "-pix_fmt",
+        "yuv420p",
+        "-vf",
+    ]
+    .map(OsString::from)
+    .into();
+    args.push(scale_filter(spec).into());
+    match (spec.quality, video_kbps) {
+        (_, Some(kbps)) => args.extend(["-b:v".into(), format!("{kbps}k").into()]),
+        (Quality::Crf(crf), None) => args.extend(["-crf".into(), crf.to_string().into()]),
+        (Quality::MaxBytes(_), None) => {}
+    }
+    if let Some((pass, log)) = pass {
+        args.extend([
+            "-pass".into(),
+            pass.to_string().into(),
+            "-passlogfile".into(),
+            log.as_os_str().to_owned(),
+        ]);
+    }
+    args
+}
+
+fn audio_args(audio_kbps: u32) -> Vec<OsString> {
+    ["-c:a", "aac", "-b:a"]
+        .map(OsString::from)
+        .into_iter()
+        .chain([format!("{audio_kbps}k").into()])
+        .collect()
+}
+
+/// Converte `path` conforme o preset, substituindo-o por `<nome>.mp4` (ou
+/// `<nome>.m4a`, só áudio). Devolve o caminho do arquivo convertido.
+///
+/// `duration_secs` é necessária para os presets com limite de tamanho.
+pub async fn transcode(
+    path: &Path,
+    preset: Preset,
+    audio_only: bool,
+    duration_secs: Option<f64>,
+) -> Result<PathBuf, DownloadError> {
+    let spec = preset.spec();
+    let bitrates = match spec.quality {
+        Quality::MaxBytes(max_bytes) => {
+            let duration = duration_secs
+                .filter(|secs| *secs > 0.0)
+                .ok_or_else(|| DownloadError::Transcode(t!("preset.no_duration")))?;
+            size_bitrates(&spec, max_bytes, duration, audio_only)
+                .map_err(DownloadError::Transcode)?
+        }
+        Quality::Crf(_) => Bitrates {
+            video_kbps: None,
+            audio_kbps: spec.audio_kbps,
+        },
+    };
+
+    let ext = if audio_only { "m4a" } else { "mp4" };
+    let temp = path.with_extension(format!("{preset}.{ext}"));
+    let log = path.with_extension(format!("{preset}.passlog"));
+    let two_pass = !audio_only && bitrates.video_kbps.is_some();
+
+    let result = async {
+        if two_pass {
+            // Primeira passagem: só analisa o vídeo, sem áudio nem saída
+            let mut args = vec![OsString::from("-y"), "-i".into(), path.into()];
+            args.extend(video_args(&spec, bitrates.video_kbps, Some((1, &log))));
+            args.extend(["-an", "-f", "null", "-"].map(OsString::from));
+            run_ffmpeg(args).await?;
+        }
+        let mut args = vec![OsString::from("-y"), "-i".into(), path.into()];
+        if audio_only {
+            args.push("-vn".into());
+        } else {
+            let pass = two_pass.then_some((2, log.as_path()));
+            args.extend(video_args(&spec, bitrates.video_kbps, pass));
+        }
+        args.extend(audio_args(bitrates.audio_kbps));
+        // Índice no começo, para tocar enquanto carrega
+        args.extend(["-movflags", "+faststart"].map(OsString::from));
+        args.push(temp.clone().into());
+        run_ffmpeg(args).await
+    }
+    .await;
+
+    if two_pass {
+        remove_pass_logs(&log);
+    }
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp);
+        return Err(e);
+    }
+    let output = path.with_extension(ext);
+    fs::rename(&temp, &output)?;
+    if output != path {
+        fs::remove_file(path)?;
+    }
+    Ok(output)
+}
+
+async fn run_ffmpeg(args: Vec<OsString>) -> Result<(), DownloadError> {
+    let ffmpeg = tools::detect(Tool::Ffmpeg).ok_or(DownloadError::MissingDependency("ffmpeg"))?;
+    let output = Command::new(&ffmpeg.program)
+        .args(["-hide_banner", "-loglevel", "error", "-nostdin"])
+        .args(args)
+        .output()
+        .await?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(DownloadError::Transcode(t!(
+        "preset.failed",
+        error = stderr.trim()
+    )))
+}
+
+/// O x264 deixa `<prefixo>-0.log` e `<prefixo>-0.log.mbtree`.
+fn remove_pass_logs(log: &Path) {
+    for suffix in ["-0.log", "-0.log.mbtree"] {
+        let mut file = log.as_os_str().to_owned();
+        file.push(suffix);
+        let _ = fs::remove_file(file);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_names() {
+        for preset in Preset::ALL {
+            assert_eq!(Preset::parse(preset.name()), Ok(preset));
+        }
+        assert!(Preset::parse("playstation").is_err());
+        assert_eq!(
+            serde_json::to_string(&Preset::AndroidLow).unwrap(),
+            "\"android-low\""
+        );
+    }
+
+    #[test]
+    fn test_size_bitrates() {
+        let spec = Preset::Email25mb.spec();
+        // 10 minutos em 25 MB: 316 kbps no total, 96 para o áudio
+        let bitrates = size_bitrates(&spec, 25_000_000, 600.0, false).unwrap();
+        assert_eq!(bitrates.video_kbps, Some(220));
+        assert_eq!(bitrates.audio_kbps, 96);
+
+        // Curto demais para precisar de muito: o teto vale
+        let bitrates = size_bitrates(&spec, 25_000_000, 10.0, false).unwrap();
+        assert_eq!(bitrates.video_kbps, Some(MAX_VIDEO_KBPS));
+
+        // Uma hora não cabe em 25 MB com vídeo, mas cabe só o áudio
+        assert!(size_bitrates(&spec, 25_000_000, 3600.0, false).is_err());
+        let audio = size_bitrates(&spec, 25_000_000, 3600.0, true).unwrap();
+        assert_eq!(audio.video_kbps, None);
+        assert_eq!(audio.audio_kbps, 52);
+    }
+
+    #[test]
+    fn test_video_args() {
+        let spec = Preset::AndroidLow.spec();
+        let args: Vec<String> = video_args(&spec, None, None)
+            .into_iter()
+            .map(|arg| arg.into_string().unwrap())
+            .collect();
+        assert!(args.windows(2).any(|w| w == ["-profile:v", "baseline"]));
+        assert!(args.windows(2).any(|w| w == ["-crf", "28"]));
+        assert!(args.iter().any(|arg| arg.contains("min(ih,480)")));
+    }
+}
@@ -0,0 +1,584 @@
+//! Provisionamento gerenciado do yt-dlp.
+//!
+//! Em vez de instalar o yt-dlp no Python do sistema, o aplicativo mantém sua
+//! própria cópia no diretório de dados: via `pipx` (com `PIPX_HOME` privado),
+//! em um venv próprio ou como binário autônomo verificado por SHA-256.
+
+use crate::config::ProvisionConfig;
+use crate::error::DownloadError;
+use crate::paths;
+use crate::stats;
+use crate::tools::{self, Tool, ToolInfo};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// Quando instalar o yt-dlp automaticamente.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfirmPolicy {
+    /// Perguntar ao usuário (apenas em terminais interativos).
+    Ask,
+    /// Instalar sem perguntar.
+    Always,
+    /// Nunca instalar; apenas reportar a dependência ausente.
+    Never,
+}
+
+/// Forma de instalação do yt-dlp gerenciado.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallMethod {
+    Pipx,
+    Venv,
+    Binary,
+}
+
+impl fmt::Display for InstallMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            InstallMethod::Pipx => "pipx",
+            InstallMethod::Venv => "venv",
+            InstallMethod::Binary => crate::i18n::tr("provision.method_binary"),
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Instalação gerenciada registrada em `backend.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Installation {
+    pub method: InstallMethod,
+    pub path: PathBuf,
+    pub version: String,
+    /// Momento da instalação, em segundos desde a época Unix.
+    pub installed_at: u64,
+    /// Versão fixada pelo usuário; atualizações não saem dela.
+    #[serde(default)]
+    pub pinned: Option<String>,
+    /// Versão instalada antes da última atualização, para `--rollback`.
+    #[serde(default)]
+    pub previous: Option<String>,
+}
+
+impl Installation {
+    pub fn state_path() -> PathBuf {
+        paths::data_dir().join("backend.json")
+    }
+
+    /// Instalação registrada, se existir e o executável ainda estiver lá.
+    pub fn load() -> Option<Installation> {
+        let content = fs::read_to_string(Self::state_path()).ok()?;
+        let installation: Installation = serde_json::from_str(&content).ok()?;
+        installation.path.exists().then_some(installation)
+    }
+
+    fn save(&self) -> io::Result<()> {
+        fs::create_dir_all(paths::data_dir())?;
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(Self::state_path(), json)
+    }
+}
+
+/// Programa yt-dlp a usar: a instalação gerenciada, se houver, ou o do PATH.
+pub fn yt_dlp_program() -> PathBuf {
+    Installation::load()
+        .map(|installation| installation.path)
+        .unwrap_or_else(|| PathBuf::from("yt-dlp"))
+}
+
+/// Garante que o yt-dlp está disponível, instalando-o (com confirmação) se necessário.
+pub async fn ensure_yt_dlp(config: &ProvisionConfig) -> Result<ToolInfo, DownloadError> {
+    if let Some(info) = tools::detect(Tool::YtDlp) {
+        return Ok(info);
+    }
+
+    println!("{}", t!("provision.missing").red().bold());
+    if !confirm(config.confirm, &t!("provision.confirm")) {
+        return Err(DownloadError::MissingDependency("yt-dlp"));
+    }
+
+    let installation = install(config).await?;
+    println!(
+        "{}",
+        t!(
+            "provision.installed",
+            version = installation.version,
+            method = installation.method
+        )
+        .green()
+    );
+    Ok(ToolInfo {
+        tool: Tool::YtDlp,
+        program: installation.path,
+        version: installation.version,
+    })
+}
+
+/// Instala o yt-dlp tentando cada método configurado, em ordem.
+pub async fn install(config: &ProvisionConfig) -> Result<Installation, DownloadError> {
+    let mut failures = Vec::new();
+
+    for &method in &config.methods {
+        println!("{}", t!("provision.installing", method = method).yellow());
+        let result = match method {
+            InstallMethod::Pipx => install_pipx(None).await,
+            InstallMethod::Venv => install_venv(None).await,
+            InstallMethod::Binary => install_binary(config, None).await,
+        };
+
+        match result {
+            Ok(path) => {
+                let installation = Installation {
+                    method,
+                    version: query_version(&path).await?,
+                    path,
+                    installed_at: stats::now(),
+                    pinned: None,
+                    previous: None,
+                };
+                installation.save()?;
+                return Ok(installation);
+            }
+            Err(reason) => {
+                println!(
+                    "{}",
+                    t!("provision.method_failed", method = method, reason = reason).yellow()
+                );
+                failures.push(format!("{method}: {reason}"));
+            }
+        }
+    }
+
+    Err(DownloadError::InstallFailed(t!(
+        "provision.all_failed",
+        failures = failures.join("; ")
+    )))
+}
+
+/// O que o `update-backend` deve fazer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateAction {
+    /// Atualizar para a versão mais recente (recusa se houver versão fixada).
+    Latest,
+    /// Instalar e fixar uma versão específica.
+    Pin(String),
+    /// Remover a versão fixada e atualizar para a mais recente.
+    Unpin,
+    /// Voltar para a versão anterior à última atualização.
+    Rollback,
+}
+
+/// Versões antes e depois de uma atualização.
+#[derive(Debug, Clone)]
+pub struct UpdateOutcome {
+    pub from: String,
+    pub to: String,
+}
+
+/// Trechos de erro do yt-dlp que costumam ser resolvidos atualizando-o.
+const OUTDATED_BACKEND_PATTERNS: &[&str] = &[
+    "signature extraction failed",
+    "nsig extraction failed",
+    "unable to extract",
+    "precondition check failed",
+    "you are on the latest version",
+    "http error 403",
+];
+
+/// Indica se a saída de erro do yt-dlp sugere uma versão desatualizada.
+pub fn stderr_suggests_update(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    OUTDATED_BACKEND_PATTERNS
+        .iter()
+        .any(|pattern| stderr.contains(pattern))
+}
+
+/// Atualiza o yt-dlp: a cópia gerenciada pelo seu método de instalação, ou o
+/// yt-dlp do sistema via `yt-dlp -U`.
+pub async fn update_backend(
+    config: &ProvisionConfig,
+    action: &UpdateAction,
+) -> Result<UpdateOutcome, DownloadError> {
+    match Installation::load() {
+        Some(installation) => update_managed(config, installation, action).await,
+        None => update_unmanaged(action).await,
+    }
+}
+
+async fn update_managed(
+    config: &ProvisionConfig,
+    mut installation: Installation,
+    action: &UpdateAction,
+) -> Result<UpdateOutcome, DownloadError> {
+    let from = installation.version.clone();
+    let target = match action {
+        UpdateAction::Latest => {
+            if let Some(pinned) = &installation.pinned {
+                return Err(DownloadError::UpdateFailed(t!(
+                    "update.pinned",
+                    version = pinned
+                )));
+            }
+            None
+        }
+        UpdateAction::Pin(version) => Some(version.clone()),
+        UpdateAction::Unpin => None,
+        UpdateAction::Rollback => Some(
+            installation
+                .previous
+                .clone()
+                .ok_or_else(|| DownloadError::UpdateFailed(t!("update.no_previous")))?,
+        ),
+    };
+
+    println!(
+        "{}",
+        t!("update.updating", from = from, method = installation.method).yellow()
+    );
+    let result = match (installation.method, action) {
+        (InstallMethod::Binary, UpdateAction::Rollback) => {
+            restore_binary_backup(&installation.path)
+        }
+        (InstallMethod::Binary, _) => install_binary(config, target.as_deref()).await,
+        (InstallMethod::Pipx, _) => install_pipx(target.as_deref()).await,
+        (InstallMethod::Venv, _) => install_venv(target.as_deref()).await,
+    };
+    let path = result
+        .map_err(|reason| DownloadError::UpdateFailed(t!("update.failed", reason = reason)))?;
+
+    let to = query_version(&path).await?;
+    if to != from {
+        installation.previous = Some(from.clone());
+    }
+    match action {
+        UpdateAction::Pin(_) => installation.pinned = Some(to.clone()),
+        UpdateAction::Unpin => installation.pinned = None,
+        UpdateAction::Rollback if installation.pinned.is_some() => {
+            installation.pinned = Some(to.clone())
+        }
+        _ => {}
+    }
+    installation.path = path;
+    installation.version = to.clone();
+    installation.installed_at = stats::now();
+    installation.save()?;
+
+    Ok(UpdateOutcome { from, to })
+}
+
+async fn update_unmanaged(action: &UpdateAction) -> Result<UpdateOutcome, DownloadError> {
+    let current = tools::detect(Tool::YtDlp).ok_or(DownloadError::MissingDependency("yt-dlp"))?;
+
+    let mut cmd = Command::new(&current.program);
+    match action {
+        UpdateAction::Latest | UpdateAction::Unpin => cmd.arg("-U"),
+        UpdateAction::Pin(version) => cmd.args(["--update-to", version]),
+        UpdateAction::Rollback => {
+            return Err(DownloadError::UpdateFailed(t!("update.rollback_unmanaged")))
+        }
+    };
+    run(&mut cmd).await.map_err(|reason| {
+        DownloadError::UpdateFailed(t!("update.system_failed", reason = reason))
+    })?;
+
+    Ok(UpdateOutcome {
+        from: current.version,
+        to: query_version(&current.program).await?,
+    })
+}
+
+/// Troca o binário atual pelo backup guardado na última atualização.
+fn restore_binary_backup(path: &Path) -> Result<PathBuf, String> {
+    let backup = backup_path(path);
+    if !backup.exists() {
+        return Err(t!("update.no_backup"));
+    }
+    let swap = path.with_file_name("yt-dlp.swap");
+    fs::rename(path, &swap).map_err(|e| e.to_string())?;
+    fs::rename(&backup, path).map_err(|e| e.to_string())?;
+    fs::rename(&swap, &backup).map_err(|e| e.to_string())?;
+    Ok(path.to_path_buf())
+}
+
+/// Pergunta ao usuário, respeitando a política configurada e `YTD_ASSUME_YES`.
+pub fn confirm(policy: ConfirmPolicy, question: &str) -> bool {
+    if std::env::var("YTD_ASSUME_YES").is_ok_and(|v| !v.is_empty() && v != "0") {
+        return true;
+    }
+    match policy {
+        ConfirmPolicy::Always => true,
+        ConfirmPolicy::Never => false,
+        ConfirmPolicy::Ask => {
+            if !io::stdin().is_terminal() {
+                return false;
+            }
+            print!("{question} {} ", t!("provision.yes_no"));
+            io::stdout().flush().ok();
+            let mut answer = String::new();
+            if io::stdin().lock().read_line(&mut answer).is_err() {
+                return false;
+            }
+            matches!(
+                answer.trim().to_lowercase().as_str(),
+                "s" | "sim" | "y" | "yes"
+            )
+        }
+    }
+}
+
+/// Especificação do pacote para o pip: `yt-dlp` ou `yt-dlp==<versão>`.
+fn package_spec(version: Option<&str>) -> String {
+    match version {
+        Some(version) => format!("yt-dlp=={version}"),
+        None => "yt-dlp".to_string(),
+    }
+}
+
+async fn install_pipx(version: Option<&str>) -> Result<PathBuf, String> {
+    let home = paths::data_dir().join("pipx");
+    let bin_dir = home.join("bin");
+    run(Command::new("pipx")
+        .args(["install", "--force", &package_spec(version)])
+        .env("PIPX_HOME", &home)
+        .env("PIPX_BIN_DIR", &bin_dir))
+    .await?;
+
+    let exe = if cfg!(windows) {
+        "yt-dlp.exe"
+    } else {
+        "yt-dlp"
+    };
+    Ok(bin_dir.join(exe))
+}
+
+async fn install_venv(version: Option<&str>) -> Result<PathBuf, String> {
+    let venv = paths::data_dir().join("venv");
+    let python = if cfg!(windows) { "python" } else { "python3" };
+    run(Command::new(python).arg("-m").arg("venv").arg(&venv)).await?;
+    run(Command::new(paths::executable_in(&venv, "pip")).args([
+        "install",
+        "--upgrade",
+        &package_spec(version),
+    ]))
+    .await?;
+    Ok(paths::executable_in(&venv, "yt-dlp"))
+}
+
+async fn install_binary(
+    config: &ProvisionConfig,
+    version: Option<&str>,
+) -> Result<PathBuf, String> {
+    let (binary_url, checksums_url, pinned_sha256) = match version {
+        Some(version) => (
+            versioned_url(&config.binary_url, version)?,
+            match &config.checksums_url {
+                Some(url) => Some(versioned_url(url, version)?),
+                None => None,
+            },
+            // O SHA-256 fixo da configuração vale apenas para a versão "latest"
+            None,
+        ),
+        None => (
+            config.binary_url.clone(),
+            config.checksums_url.clone(),
+            config.binary_sha256.clone(),
+        ),
+    };
+    let bytes = fetch(&binary_url).await?;
+
+    let expected = match (pinned_sha256, &checksums_url) {
+        (Some(sha256), _) => sha256,
+        (None, Some(url)) => {
+            let sums = String::from_utf8_lossy(&fetch(url).await?).into_owned();
+            let asset = asset_name(&binary_url);
+            find_checksum(&sums, asset)
+                .ok_or_else(|| t!("provision.checksum_not_listed", asset = asset, url = url))?
+        }
+        (None, None) => return Err(t!("provision.no_sha256")),
+    };
+
+    let actual = sha256_hex(&bytes);
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        return Err(t!(
+            "provision.sha256_mismatch",
+            expected = expected,
+            actual = actual
+        ));
+    }
+
+    let exe = if cfg!(windows) {
+        "yt-dlp.exe"
+    } else {
+        "yt-dlp"
+    };
+    let path = paths::data_dir().join("bin").join(exe);
+    if path.exists() {
+        // Guardar o binário atual para permitir `--rollback`
+        fs::copy(&path, backup_path(&path)).map_err(|e| e.to_string())?;
+    }
+    write_executable(&path, &bytes).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    path.with_file_name("yt-dlp.previous")
+}
+
+/// Troca `releases/latest/download` por `releases/download/<versão>` na URL.
+fn versioned_url(url: &str, version: &str) -> Result<String, String> {
+    if !url.contains("/latest/download/") {
+        return Err(t!("provision.cannot_pin_url", url = url));
+    }
+    Ok(url.replace("/latest/download/", &format!("/download/{version}/")))
+}
+
+async fn fetch(url: &str) -> Result<Vec<u8>, String> {
+    let response = reqwest::get(url)
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| t!("provision.fetch_failed", url = url, error = e))?;
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| t!("provision.fetch_failed", url = url, error = e))?;
+    Ok(bytes.to_vec())
+}
+
+fn write_executable(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, bytes)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(())
+}
+
+/// Executa um comando de instalação, resumindo a falha em uma linha. Uma
+/// instalação pelo pip leva dezenas de segundos, e o processo assíncrono não
+/// prende a thread do runtime nesse meio tempo.
+async fn run(cmd: &mut Command) -> Result<(), String> {
+    let program = cmd.as_std().get_program().to_string_lossy().into_owned();
+    let output = cmd
+        .output()
+        .await
+        .map_err(|_| t!("provision.program_not_found", program = program))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let reason = stderr
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .map(str::trim)
+        .unwrap_or(crate::i18n::tr("provision.unknown_error"));
+    Err(t!(
+        "provision.program_failed",
+        program = program,
+        reason = reason
+    ))
+}
+
+async fn query_version(program: &Path) -> Result<String, DownloadError> {
+    let output = Command::new(program).arg("--version").output().await?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Nome do arquivo (último segmento) de uma URL de download.
+fn asset_name(url: &str) -> &str {
+    url.rsplit('/').next().unwrap_or(url)
+}
+
+/// Procura o hash de um arquivo em uma lista no formato do `sha256sum`.
+fn find_checksum(sums: &str, asset: &str) -> Option<String> {
+    sums.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let hash = parts.next()?;
+        let name = parts.next()?.trim_start_matches('*');
+        (name == asset).then(|| hash.to_string())
+    })
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_checksum() {
+        let sums = "\
+aaaa  yt-dlp.exe
+bbbb  yt-dlp
+cccc *yt-dlp_macos
+";
+        assert_eq!(find_checksum(sums, "yt-dlp"), Some("bbbb".to_string()));
+        assert_eq!(
+            find_checksum(sums, "yt-dlp_macos"),
+            Some("cccc".to_string())
+        );
+        assert_eq!(find_checksum(sums, "yt-dlp_linux"), None);
+    }
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_asset_name() {
+        assert_eq!(
+            asset_name("https://example.com/releases/latest/download/yt-dlp_macos"),
+            "yt-dlp_macos"
+        );
+    }
+
+    #[test]
+    fn test_versioned_url() {
+        assert_eq!(
+            versioned_url(
+                "https://github.com/yt-dlp/yt-dlp/releases/latest/download/yt-dlp",
+                "2024.05.27"
+            )
+            .unwrap(),
+            "https://github.com/yt-dlp/yt-dlp/releases/download/2024.05.27/yt-dlp"
+        );
+        assert!(versioned_url("https://mirror.example/yt-dlp", "2024.05.27").is_err());
+    }
+
+    #[test]
+    fn test_stderr_suggests_update() {
+        assert!(stderr_suggests_update(
+            "WARNING: [youtube] abc: nsig extraction failed: You may experience throttling"
+        ));
+        assert!(stderr_suggests_update(
+            "ERROR: [youtube] abc: Signature extraction failed: Some formats may be missing"
+        ));
+        assert!(!stderr_suggests_update(
+            "ERROR: [youtube] abc: Private video. Sign in if you've been granted access"
+        ));
+    }
+
+    #[test]
+    fn test_confirm_policies() {
+        assert!(confirm(ConfirmPolicy::Always, "?"));
+        if std::env::var_os("YTD_ASSUME_YES").is_none() {
+            assert!(!confirm(ConfirmPolicy::Never, "?"));
+        }
+    }
+}
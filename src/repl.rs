@@ -0,0 +1,338 @@
+//! Auxiliar do prompt `ytd>`: completa comandos, flags, arquivos e URLs já
+//! usadas, sugere a sintaxe do comando e destaca URLs inválidas.
+
+use colored::*;
+use rustyline::completion::{Completer, FilenameCompleter, Pair};
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::SearchDirection;
+use rustyline::validate::Validator;
+use rustyline::{Context, Helper};
+use std::borrow::Cow;
+use youtube_video_downloader::{i18n, is_youtube_url};
+
+/// Comando do modo interativo, com suas flags e a chave da dica de sintaxe.
+struct CommandSpec {
+    name: &'static str,
+    flags: &'static [&'static str],
+    syntax: Option<&'static str>,
+}
+
+const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "download",
+        flags: &["-a", "--audio", "-o"],
+        syntax: Some("repl.syntax.download"),
+    },
+    CommandSpec {
+        name: "search",
+        flags: &["-n", "--results"],
+        syntax: Some("repl.syntax.search"),
+    },
+    CommandSpec {
+        name: "get",
+        flags: &["-a", "--audio"],
+        syntax: Some("repl.syntax.get"),
+    },
+    CommandSpec {
+        name: "throttle",
+        flags: &[],
+        syntax: Some("repl.syntax.throttle"),
+    },
+    CommandSpec {
+        name: "resume",
+        flags: &["--discard"],
+        syntax: Some("repl.syntax.resume"),
+    },
+    CommandSpec {
+        name: "stats",
+        flags: &["--today", "--week", "--all"],
+        syntax: Some("repl.syntax.stats"),
+    },
+    CommandSpec {
+        name: "update-backend",
+        flags: &["--pin", "--unpin", "--rollback"],
+        syntax: Some("repl.syntax.update_backend"),
+    },
+    CommandSpec {
+        name: "doctor",
+        flags: &[],
+        syntax: None,
+    },
+    CommandSpec {
+        name: "help",
+        flags: &[],
+        syntax: None,
+    },
+    CommandSpec {
+        name: "clear",
+        flags: &[],
+        syntax: None,
+    },
+    CommandSpec {
+        name: "demo",
+        flags: &[],
+        syntax: None,
+    },
+    CommandSpec {
+        name: "exit",
+        flags: &[],
+        syntax: None,
+    },
+    CommandSpec {
+        name: "quit",
+        flags: &[],
+        syntax: None,
+    },
+];
+
+fn command(name: &str) -> Option<&'static CommandSpec> {
+    COMMANDS.iter().find(|c| c.name == name)
+}
+
+pub struct ReplHelper {
+    files: FilenameCompleter,
+}
+
+impl ReplHelper {
+    pub fn new() -> Self {
+        ReplHelper {
+            files: FilenameCompleter::new(),
+        }
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let before = &line[..pos];
+        let start = before
+            .rfind(char::is_whitespace)
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let word = &before[start..];
+        let previous: Vec<&str> = before[..start].split_whitespace().collect();
+
+        let Some(&name) = previous.first() else {
+            // Primeira palavra: um comando ou uma URL colada direto
+            let mut candidates = words(COMMANDS.iter().map(|c| c.name), word);
+            candidates.extend(words(history_urls(ctx), word));
+            return Ok((start, candidates));
+        };
+
+        if previous.last() == Some(&"-o") || looks_like_path(word) {
+            return self.files.complete_path(line, pos);
+        }
+        let spec = command(name);
+        if word.starts_with('-') {
+            let flags = spec.map(|c| c.flags).unwrap_or_default();
+            return Ok((start, words(flags.iter().copied(), word)));
+        }
+        if name == "download" {
+            return Ok((start, words(history_urls(ctx), word)));
+        }
+        Ok((start, Vec::new()))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+
+    fn hint(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> Option<String> {
+        if pos < line.len() || line.is_empty() {
+            return None;
+        }
+        // "down" → "load", ou "download " → "<URL> [-a] [-o nome]"
+        if let Some(name) = line.strip_suffix(' ') {
+            let syntax = command(name)?.syntax?;
+            return Some(i18n::tr(syntax).to_string());
+        }
+        if line.contains(char::is_whitespace) {
+            return None;
+        }
+        let mut matches = COMMANDS.iter().filter(|c| c.name.starts_with(line));
+        match (matches.next(), matches.next()) {
+            (Some(spec), None) if spec.name != line => Some(spec.name[line.len()..].to_string()),
+            _ => None,
+        }
+    }
+}
+
+impl Highlighter for ReplHelper {
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+        if !line.contains("http") {
+            return Cow::Borrowed(line);
+        }
+        let mut highlighted = String::with_capacity(line.len() * 2);
+        let mut rest = line;
+        while !rest.is_empty() {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let (token, tail) = rest.split_at(end);
+            highlighted.push_str(&highlight_token(token));
+            let spaces = tail.len() - tail.trim_start().len();
+            highlighted.push_str(&tail[..spaces]);
+            rest = &tail[spaces..];
+        }
+        Cow::Owned(highlighted)
+    }
+
+    fn highlight_hint<'h>(&self, hint: &'h str) -> Cow<'h, str> {
+        Cow::Owned(hint.dimmed().to_string())
+    }
+
+    fn highlight_char(&self, line: &str, _pos: usize, _forced: bool) -> bool {
+        line.contains("http")
+    }
+}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+/// URLs em verde quando são do YouTube e em vermelho caso contrário.
+fn highlight_token(token: &str) -> String {
+    if !token.starts_with("http") {
+        token.to_string()
+    } else if is_youtube_url(token) {
+        token.green().to_string()
+    } else {
+        token.red().to_string()
+    }
+}
+
+fn looks_like_path(word: &str) -> bool {
+    !word.starts_with("http") && (word.starts_with(['.', '/', '~']) || word.contains('/'))
+}
+
+/// URLs do YouTube já usadas, da mais recente para a mais antiga.
+fn history_urls(ctx: &Context<'_>) -> Vec<String> {
+    let history = ctx.history();
+    let mut urls: Vec<String> = Vec::new();
+    for index in (0..history.len()).rev() {
+        let Ok(Some(result)) = history.get(index, SearchDirection::Reverse) else {
+            continue;
+        };
+        for token in result.entry.split_whitespace() {
+            if is_youtube_url(token) && !urls.iter().any(|url| url == token) {
+                urls.push(token.to_string());
+            }
+        }
+    }
+    urls
+}
+
+/// Candidatos que começam com `prefix`, seguidos de espaço ao serem inseridos.
+fn words<S: AsRef<str>>(options: impl IntoIterator<Item = S>, prefix: &str) -> Vec<Pair> {
+    options
+        .into_iter()
+        .filter(|option| option.as_ref().starts_with(prefix))
+        .map(|option| Pair {
+            display: option.as_ref().to_string(),
+            replacement: format!("{} ", option.as_ref()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustyline::history::{DefaultHistory, History};
+
+    fn complete(line: &str, history: &DefaultHistory) -> (usize, Vec<String>) {
+        let ctx = Context::new(history);
+        let (start, pairs) = ReplHelper::new().complete(line, line.len(), &ctx).unwrap();
+        (start, pairs.into_iter().map(|p| p.display).collect())
+    }
+
+    fn hint(line: &str) -> Option<String> {
+        let history = DefaultHistory::new();
+        ReplHelper::new().hint(line, line.len(), &Context::new(&history))
+    }
+
+    #[test]
+    fn test_completes_commands_and_flags() {
+        let history = DefaultHistory::new();
+        assert_eq!(
+            complete("do", &history),
+            (0, vec!["download".into(), "doctor".into()])
+        );
+        assert_eq!(complete("st", &history), (0, vec!["stats".into()]));
+        assert_eq!(
+            complete("stats --", &history),
+            (6, vec!["--today".into(), "--week".into(), "--all".into()])
+        );
+        assert_eq!(
+            complete("update-backend --p", &history),
+            (15, vec!["--pin".into()])
+        );
+        assert_eq!(complete("help x", &history).1, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_completes_urls_from_history() {
+        let mut history = DefaultHistory::new();
+        history.add("download https://youtu.be/antigo -a").unwrap();
+        history
+            .add("https://www.youtube.com/watch?v=recente")
+            .unwrap();
+        history.add("download https://youtu.be/antigo").unwrap();
+        history.add("download https://vimeo.com/123").unwrap();
+
+        assert_eq!(
+            complete("download ", &history).1,
+            vec![
+                "https://youtu.be/antigo".to_string(),
+                "https://www.youtube.com/watch?v=recente".to_string(),
+            ]
+        );
+        assert_eq!(
+            complete("https://www", &history).1,
+            vec!["https://www.youtube.com/watch?v=recente".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_completes_file_names_after_output_flag() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("lista.txt"), "").unwrap();
+        let prefix = format!("{}/li", dir.path().display());
+        let history = DefaultHistory::new();
+
+        let (_, names) = complete(
+            &format!("download https://youtu.be/abc -o {prefix}"),
+            &history,
+        );
+        assert_eq!(names, vec!["lista.txt".to_string()]);
+    }
+
+    #[test]
+    fn test_hints() {
+        assert_eq!(hint("down").as_deref(), Some("load"));
+        assert_eq!(hint("d"), None);
+        assert_eq!(
+            hint("download ").as_deref(),
+            Some(i18n::tr("repl.syntax.download"))
+        );
+        assert_eq!(hint("clear "), None);
+        assert_eq!(hint("download https://youtu.be/abc"), None);
+    }
+
+    #[test]
+    fn test_highlights_invalid_urls() {
+        let helper = ReplHelper::new();
+        let line = "download https://vimeo.com/123  -a";
+        assert_eq!(
+            helper.highlight(line, 0),
+            format!("download {}  -a", "https://vimeo.com/123".red())
+        );
+        let line = "https://youtu.be/abc";
+        assert_eq!(helper.highlight(line, 0), line.green().to_string());
+        assert_eq!(helper.highlight("stats --week", 0), "stats --week");
+    }
+}
@@ -0,0 +1,191 @@
+//! Novas tentativas para falhas passageiras do yt-dlp.
+//!
+//! Erros de rede, respostas 5xx e bloqueios temporários (403, 429) valem uma
+//! nova tentativa após uma espera exponencial com variação aleatória; vídeos
+//! privados, removidos ou URLs inválidas falham de imediato.
+
+use crate::config::RetryConfig;
+use crate::error::DownloadError;
+use rand::Rng;
+use std::time::Duration;
+
+/// Se vale a pena tentar de novo após um erro.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    /// Falha passageira: rede, servidor ou limite de requisições.
+    Retryable,
+    /// Tentar de novo daria o mesmo resultado.
+    Permanent,
+}
+
+/// Trechos de erro do yt-dlp que não mudam com novas tentativas.
+const PERMANENT_PATTERNS: &[&str] = &[
+    "private video",
+    "video unavailable",
+    "has been removed",
+    "account associated with this video has been terminated",
+    "not available in your country",
+    "sign in to confirm your age",
+    "members-only",
+    "unsupported url",
+    "is not a valid url",
+    "http error 404",
+    "http error 410",
+    // Extrator desatualizado: só uma atualização do yt-dlp resolve
+    "signature extraction failed",
+    "unable to extract",
+    "precondition check failed",
+];
+
+/// Trechos de erro que indicam uma falha passageira.
+const RETRYABLE_PATTERNS: &[&str] = &[
+    "http error 403",
+    "http error 429",
+    "http error 5",
+    "too many requests",
+    "connection reset",
+    "connection refused",
+    "connection aborted",
+    "remote end closed connection",
+    "timed out",
+    "temporary failure in name resolution",
+    "network is unreachable",
+    "incompleteread",
+    "broken pipe",
+    "unable to download",
+];
+
+/// Classifica um erro de download.
+pub fn classify(error: &DownloadError) -> ErrorClass {
+    match error {
+        DownloadError::Backend { stderr, .. } => classify_stderr(stderr),
+        DownloadError::RetriesExhausted { last, .. } => classify(last),
+        // Um arquivo truncado costuma sair inteiro numa nova tentativa
+        DownloadError::Verification(_) => ErrorClass::Retryable,
+        _ => ErrorClass::Permanent,
+    }
+}
+
+/// Olha apenas as linhas `ERROR:`, já que avisos como "You may experience
+/// throttling" aparecem também em downloads que terminam bem.
+fn classify_stderr(stderr: &str) -> ErrorClass {
+    let errors = error_lines(stderr).to_lowercase();
+    if PERMANENT_PATTERNS.iter().any(|p| errors.contains(p)) {
+        return ErrorClass::Permanent;
+    }
+    if RETRYABLE_PATTERNS.iter().any(|p| errors.contains(p)) {
+        ErrorClass::Retryable
+    } else {
+        ErrorClass::Permanent
+    }
+}
+
+fn error_lines(stderr: &str) -> String {
+    let lines: Vec<&str> = stderr
+        .lines()
+        .filter(|line| line.starts_with("ERROR:"))
+        .collect();
+    if lines.is_empty() {
+        stderr.to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+/// Resumo de uma linha do erro, para o aviso de nova tentativa.
+pub fn summary(error: &DownloadError) -> String {
+    match error {
+        DownloadError::Backend { stderr, .. } => stderr
+            .lines()
+            .rev()
+            .find(|line| line.starts_with("ERROR:"))
+            .or_else(|| stderr.lines().rev().find(|line| !line.trim().is_empty()))
+            .map(str::to_string)
+            .unwrap_or_else(|| error.to_string()),
+        other => other.to_string(),
+    }
+}
+
+/// Espera antes da tentativa `retry + 1`: `initial_delay_ms` dobrando a cada
+/// nova tentativa até `max_delay_ms`, sorteada entre metade e o valor cheio.
+pub fn backoff(config: &RetryConfig, retry: u32, rng: &mut impl Rng) -> Duration {
+    let factor = 2u64.saturating_pow(retry.saturating_sub(1));
+    let delay = config
+        .initial_delay_ms
+        .saturating_mul(factor)
+        .min(config.max_delay_ms);
+    let half = delay / 2;
+    Duration::from_millis(half + rng.gen_range(0..=delay - half))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn backend(stderr: &str) -> DownloadError {
+        DownloadError::Backend {
+            status: Some(1),
+            stderr: stderr.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        for stderr in [
+            "ERROR: unable to download video data: HTTP Error 403: Forbidden",
+            "ERROR: [youtube] abc: Unable to download API page: HTTP Error 503: Service Unavailable",
+            "ERROR: [Errno 104] Connection reset by peer",
+            "ERROR: HTTP Error 429: Too Many Requests",
+        ] {
+            assert_eq!(classify(&backend(stderr)), ErrorClass::Retryable, "{stderr}");
+        }
+        for stderr in [
+            "ERROR: [youtube] abc: Private video. Sign in if you've been granted access",
+            "ERROR: [youtube] abc: Video unavailable. This video has been removed by the uploader",
+            "ERROR: [youtube] abc: Unable to download webpage: HTTP Error 404: Not Found",
+            "ERROR: [youtube] abc: Signature extraction failed",
+            "ERROR: algo inesperado",
+        ] {
+            assert_eq!(
+                classify(&backend(stderr)),
+                ErrorClass::Permanent,
+                "{stderr}"
+            );
+        }
+        // Avisos não decidem a classificação
+        let stderr = "WARNING: nsig extraction failed: You may experience throttling\n\
+                      ERROR: Read timed out";
+        assert_eq!(classify(&backend(stderr)), ErrorClass::Retryable);
+
+        assert_eq!(
+            classify(&DownloadError::InvalidUrl("x".into())),
+            ErrorClass::Permanent
+        );
+        assert_eq!(classify(&DownloadError::Cancelled), ErrorClass::Permanent);
+    }
+
+    #[test]
+    fn test_backoff_grows_with_jitter() {
+        let config = RetryConfig {
+            attempts: 5,
+            initial_delay_ms: 1000,
+            max_delay_ms: 5000,
+        };
+        let mut rng = StdRng::seed_from_u64(7);
+        for (retry, full) in [(1, 1000), (2, 2000), (3, 4000), (4, 5000), (40, 5000)] {
+            let delay = backoff(&config, retry, &mut rng).as_millis() as u64;
+            assert!(
+                (full / 2..=full).contains(&delay),
+                "tentativa {retry}: {delay}ms"
+            );
+        }
+    }
+
+    #[test]
+    fn test_summary_uses_last_error_line() {
+        let error = backend("WARNING: lento\nERROR: primeiro\nERROR: HTTP Error 503\n");
+        assert_eq!(summary(&error), "ERROR: HTTP Error 503");
+    }
+}
@@ -0,0 +1,378 @@
+//! Busca no YouTube pelo yt-dlp (`ytsearchN:termos`), listagem de canais e
+//! playlists e seleção de resultados como `3` ou `1,4-6`.
+
+use crate::access::{self, AccessOptions};
+use crate::archive;
+use crate::config::Config;
+use crate::error::DownloadError;
+use crate::provision;
+use colored::*;
+use regex::Regex;
+use serde::Deserialize;
+use std::fmt;
+use std::sync::OnceLock;
+use tokio::process::Command;
+
+/// Quantidade de resultados quando não informada.
+pub const DEFAULT_LIMIT: usize = 10;
+
+/// Um vídeo encontrado na busca ou listado de uma playlist ou canal.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchResult {
+    pub id: String,
+    pub title: String,
+    pub channel: Option<String>,
+    pub duration_secs: Option<f64>,
+    pub url: String,
+    /// `AAAAMMDD`, como o yt-dlp informa.
+    pub upload_date: Option<String>,
+    /// `not_live`, `is_live`, `was_live`, `is_upcoming` ou `post_live`.
+    pub live_status: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// Início previsto de uma live agendada, em segundos desde a época Unix.
+    pub release_timestamp: Option<i64>,
+}
+
+/// Entrada do `--flat-playlist --dump-json`; o yt-dlp omite ou anula campos
+/// que não conhece.
+#[derive(Deserialize)]
+struct Entry {
+    id: String,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    channel: Option<String>,
+    #[serde(default)]
+    uploader: Option<String>,
+    #[serde(default)]
+    duration: Option<f64>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    webpage_url: Option<String>,
+    #[serde(default)]
+    upload_date: Option<String>,
+    #[serde(default)]
+    live_status: Option<String>,
+    #[serde(default)]
+    width: Option<u32>,
+    #[serde(default)]
+    height: Option<u32>,
+    #[serde(default)]
+    release_timestamp: Option<i64>,
+}
+
+/// Busca `query` no YouTube, devolvendo até `limit` vídeos.
+pub async fn search(query: &str, limit: usize) -> Result<Vec<SearchResult>, DownloadError> {
+    flat_list(search_args(query, limit), &AccessOptions::default()).await
+}
+
+/// Executa o yt-dlp com `--flat-playlist --dump-json`, sem baixar nada, e
+/// lista os vídeos encontrados.
+pub(crate) async fn flat_list(
+    args: Vec<String>,
+    explicit: &AccessOptions,
+) -> Result<Vec<SearchResult>, DownloadError> {
+    let config = Config::load();
+    let access = access::resolve(&config, explicit)?;
+    let yt_dlp = provision::ensure_yt_dlp(&config.provision).await?;
+    let output = Command::new(&yt_dlp.program)
+        .args(access.ytdlp_args())
+        .args(["--flat-playlist", "--dump-json"])
+        .args(args)
+        .output()
+        .await?;
+    if !output.status.success() {
+        return Err(DownloadError::Backend {
+            status: output.status.code(),
+            stderr: access::redact(&String::from_utf8_lossy(&output.stderr)).into_owned(),
+        });
+    }
+    Ok(parse_results(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Metadados completos de um vídeo, sem baixá-lo.
+pub async fn video_info(
+    url: &str,
+    explicit: &AccessOptions,
+) -> Result<SearchResult, DownloadError> {
+    let args = vec!["--no-playlist".to_string(), url.to_string()];
+    let results = flat_list(args, explicit).await?;
+    let id = archive::video_id(url);
+    results
+        .iter()
+        .find(|result| Some(&result.id) == id.as_ref())
+        .or(results.first())
+        .cloned()
+        .ok_or_else(|| DownloadError::Backend {
+            status: None,
+            stderr: t!("search.no_metadata"),
+        })
+}
+
+/// Metadados completos de um vídeo já listado, para filtros que dependem de
+/// campos ausentes na listagem. O link original é mantido.
+pub(crate) async fn details(
+    video: &SearchResult,
+    explicit: &AccessOptions,
+) -> Result<SearchResult, DownloadError> {
+    let details = video_info(&video.url, explicit).await?;
+    Ok(SearchResult {
+        url: video.url.clone(),
+        ..details
+    })
+}
+
+/// Canais (`@nome`, `channel/`, `c/`, `user/`) e playlists do YouTube.
+pub fn is_collection_url(url: &str) -> bool {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN
+        .get_or_init(|| {
+            Regex::new(
+                r"^https?://(www\.|m\.)?youtube\.com/(@[\w.-]+|channel/[\w-]+|c/[\w.-]+|user/[\w.-]+|playlist\?list=[\w-]+)",
+            )
+            .unwrap()
+        })
+        .is_match(url)
+}
+
+/// Numa URL de canal, a aba de vídeos; sem ela, o yt-dlp lista as abas
+/// (vídeos, Shorts, lives) em vez dos vídeos.
+fn listing_url(url: &str) -> String {
+    if url.contains("playlist?list=") {
+        return url.to_string();
+    }
+    let url = url.trim_end_matches('/');
+    let has_tab = ["/videos", "/shorts", "/streams", "/featured"]
+        .iter()
+        .any(|tab| url.ends_with(tab));
+    if has_tab {
+        url.to_string()
+    } else {
+        format!("{url}/videos")
+    }
+}
+
+/// Vídeos de um canal ou playlist, do mais recente ao mais antigo nos canais;
+/// `limit` corta a lista no yt-dlp.
+pub async fn list_collection(
+    url: &str,
+    limit: Option<usize>,
+    explicit: &AccessOptions,
+) -> Result<Vec<SearchResult>, DownloadError> {
+    let mut args = Vec::new();
+    if let Some(limit) = limit {
+        args.extend(["--playlist-end".to_string(), limit.to_string()]);
+    }
+    args.push(listing_url(url));
+    flat_list(args, explicit).await
+}
+
+fn search_args(query: &str, limit: usize) -> Vec<String> {
+    vec![format!("ytsearch{limit}:{query}")]
+}
+
+/// Uma entrada JSON por linha; linhas que não são vídeos são ignoradas.
+fn parse_results(stdout: &str) -> Vec<SearchResult> {
+    stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Entry>(line).ok())
+        .map(|entry| SearchResult {
+            // Nos metadados completos, `url` pode ser o link direto do arquivo
+            url: entry
+                .webpage_url
+                .or(entry.url)
+                .filter(|url| url.starts_with("http"))
+                .unwrap_or_else(|| format!("https://www.youtube.com/watch?v={}", entry.id)),
+            title: entry.title.unwrap_or_else(|| entry.id.clone()),
+            channel: entry.channel.or(entry.uploader),
+            duration_secs: entry.duration,
+            upload_date: entry.upload_date,
+            live_status: entry.live_status,
+            width: entry.width,
+            height: entry.height,
+            release_timestamp: entry.release_timestamp,
+            id: entry.id,
+        })
+        .collect()
+}
+
+/// Lista numerada com título, canal e duração.
+pub fn print_results(query: &str, results: &[SearchResult]) {
+    if results.is_empty() {
+        println!("{}", t!("search.no_results", query = query).yellow());
+        return;
+    }
+    println!(
+        "{}",
+        t!("search.title", query = query, count = results.len())
+            .cyan()
+            .bold()
+    );
+    for (index, result) in results.iter().enumerate() {
+        let duration = result
+            .duration_secs
+            .map(format_clock)
+            .unwrap_or_else(|| "--:--".to_string());
+        println!(
+            "{:>4}. {}  {}  {}",
+            (index + 1).to_string().green().bold(),
+            result.title,
+            result.channel.as_deref().unwrap_or("-").dimmed(),
+            duration.cyan()
+        );
+    }
+    println!("{}", t!("search.hint").yellow());
+}
+
+/// `3:07` ou `1:02:03`.
+pub fn format_clock(secs: f64) -> String {
+    let secs = secs.round() as u64;
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+/// Seleção inválida de resultados.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelectionError {
+    /// Trecho que não é um número nem um intervalo `a-b`.
+    Invalid(String),
+    /// Número fora da lista de resultados.
+    OutOfRange { number: usize, count: usize },
+}
+
+impl fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SelectionError::Invalid(part) => {
+                write!(f, "{}", t!("search.invalid_selection", part = part))
+            }
+            SelectionError::OutOfRange { number, count } => write!(
+                f,
+                "{}",
+                t!("search.out_of_range", number = number, count = count)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SelectionError {}
+
+/// Interpreta `1,4-6` como os números 1, 4, 5 e 6, na ordem em que aparecem
+/// e sem repetições.
+pub fn parse_selection(spec: &str) -> Result<Vec<usize>, SelectionError> {
+    let mut numbers = Vec::new();
+    for part in spec.split(',').map(str::trim) {
+        let invalid = || SelectionError::Invalid(part.to_string());
+        let number = |text: &str| text.trim().parse::<usize>().ok().filter(|&n| n > 0);
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (number(start), number(end)),
+            None => (number(part), number(part)),
+        };
+        let (Some(start), Some(end)) = (start, end) else {
+            return Err(invalid());
+        };
+        if start > end {
+            return Err(invalid());
+        }
+        for n in start..=end {
+            if !numbers.contains(&n) {
+                numbers.push(n);
+            }
+        }
+    }
+    Ok(numbers)
+}
+
+/// Resultados escolhidos, conferindo que todos os números existem.
+pub fn select<'a>(
+    results: &'a [SearchResult],
+    numbers: &[usize],
+) -> Result<Vec<&'a SearchResult>, SelectionError> {
+    numbers
+        .iter()
+        .map(|&number| {
+            results.get(number - 1).ok_or(SelectionError::OutOfRange {
+                number,
+                count: results.len(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_selection() {
+        assert_eq!(parse_selection("3"), Ok(vec![3]));
+        assert_eq!(parse_selection("1,4-6"), Ok(vec![1, 4, 5, 6]));
+        assert_eq!(parse_selection(" 2 , 1-2 "), Ok(vec![2, 1]));
+        for spec in ["", "0", "a", "3-1", "1,", "-2", "1-"] {
+            assert!(
+                matches!(parse_selection(spec), Err(SelectionError::Invalid(_))),
+                "deveria falhar: {spec:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_results_and_select() {
+        let stdout = concat!(
+            r#"{"id": "abc", "title": "Primeiro", "channel": "Canal", "duration": 187.0, "url": "https://www.youtube.com/watch?v=abc"}"#,
+            "\n",
+            r#"{"id": "def", "title": null, "uploader": "Outro", "duration": null}"#,
+            "\nWARNING: linha que não é JSON\n",
+        );
+        let results = parse_results(stdout);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].channel.as_deref(), Some("Canal"));
+        assert_eq!(results[0].duration_secs, Some(187.0));
+        assert_eq!(results[1].title, "def");
+        assert_eq!(results[1].channel.as_deref(), Some("Outro"));
+        assert_eq!(results[1].url, "https://www.youtube.com/watch?v=def");
+
+        let picked = select(&results, &[2, 1]).unwrap();
+        assert_eq!(picked[0].id, "def");
+        assert_eq!(
+            select(&results, &[3]),
+            Err(SelectionError::OutOfRange {
+                number: 3,
+                count: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_collection_url_and_listing_url() {
+        assert!(is_collection_url("https://www.youtube.com/@canal"));
+        assert!(is_collection_url(
+            "https://www.youtube.com/playlist?list=PLabc-123"
+        ));
+        assert!(is_collection_url("https://youtube.com/channel/UCabc"));
+        assert!(!is_collection_url(
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
+        ));
+        assert_eq!(
+            listing_url("https://www.youtube.com/@canal/"),
+            "https://www.youtube.com/@canal/videos"
+        );
+        assert_eq!(
+            listing_url("https://www.youtube.com/@canal/streams"),
+            "https://www.youtube.com/@canal/streams"
+        );
+    }
+
+    #[test]
+    fn test_format_clock() {
+        assert_eq!(format_clock(187.0), "3:07");
+        assert_eq!(format_clock(3723.4), "1:02:03");
+        assert_eq!(search_args("lofi", 5).last().unwrap(), "ytsearch5:lofi");
+    }
+}
@@ -0,0 +1,722 @@
+//! API HTTP local (`serve`) para disparar downloads de outras ferramentas.
+//!
+//! - `POST /jobs` inicia um download e devolve o job criado;
+//! - `GET /jobs` e `GET /jobs/{id}` mostram o estado dos jobs;
+//! - `DELETE /jobs/{id}` cancela um job em andamento ou esquece um que já
+//!   terminou;
+//! - `GET /events` e `GET /jobs/{id}/events` transmitem o progresso como
+//!   server-sent events (`job` com o job atualizado, `status` com as
+//!   mensagens do download);
+//! - `POST /pair` e `POST /enqueue` atendem extensões e bookmarklets, com
+//!   CORS para as origens de `server.allowed_origins` (veja [`pairing`]).
+//!
+//! Os downloads passam pelo mesmo [`download_with`] da linha de comando, com
+//! novas tentativas, diário e limite de banda. Com um token configurado,
+//! toda requisição precisa de `Authorization: Bearer <token>`; como o
+//! `EventSource` dos navegadores não envia cabeçalhos, `?token=` também vale.
+
+use crate::access;
+use crate::config::Config;
+use crate::download::{download_with, is_youtube_url, DownloadOptions, Downloaded, TITLE_OUTPUT};
+use crate::error::DownloadError;
+use crate::job::{JobControl, JobEvent, Progress};
+use crate::pairing;
+use crate::stats;
+use axum::body::Body;
+use axum::extract::rejection::{JsonRejection, QueryRejection};
+use axum::extract::{Path as UrlPath, Query, State};
+use axum::http::{header, HeaderMap, HeaderName, HeaderValue, Method, Request, StatusCode, Uri};
+use axum::middleware::{self, Next};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::future::Future;
+use std::io;
+use std::net::TcpListener;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+
+/// Eventos guardados para clientes lentos antes de eles perderem mensagens.
+const EVENT_BUFFER: usize = 256;
+/// Códigos de pareamento errados aceitos antes de recusar novas tentativas.
+const MAX_PAIRING_ATTEMPTS: u32 = 5;
+
+/// Como o servidor grava os downloads e protege a API.
+#[derive(Debug, Clone, Default)]
+pub struct ServerOptions {
+    /// Diretório onde os arquivos são gravados.
+    pub dir: PathBuf,
+    /// Token exigido nas requisições; `None` deixa a API aberta.
+    pub token: Option<String>,
+    /// Origens de navegador aceitas em `/enqueue` e `/pair`.
+    pub allowed_origins: Vec<String>,
+    /// Código que um navegador troca por um token em `/pair`; `None`
+    /// desativa o pareamento.
+    pub pairing_code: Option<String>,
+}
+
+/// Situação de um job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Running,
+    Done,
+    Failed,
+    Cancelled,
+}
+
+/// Um job como a API o mostra.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobView {
+    pub id: u64,
+    pub url: String,
+    pub audio_only: bool,
+    pub status: JobStatus,
+    pub progress: Progress,
+    /// Arquivo final, quando o download termina.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Criação do job, em segundos desde a época Unix.
+    pub created_at: u64,
+}
+
+/// Corpo do `POST /jobs`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NewJob {
+    url: String,
+    /// Nome do arquivo, sem extensão; o padrão é o título do vídeo.
+    #[serde(default)]
+    output: Option<String>,
+    #[serde(default)]
+    audio_only: bool,
+    /// Formato do áudio com `audio_only`; `original` não converte.
+    #[serde(default)]
+    audio_format: Option<String>,
+    /// Perfil de acesso da configuração.
+    #[serde(default)]
+    profile: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+enum ServerEvent {
+    Job(JobView),
+    Status { id: u64, message: String },
+}
+
+impl ServerEvent {
+    fn job_id(&self) -> u64 {
+        match self {
+            ServerEvent::Job(view) => view.id,
+            ServerEvent::Status { id, .. } => *id,
+        }
+    }
+
+    fn to_sse(&self) -> Event {
+        let (name, data) = match self {
+            ServerEvent::Job(view) => ("job", serde_json::to_string(view)),
+            ServerEvent::Status { id, message } => (
+                "status",
+                serde_json::to_string(&serde_json::json!({ "id": id, "message": message })),
+            ),
+        };
+        Event::default().event(name).data(data.unwrap_or_default())
+    }
+}
+
+struct Job {
+    view: JobView,
+    control: JobControl,
+}
+
+struct Shared {
+    options: ServerOptions,
+    jobs: Mutex<BTreeMap<u64, Job>>,
+    next_id: AtomicU64,
+    events: broadcast::Sender<ServerEvent>,
+    /// Passa a `true` quando o servidor está encerrando, fechando os streams.
+    stopping: watch::Receiver<bool>,
+    tasks: Mutex<Vec<JoinHandle<()>>>,
+    pairing_failures: AtomicU32,
+}
+
+#[derive(Clone)]
+struct AppState(Arc<Shared>);
+
+impl AppState {
+    fn jobs(&self) -> std::sync::MutexGuard<'_, BTreeMap<u64, Job>> {
+        self.0.jobs.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn publish(&self, event: ServerEvent) {
+        // Sem clientes ouvindo o envio falha, o que não é um problema
+        let _ = self.0.events.send(event);
+    }
+
+    /// Cria o job e começa o download numa tarefa própria.
+    fn start(&self, options: DownloadOptions) -> JobView {
+        let id = self.0.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let view = JobView {
+            id,
+            url: options.url.clone(),
+            audio_only: options.audio_only,
+            status: JobStatus::Running,
+            progress: Progress::default(),
+            path: None,
+            error: None,
+            created_at: stats::now(),
+        };
+        let control = JobControl::new();
+        self.jobs().insert(
+            id,
+            Job {
+                view: view.clone(),
+                control: control.clone(),
+            },
+        );
+        self.publish(ServerEvent::Job(view.clone()));
+
+        let state = self.clone();
+        let task = tokio::spawn(async move {
+            let events = state.clone();
+            let result =
+                download_with(&options, &control, move |event| events.on_event(id, event)).await;
+            state.finish(id, result);
+        });
+        let mut tasks = self.0.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        tasks.retain(|task| !task.is_finished());
+        tasks.push(task);
+        view
+    }
+
+    fn on_event(&self, id: u64, event: JobEvent) {
+        match event {
+            JobEvent::Progress(progress) => {
+                let view = {
+                    let mut jobs = self.jobs();
+                    let Some(job) = jobs.get_mut(&id) else {
+                        return;
+                    };
+                    job.view.progress = progress;
+                    job.view.clone()
+                };
+                self.publish(ServerEvent::Job(view));
+            }
+            JobEvent::Status(message) => self.publish(ServerEvent::Status { id, message }),
+            JobEvent::Log(_) => {}
+        }
+    }
+
+    fn finish(&self, id: u64, result: Result<Downloaded, DownloadError>) {
+        let view = {
+            let mut jobs = self.jobs();
+            let Some(job) = jobs.get_mut(&id) else {
+                return;
+            };
+            match result {
+                Ok(downloaded) => {
+                    job.view.status = JobStatus::Done;
+                    job.view.progress.percent = 100.0;
+                    job.view.path = downloaded.path;
+                }
+                Err(DownloadError::Cancelled) => job.view.status = JobStatus::Cancelled,
+                Err(e) => {
+                    job.view.status = JobStatus::Failed;
+                    job.view.error = Some(e.to_string());
+                }
+            }
+            job.view.clone()
+        };
+        self.publish(ServerEvent::Job(view));
+    }
+
+    /// Cancela os downloads em andamento e espera suas tarefas terminarem.
+    async fn stop_jobs(&self) {
+        for job in self.jobs().values() {
+            if job.view.status == JobStatus::Running {
+                job.control.cancel();
+            }
+        }
+        let tasks = std::mem::take(&mut *self.0.tasks.lock().unwrap_or_else(|e| e.into_inner()));
+        for task in tasks {
+            let _ = task.await;
+        }
+    }
+}
+
+/// Resposta de erro da API: `{"error": "..."}`.
+#[derive(Debug)]
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = Json(serde_json::json!({ "error": self.1 }));
+        if self.0 == StatusCode::UNAUTHORIZED {
+            return (self.0, [(header::WWW_AUTHENTICATE, "Bearer")], body).into_response();
+        }
+        (self.0, body).into_response()
+    }
+}
+
+/// Atende a API em `listener` até `shutdown` terminar; então cancela os
+/// downloads em andamento.
+pub async fn serve(
+    listener: TcpListener,
+    options: ServerOptions,
+    shutdown: impl Future<Output = ()>,
+) -> io::Result<()> {
+    let (stop, stopping) = watch::channel(false);
+    let state = AppState(Arc::new(Shared {
+        options,
+        jobs: Mutex::new(BTreeMap::new()),
+        next_id: AtomicU64::new(0),
+        events: broadcast::channel(EVENT_BUFFER).0,
+        stopping,
+        tasks: Mutex::new(Vec::new()),
+        pairing_failures: AtomicU32::new(0),
+    }));
+    listener.set_nonblocking(true)?;
+    let result = axum::Server::from_tcp(listener)
+        .map_err(io::Error::other)?
+        .serve(router(state.clone()).into_make_service())
+        .with_graceful_shutdown(async {
+            shutdown.await;
+            // Os streams de eventos não terminam sozinhos
+            let _ = stop.send(true);
+        })
+        .await;
+    state.stop_jobs().await;
+    result.map_err(io::Error::other)
+}
+
+fn router(state: AppState) -> Router {
+    let api = Router::new()
+        .route("/jobs", get(list_jobs).post(create_job))
+        .route("/jobs/:id", get(show_job).delete(delete_job))
+        .route("/jobs/:id/events", get(job_events))
+        .route("/events", get(all_events))
+        .layer(middleware::from_fn_with_state(state.clone(), authorize));
+    // Rotas para navegadores: autenticadas pelo próprio handler
+    let browser = Router::new()
+        .route("/enqueue", post(enqueue))
+        .route("/pair", post(pair))
+        .layer(middleware::from_fn_with_state(state.clone(), cors));
+    api.merge(browser).with_state(state)
+}
+
+async fn authorize(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next<Body>,
+) -> Response {
+    let Some(expected) = &state.0.options.token else {
+        return next.run(request).await;
+    };
+    match request_token(request.headers(), request.uri()) {
+        Some(token) if same_token(&token, expected) => next.run(request).await,
+        _ => ApiError(StatusCode::UNAUTHORIZED, t!("server.unauthorized")).into_response(),
+    }
+}
+
+/// Token de `Authorization: Bearer` ou, na falta dele, de `?token=`.
+fn request_token(headers: &HeaderMap, uri: &Uri) -> Option<String> {
+    let header_token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::to_string);
+    header_token.or_else(|| {
+        url::form_urlencoded::parse(uri.query()?.as_bytes())
+            .find(|(key, _)| key == "token")
+            .map(|(_, value)| value.into_owned())
+    })
+}
+
+/// CORS das rotas para navegadores. Pedidos sem `Origin` (curl, scripts)
+/// passam direto; os de origens fora de `allowed_origins` são recusados.
+async fn cors(State(state): State<AppState>, request: Request<Body>, next: Next<Body>) -> Response {
+    let Some(origin) = request
+        .headers()
+        .get(header::ORIGIN)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+    else {
+        return next.run(request).await;
+    };
+    if !origin_allowed(&state.0.options.allowed_origins, &origin) {
+        return ApiError(
+            StatusCode::FORBIDDEN,
+            t!("server.origin_denied", origin = origin),
+        )
+        .into_response();
+    }
+    let mut response = if request.method() == Method::OPTIONS {
+        let mut response = StatusCode::NO_CONTENT.into_response();
+        let headers = response.headers_mut();
+        headers.insert(
+            header::ACCESS_CONTROL_ALLOW_METHODS,
+            HeaderValue::from_static("POST, OPTIONS"),
+        );
+        headers.insert(
+            header::ACCESS_CONTROL_ALLOW_HEADERS,
+            HeaderValue::from_static("authorization, content-type"),
+        );
+        headers.insert(
+            header::ACCESS_CONTROL_MAX_AGE,
+            HeaderValue::from_static("600"),
+        );
+        // O Chrome pergunta antes de uma página pública acessar 127.0.0.1
+        headers.insert(
+            HeaderName::from_static("access-control-allow-private-network"),
+            HeaderValue::from_static("true"),
+        );
+        response
+    } else {
+        next.run(request).await
+    };
+    let headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&origin) {
+        headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, value);
+    }
+    headers.insert(header::VARY, HeaderValue::from_static("Origin"));
+    response
+}
+
+/// Origem exata ou, com `*` no fim do padrão, pelo começo.
+fn origin_allowed(patterns: &[String], origin: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => origin.starts_with(prefix) && origin.len() > prefix.len(),
+            None => pattern.eq_ignore_ascii_case(origin),
+        })
+}
+
+/// Compara sem parar no primeiro byte diferente, para não revelar o token
+/// pelo tempo de resposta.
+fn same_token(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+async fn list_jobs(State(state): State<AppState>) -> Json<Vec<JobView>> {
+    Json(state.jobs().values().map(|job| job.view.clone()).collect())
+}
+
+async fn create_job(
+    State(state): State<AppState>,
+    body: Result<Json<NewJob>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Json(request) = body.map_err(|e| {
+        ApiError(
+            StatusCode::BAD_REQUEST,
+            t!("server.invalid_request", error = e.body_text()),
+        )
+    })?;
+    let options = job_options(&state.0.options.dir, request)
+        .map_err(|message| ApiError(StatusCode::BAD_REQUEST, message))?;
+    let view = state.start(options);
+    let location = format!("/jobs/{}", view.id);
+    Ok((
+        StatusCode::CREATED,
+        [(header::LOCATION, location)],
+        Json(view),
+    ))
+}
+
+/// Parâmetros do `POST /enqueue`.
+#[derive(Debug, Deserialize)]
+struct EnqueueQuery {
+    url: String,
+    #[serde(default)]
+    profile: Option<String>,
+    #[serde(default)]
+    audio_only: bool,
+}
+
+/// `POST /enqueue?url=...`: baixa a página enviada por um navegador pareado
+/// e devolve o ID do job.
+async fn enqueue(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    uri: Uri,
+    query: Result<Query<EnqueueQuery>, QueryRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    // Vale um token de pareamento ou o token da API
+    let authorized = request_token(&headers, &uri).is_some_and(|token| {
+        let api_token = state.0.options.token.as_deref();
+        api_token.is_some_and(|expected| same_token(&token, expected)) || pairing::is_paired(&token)
+    });
+    if !authorized {
+        return Err(ApiError(
+            StatusCode::UNAUTHORIZED,
+            t!("server.unauthorized"),
+        ));
+    }
+    let Query(query) = query.map_err(|e| {
+        ApiError(
+            StatusCode::BAD_REQUEST,
+            t!("server.invalid_request", error = e.body_text()),
+        )
+    })?;
+    let request = NewJob {
+        url: query.url,
+        output: None,
+        audio_only: query.audio_only,
+        audio_format: None,
+        profile: query.profile,
+    };
+    let options = job_options(&state.0.options.dir, request)
+        .map_err(|message| ApiError(StatusCode::BAD_REQUEST, message))?;
+    let view = state.start(options);
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({ "id": view.id })),
+    ))
+}
+
+/// Parâmetros do `POST /pair`.
+#[derive(Debug, Deserialize)]
+struct PairQuery {
+    code: String,
+    /// Como o navegador aparece em `clients.json`.
+    #[serde(default)]
+    name: Option<String>,
+}
+
+/// `POST /pair?code=...&name=...`: troca o código mostrado pelo `serve` por
+/// um token de `/enqueue`.
+async fn pair(
+    State(state): State<AppState>,
+    query: Result<Query<PairQuery>, QueryRejection>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let Query(query) = query.map_err(|e| {
+        ApiError(
+            StatusCode::BAD_REQUEST,
+            t!("server.invalid_request", error = e.body_text()),
+        )
+    })?;
+    let failures = &state.0.pairing_failures;
+    let Some(expected) = &state.0.options.pairing_code else {
+        return Err(ApiError(StatusCode::FORBIDDEN, t!("server.pairing_locked")));
+    };
+    if failures.load(Ordering::SeqCst) >= MAX_PAIRING_ATTEMPTS {
+        return Err(ApiError(StatusCode::FORBIDDEN, t!("server.pairing_locked")));
+    }
+    if !pairing::code_matches(&query.code, expected) {
+        failures.fetch_add(1, Ordering::SeqCst);
+        return Err(ApiError(
+            StatusCode::UNAUTHORIZED,
+            t!("server.pairing_invalid"),
+        ));
+    }
+    let name = query
+        .name
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| t!("server.unnamed_client"));
+    let token = pairing::pair(&name).map_err(|e| {
+        ApiError(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            t!("server.pairing_failed", error = e),
+        )
+    })?;
+    Ok(Json(serde_json::json!({ "token": token })))
+}
+
+/// Valida o pedido e monta as opções do download.
+fn job_options(dir: &Path, request: NewJob) -> Result<DownloadOptions, String> {
+    if !is_youtube_url(&request.url) {
+        return Err(DownloadError::InvalidUrl(request.url).to_string());
+    }
+    let output = request.output.as_deref().unwrap_or(TITLE_OUTPUT);
+    // Um cliente da API não escolhe diretórios fora do `--dir`
+    if !matches!(
+        Path::new(output)
+            .components()
+            .collect::<Vec<_>>()
+            .as_slice(),
+        [Component::Normal(_)]
+    ) {
+        return Err(t!("server.invalid_output", output = output));
+    }
+    let output_name = dir.join(output).display().to_string();
+    let mut options = DownloadOptions::new(&request.url, &output_name, request.audio_only);
+    if let Some(format) = request.audio_format.filter(|_| request.audio_only) {
+        options.audio_format = (format != "original").then_some(format);
+    }
+    let config = Config::load();
+    if let Some(name) = &request.profile {
+        options.access = access::profile(&config, name).map_err(|e| e.to_string())?;
+    }
+    // Cookies ou proxy inválidos devem falhar aqui, e não depois no job
+    access::resolve(&config, &options.access).map_err(|e| e.to_string())?;
+    Ok(options)
+}
+
+fn not_found(id: u64) -> ApiError {
+    ApiError(StatusCode::NOT_FOUND, t!("server.job_not_found", id = id))
+}
+
+async fn show_job(
+    State(state): State<AppState>,
+    UrlPath(id): UrlPath<u64>,
+) -> Result<Json<JobView>, ApiError> {
+    state
+        .jobs()
+        .get(&id)
+        .map(|job| Json(job.view.clone()))
+        .ok_or_else(|| not_found(id))
+}
+
+/// Cancela um job em andamento (`202`) ou remove um que já terminou (`204`).
+async fn delete_job(
+    State(state): State<AppState>,
+    UrlPath(id): UrlPath<u64>,
+) -> Result<Response, ApiError> {
+    let mut jobs = state.jobs();
+    let job = jobs.get(&id).ok_or_else(|| not_found(id))?;
+    if job.view.status == JobStatus::Running {
+        job.control.cancel();
+        return Ok((StatusCode::ACCEPTED, Json(job.view.clone())).into_response());
+    }
+    jobs.remove(&id);
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn all_events(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let events = state.0.events.subscribe();
+    Sse::new(event_stream(&state, events, None, Vec::new())).keep_alive(KeepAlive::default())
+}
+
+/// Eventos de um job, começando pelo estado atual; o stream termina quando
+/// o job termina.
+async fn job_events(
+    State(state): State<AppState>,
+    UrlPath(id): UrlPath<u64>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    // Assinar antes de ler o estado, para não perder o que acontecer entre os dois
+    let events = state.0.events.subscribe();
+    let view = state
+        .jobs()
+        .get(&id)
+        .map(|job| job.view.clone())
+        .ok_or_else(|| not_found(id))?;
+    let initial = vec![ServerEvent::Job(view)];
+    Ok(Sse::new(event_stream(&state, events, Some(id), initial)).keep_alive(KeepAlive::default()))
+}
+
+fn event_stream(
+    state: &AppState,
+    events: broadcast::Receiver<ServerEvent>,
+    job: Option<u64>,
+    initial: Vec<ServerEvent>,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    struct Cursor {
+        events: broadcast::Receiver<ServerEvent>,
+        stopping: watch::Receiver<bool>,
+        pending: std::vec::IntoIter<ServerEvent>,
+        done: bool,
+    }
+
+    let cursor = Cursor {
+        events,
+        stopping: state.0.stopping.clone(),
+        pending: initial.into_iter(),
+        done: false,
+    };
+    let ended = move |event: &ServerEvent| {
+        job.is_some()
+            && matches!(event, ServerEvent::Job(view) if view.status != JobStatus::Running)
+    };
+    futures_util::stream::unfold(cursor, move |mut cursor| async move {
+        if cursor.done {
+            return None;
+        }
+        let event = match cursor.pending.next() {
+            Some(event) => event,
+            None => loop {
+                if *cursor.stopping.borrow() {
+                    return None;
+                }
+                tokio::select! {
+                    received = cursor.events.recv() => match received {
+                        Ok(event) if job.is_none_or(|id| id == event.job_id()) => break event,
+                        Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => return None,
+                    },
+                    _ = cursor.stopping.changed() => return None,
+                }
+            },
+        };
+        cursor.done = ended(&event);
+        Some((Ok(event.to_sse()), cursor))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(json: &str) -> NewJob {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_job_options() {
+        let dir = Path::new("/srv/videos");
+        let options = job_options(
+            dir,
+            request(r#"{"url": "https://youtu.be/dQw4w9WgXcQ", "output": "aula", "audio_only": true, "audio_format": "original"}"#),
+        )
+        .unwrap();
+        assert_eq!(options.output_name, "/srv/videos/aula");
+        assert_eq!(options.audio_format, None);
+
+        for output in ["../fora", "/etc/passwd", "a/b", ".."] {
+            let json =
+                format!(r#"{{"url": "https://youtu.be/dQw4w9WgXcQ", "output": "{output}"}}"#);
+            assert!(job_options(dir, request(&json)).is_err(), "{output}");
+        }
+        assert!(job_options(dir, request(r#"{"url": "https://example.com"}"#)).is_err());
+    }
+
+    #[test]
+    fn test_origin_allowed() {
+        let patterns = [
+            "https://www.youtube.com".to_string(),
+            "moz-extension://*".to_string(),
+        ];
+        assert!(origin_allowed(&patterns, "https://www.youtube.com"));
+        assert!(origin_allowed(&patterns, "moz-extension://1234-abcd"));
+        assert!(!origin_allowed(&patterns, "moz-extension://"));
+        assert!(!origin_allowed(
+            &patterns,
+            "https://www.youtube.com.example.org"
+        ));
+        assert!(!origin_allowed(&patterns, "null"));
+    }
+
+    #[test]
+    fn test_same_token() {
+        assert!(same_token("segredo", "segredo"));
+        assert!(!same_token("segred0", "segredo"));
+        assert!(!same_token("seg", "segredo"));
+    }
+}
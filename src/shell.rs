@@ -0,0 +1,559 @@
+//! Gramática dos comandos do modo interativo.
+//!
+//! A linha é dividida como em um shell (aspas simples e duplas, `\` para
+//! escapar) e cada comando é validado pela mesma definição clap usada na linha
+//! de comando, o que garante as mesmas flags e mensagens de uso.
+
+use crate::{
+    doctor_command, results_arg, stats_command, stats_period, update_action, update_backend_command,
+};
+use clap::{Arg, ArgMatches, Command};
+use std::fmt;
+use youtube_video_downloader::bandwidth::Limit;
+use youtube_video_downloader::provision::UpdateAction;
+use youtube_video_downloader::search::parse_selection;
+use youtube_video_downloader::stats::Period;
+use youtube_video_downloader::{is_youtube_url, t};
+
+/// Comando interativo já validado.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplCommand {
+    Exit,
+    Help,
+    Clear,
+    Demo,
+    Doctor {
+        dir: String,
+    },
+    Stats(Period),
+    UpdateBackend(UpdateAction),
+    Search {
+        query: String,
+        limit: usize,
+    },
+    /// Baixa resultados da última busca, pelos números da lista.
+    Get {
+        numbers: Vec<usize>,
+        audio_only: bool,
+    },
+    Download {
+        url: String,
+        output: String,
+        audio_only: bool,
+    },
+    /// Mostra ou muda o limite de banda dos próximos downloads.
+    Throttle(ThrottleChange),
+    /// Retoma (ou descarta) downloads interrompidos em execuções anteriores.
+    Resume {
+        discard: bool,
+    },
+    /// URL colada sozinha no prompt.
+    Url(String),
+}
+
+/// O que o `throttle` faz com o limite de banda.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThrottleChange {
+    Show,
+    Set(Limit),
+    /// Volta a seguir a configuração (`throttle auto`).
+    Auto,
+}
+
+/// Download pedido como `[download] <URL> [-a] [-o nome]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobSpec {
+    pub url: String,
+    /// Nome de saída informado com `-o`.
+    pub output: Option<String>,
+    pub audio_only: bool,
+}
+
+/// Motivo pelo qual uma linha não pôde ser interpretada.
+#[derive(Debug)]
+pub enum ParseError {
+    UnclosedQuote(char),
+    TrailingBackslash,
+    UnknownCommand(String),
+    /// Argumentos inválidos; a mensagem do clap já inclui o uso correto.
+    Usage(clap::Error),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnclosedQuote(quote) => {
+                write!(f, "{}", t!("shell.unclosed_quote", quote = quote))
+            }
+            ParseError::TrailingBackslash => write!(f, "{}", t!("shell.trailing_backslash")),
+            ParseError::UnknownCommand(command) => {
+                write!(
+                    f,
+                    "{}",
+                    t!("interactive.unknown_command", command = command)
+                )
+            }
+            ParseError::Usage(err) => write!(f, "{}", err.to_string().trim_end()),
+        }
+    }
+}
+
+/// Divide a linha em palavras seguindo as regras de aspas de um shell POSIX.
+pub fn split(line: &str) -> Result<Vec<String>, ParseError> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    // Distingue "nenhuma palavra" de uma palavra vazia como `""`
+    let mut in_word = false;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err(ParseError::UnclosedQuote('\'')),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => current.push(c),
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => return Err(ParseError::UnclosedQuote('"')),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err(ParseError::UnclosedQuote('"')),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                current.push(chars.next().ok_or(ParseError::TrailingBackslash)?);
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+/// Interpreta uma linha do prompt. `Ok(None)` para linhas vazias.
+pub fn parse(line: &str) -> Result<Option<ReplCommand>, ParseError> {
+    let words = split(line)?;
+    let Some(name) = words.first() else {
+        return Ok(None);
+    };
+    let args = &words[1..];
+
+    let command = match name.as_str() {
+        "exit" => no_args("exit", args, ReplCommand::Exit)?,
+        "quit" => no_args("quit", args, ReplCommand::Exit)?,
+        "help" => no_args("help", args, ReplCommand::Help)?,
+        "clear" => no_args("clear", args, ReplCommand::Clear)?,
+        "demo" => no_args("demo", args, ReplCommand::Demo)?,
+        "doctor" => {
+            let matches = matches(doctor_command(), args)?;
+            ReplCommand::Doctor {
+                dir: matches.get_one::<String>("dir").unwrap().clone(),
+            }
+        }
+        "stats" => ReplCommand::Stats(stats_period(&matches(stats_command(), args)?)),
+        "update-backend" => {
+            ReplCommand::UpdateBackend(update_action(&matches(update_backend_command(), args)?))
+        }
+        "download" => download(job_spec(&matches(download_command(), args)?)),
+        "search" => {
+            let matches = matches(search_command(), args)?;
+            let words: Vec<&str> = matches
+                .get_many::<String>("query")
+                .unwrap()
+                .map(String::as_str)
+                .collect();
+            ReplCommand::Search {
+                query: words.join(" "),
+                limit: *matches.get_one::<usize>("results").unwrap(),
+            }
+        }
+        "get" => {
+            let matches = matches(get_command(), args)?;
+            ReplCommand::Get {
+                numbers: matches.get_one::<Vec<usize>>("selection").unwrap().clone(),
+                audio_only: matches.get_flag("audio"),
+            }
+        }
+        "throttle" => {
+            let matches = matches(throttle_command(), args)?;
+            ReplCommand::Throttle(match matches.get_one::<Option<Limit>>("rate") {
+                None => ThrottleChange::Show,
+                Some(None) => ThrottleChange::Auto,
+                Some(Some(limit)) => ThrottleChange::Set(*limit),
+            })
+        }
+        "resume" => ReplCommand::Resume {
+            discard: matches(resume_command(), args)?.get_flag("discard"),
+        },
+        url if is_youtube_url(url) && args.is_empty() => ReplCommand::Url(url.to_string()),
+        // URL colada com flags: `<URL> -a -o nome`
+        url if is_youtube_url(url) => download(job_spec(&matches(download_command(), &words)?)),
+        other => return Err(ParseError::UnknownCommand(other.to_string())),
+    };
+    Ok(Some(command))
+}
+
+/// Comando `download` do modo interativo.
+pub fn download_command() -> Command {
+    Command::new("download")
+        .arg(
+            Arg::new("url")
+                .value_name("URL")
+                .required(true)
+                .help(t!("cli.arg.url")),
+        )
+        .arg(
+            Arg::new("audio")
+                .short('a')
+                .long("audio")
+                .visible_alias("audio-only")
+                .action(clap::ArgAction::SetTrue)
+                .help(t!("cli.arg.audio_only")),
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name(t!("cli.value.name"))
+                .help(t!("cli.arg.output")),
+        )
+}
+
+/// Comando `search` do modo interativo.
+pub fn search_command() -> Command {
+    Command::new("search")
+        .arg(
+            Arg::new("query")
+                .value_name(t!("cli.value.terms"))
+                .required(true)
+                .num_args(1..)
+                .help(t!("search.arg_query")),
+        )
+        .arg(results_arg().short('n'))
+}
+
+/// Comando `get`, que baixa resultados da última busca.
+pub fn get_command() -> Command {
+    Command::new("get")
+        .arg(
+            Arg::new("selection")
+                .value_name("1,4-6")
+                .required(true)
+                .value_parser(parse_selection)
+                .help(t!("search.arg_selection")),
+        )
+        .arg(
+            Arg::new("audio")
+                .short('a')
+                .long("audio")
+                .visible_alias("audio-only")
+                .action(clap::ArgAction::SetTrue)
+                .help(t!("cli.arg.audio_only")),
+        )
+}
+
+/// Comando `throttle`; `auto` devolve o controle à configuração.
+pub fn throttle_command() -> Command {
+    Command::new("throttle").arg(
+        Arg::new("rate")
+            .value_name("2M|off|auto")
+            .value_parser(|text: &str| match text {
+                "auto" => Ok(None),
+                text => Limit::parse(text).map(Some),
+            })
+            .help(t!("bandwidth.arg_rate")),
+    )
+}
+
+/// Comando `resume`, para downloads interrompidos.
+pub fn resume_command() -> Command {
+    Command::new("resume").arg(
+        Arg::new("discard")
+            .long("discard")
+            .action(clap::ArgAction::SetTrue)
+            .help(t!("resume.arg_discard")),
+    )
+}
+
+/// Interpreta um pedido de download, com ou sem a palavra `download`.
+/// `Ok(None)` para linhas vazias.
+pub fn parse_job(line: &str) -> Result<Option<JobSpec>, ParseError> {
+    let words = split(line)?;
+    let args = match words.first().map(String::as_str) {
+        None => return Ok(None),
+        Some("download") => &words[1..],
+        Some(_) => &words[..],
+    };
+    Ok(Some(job_spec(&matches(download_command(), args)?)))
+}
+
+fn job_spec(matches: &ArgMatches) -> JobSpec {
+    JobSpec {
+        url: matches.get_one::<String>("url").unwrap().clone(),
+        output: matches.get_one::<String>("output").cloned(),
+        audio_only: matches.get_flag("audio"),
+    }
+}
+
+fn download(spec: JobSpec) -> ReplCommand {
+    let output = match spec.output {
+        Some(output) => output,
+        None if spec.audio_only => "audio".to_string(),
+        None => "video".to_string(),
+    };
+    ReplCommand::Download {
+        url: spec.url,
+        output,
+        audio_only: spec.audio_only,
+    }
+}
+
+/// Valida os argumentos com a definição clap do comando.
+fn matches(command: Command, args: &[String]) -> Result<ArgMatches, ParseError> {
+    command
+        .no_binary_name(true)
+        .try_get_matches_from(args)
+        .map_err(ParseError::Usage)
+}
+
+fn no_args(
+    name: &'static str,
+    args: &[String],
+    command: ReplCommand,
+) -> Result<ReplCommand, ParseError> {
+    matches(Command::new(name), args)?;
+    Ok(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(line: &str) -> Vec<String> {
+        split(line).unwrap()
+    }
+
+    fn download(url: &str, output: &str, audio_only: bool) -> Option<ReplCommand> {
+        Some(ReplCommand::Download {
+            url: url.to_string(),
+            output: output.to_string(),
+            audio_only,
+        })
+    }
+
+    #[test]
+    fn test_split_quoting() {
+        assert_eq!(words("  download   url  "), vec!["download", "url"]);
+        assert_eq!(words(r#"-o "Meu Vídeo""#), vec!["-o", "Meu Vídeo"]);
+        assert_eq!(words("-o 'Meu \"Vídeo\"'"), vec!["-o", "Meu \"Vídeo\""]);
+        assert_eq!(words(r"-o Meu\ Vídeo"), vec!["-o", "Meu Vídeo"]);
+        assert_eq!(words(r#""a\"b\n""#), vec![r#"a"b\n"#]);
+        assert_eq!(words(r#"a""b ''"#), vec!["ab", ""]);
+        assert!(words("").is_empty());
+    }
+
+    #[test]
+    fn test_split_errors() {
+        assert!(matches!(
+            split("-o \"aberto"),
+            Err(ParseError::UnclosedQuote('"'))
+        ));
+        assert!(matches!(
+            split("-o 'aberto"),
+            Err(ParseError::UnclosedQuote('\''))
+        ));
+        assert!(matches!(split("fim\\"), Err(ParseError::TrailingBackslash)));
+    }
+
+    #[test]
+    fn test_parse_download() {
+        let url = "https://youtu.be/abc";
+        assert_eq!(
+            parse(&format!("download {url}")).unwrap(),
+            download(url, "video", false)
+        );
+        assert_eq!(
+            parse(&format!("download {url} -a")).unwrap(),
+            download(url, "audio", true)
+        );
+        assert_eq!(
+            parse(&format!(r#"download -o "Meu Vídeo" {url}"#)).unwrap(),
+            download(url, "Meu Vídeo", false)
+        );
+        assert_eq!(
+            parse(&format!("download {url} --audio --output=faixa")).unwrap(),
+            download(url, "faixa", true)
+        );
+        assert_eq!(
+            parse(&format!("{url} -a -o 'minha faixa'")).unwrap(),
+            download(url, "minha faixa", true)
+        );
+        assert_eq!(parse(url).unwrap(), Some(ReplCommand::Url(url.to_string())));
+    }
+
+    #[test]
+    fn test_parse_download_usage_errors() {
+        let url = "https://youtu.be/abc";
+        for line in [
+            format!("download {url} -o"),
+            format!("download {url} --formato mp3"),
+            format!("download {url} outro"),
+            "download".to_string(),
+            "download -a".to_string(),
+        ] {
+            assert!(
+                matches!(parse(&line), Err(ParseError::Usage(_))),
+                "deveria falhar: {line}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_job() {
+        let spec = |output: Option<&str>, audio_only| {
+            Some(JobSpec {
+                url: "https://youtu.be/abc".into(),
+                output: output.map(str::to_string),
+                audio_only,
+            })
+        };
+        assert_eq!(
+            parse_job("https://youtu.be/abc").unwrap(),
+            spec(None, false)
+        );
+        assert_eq!(
+            parse_job("download https://youtu.be/abc -a -o 'minha faixa'").unwrap(),
+            spec(Some("minha faixa"), true)
+        );
+        assert_eq!(parse_job("  ").unwrap(), None);
+        assert!(matches!(
+            parse_job("https://youtu.be/abc -o"),
+            Err(ParseError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_search_and_get() {
+        assert_eq!(
+            parse("search lofi hip hop").unwrap(),
+            Some(ReplCommand::Search {
+                query: "lofi hip hop".into(),
+                limit: 10
+            })
+        );
+        assert_eq!(
+            parse("search -n 3 'rust async'").unwrap(),
+            Some(ReplCommand::Search {
+                query: "rust async".into(),
+                limit: 3
+            })
+        );
+        assert_eq!(
+            parse("get 1,4-6 -a").unwrap(),
+            Some(ReplCommand::Get {
+                numbers: vec![1, 4, 5, 6],
+                audio_only: true
+            })
+        );
+        for line in ["search", "search -n dez lofi", "get", "get 0", "get 2-1"] {
+            assert!(
+                matches!(parse(line), Err(ParseError::Usage(_))),
+                "deveria falhar: {line}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_other_commands() {
+        assert_eq!(parse("   ").unwrap(), None);
+        assert_eq!(parse("quit").unwrap(), Some(ReplCommand::Exit));
+        assert_eq!(
+            parse("stats").unwrap(),
+            Some(ReplCommand::Stats(Period::All))
+        );
+        assert_eq!(
+            parse("stats --week").unwrap(),
+            Some(ReplCommand::Stats(Period::Week))
+        );
+        assert_eq!(
+            parse("update-backend --pin 2024.05.27").unwrap(),
+            Some(ReplCommand::UpdateBackend(UpdateAction::Pin(
+                "2024.05.27".into()
+            )))
+        );
+        assert_eq!(
+            parse("doctor --dir '/tmp/meus vídeos'").unwrap(),
+            Some(ReplCommand::Doctor {
+                dir: "/tmp/meus vídeos".into()
+            })
+        );
+        assert!(matches!(
+            parse("stats --today --week"),
+            Err(ParseError::Usage(_))
+        ));
+        assert_eq!(
+            parse("resume").unwrap(),
+            Some(ReplCommand::Resume { discard: false })
+        );
+        assert_eq!(
+            parse("resume --discard").unwrap(),
+            Some(ReplCommand::Resume { discard: true })
+        );
+        assert!(matches!(parse("resume agora"), Err(ParseError::Usage(_))));
+        assert_eq!(
+            parse("throttle").unwrap(),
+            Some(ReplCommand::Throttle(ThrottleChange::Show))
+        );
+        assert_eq!(
+            parse("throttle 512K").unwrap(),
+            Some(ReplCommand::Throttle(ThrottleChange::Set(Limit::Rate(
+                512 * 1024
+            ))))
+        );
+        assert_eq!(
+            parse("throttle off").unwrap(),
+            Some(ReplCommand::Throttle(ThrottleChange::Set(Limit::Unlimited)))
+        );
+        assert_eq!(
+            parse("throttle auto").unwrap(),
+            Some(ReplCommand::Throttle(ThrottleChange::Auto))
+        );
+        assert!(matches!(
+            parse("throttle rápido"),
+            Err(ParseError::Usage(_))
+        ));
+        assert!(matches!(parse("clear agora"), Err(ParseError::Usage(_))));
+        assert!(matches!(
+            parse("baixar https://youtu.be/abc"),
+            Err(ParseError::UnknownCommand(name)) if name == "baixar"
+        ));
+    }
+}
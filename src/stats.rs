@@ -0,0 +1,279 @@
+//! Estatísticas persistentes de downloads (`stats.jsonl` no diretório de dados).
+//!
+//! Cada download concluído ou que falhou no yt-dlp vira uma linha JSON; os
+//! totais por período são calculados na leitura.
+
+use crate::doctor::format_bytes;
+use crate::paths;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Quantos canais aparecem no resumo.
+const TOP_CHANNELS: usize = 5;
+
+/// Um download registrado.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    /// Momento do download, em segundos desde a época Unix.
+    pub at: u64,
+    pub url: String,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    /// Tamanho do arquivo final.
+    #[serde(default)]
+    pub bytes: u64,
+    /// Duração da mídia, em segundos.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<f64>,
+}
+
+impl Record {
+    pub fn new(url: &str, ok: bool) -> Self {
+        Record {
+            at: now(),
+            url: url.to_string(),
+            ok,
+            channel: None,
+            bytes: 0,
+            duration_secs: None,
+        }
+    }
+}
+
+pub fn path() -> PathBuf {
+    paths::data_dir().join("stats.jsonl")
+}
+
+/// Acrescenta um registro ao arquivo de estatísticas.
+pub fn record(record: &Record) -> io::Result<()> {
+    fs::create_dir_all(paths::data_dir())?;
+    let line = serde_json::to_string(record).map_err(io::Error::other)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path())?;
+    writeln!(file, "{line}")
+}
+
+/// Todos os registros; linhas corrompidas são ignoradas.
+pub fn load() -> Vec<Record> {
+    fs::read_to_string(path())
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Período coberto por um resumo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    /// Desde a meia-noite (horário local).
+    Today,
+    /// Últimos 7 dias, contando hoje.
+    Week,
+    All,
+}
+
+impl Period {
+    /// Primeiro instante (segundos Unix) incluído no período.
+    fn start(self, now: u64, utc_offset: i64) -> u64 {
+        let local_day = (now as i64 + utc_offset).div_euclid(86_400);
+        let days_back = match self {
+            Period::Today => 0,
+            Period::Week => 6,
+            Period::All => return 0,
+        };
+        ((local_day - days_back) * 86_400 - utc_offset).max(0) as u64
+    }
+
+    fn title(self) -> String {
+        match self {
+            Period::Today => t!("stats.period_today"),
+            Period::Week => t!("stats.period_week"),
+            Period::All => t!("stats.period_all"),
+        }
+    }
+}
+
+/// Totais de um período.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub period: Period,
+    pub downloads: u32,
+    pub failures: u32,
+    pub bytes: u64,
+    pub duration_secs: f64,
+    /// Downloads por canal, do maior para o menor.
+    pub channels: Vec<(String, u32)>,
+}
+
+impl Summary {
+    pub fn print(&self) {
+        println!(
+            "{}",
+            t!("stats.title", period = self.period.title())
+                .cyan()
+                .bold()
+        );
+        println!("  {:<18} {}", t!("stats.downloads"), self.downloads);
+        println!("  {:<18} {}", t!("stats.failures"), self.failures);
+        println!("  {:<18} {}", t!("stats.bytes"), format_bytes(self.bytes));
+        println!(
+            "  {:<18} {}",
+            t!("stats.duration"),
+            format_duration(self.duration_secs)
+        );
+        if !self.channels.is_empty() {
+            println!("  {}", t!("stats.channels"));
+            for (channel, count) in self.channels.iter().take(TOP_CHANNELS) {
+                println!("    • {channel}: {count}");
+            }
+        }
+    }
+}
+
+/// Resume os registros do período, com o "hoje" calculado no fuso local.
+pub fn summarize(records: &[Record], period: Period) -> Summary {
+    summarize_at(records, period, now(), utc_offset())
+}
+
+fn summarize_at(records: &[Record], period: Period, now: u64, utc_offset: i64) -> Summary {
+    let start = period.start(now, utc_offset);
+    let mut summary = Summary {
+        period,
+        downloads: 0,
+        failures: 0,
+        bytes: 0,
+        duration_secs: 0.0,
+        channels: Vec::new(),
+    };
+    let mut channels: HashMap<&str, u32> = HashMap::new();
+
+    for record in records.iter().filter(|r| r.at >= start) {
+        if !record.ok {
+            summary.failures += 1;
+            continue;
+        }
+        summary.downloads += 1;
+        summary.bytes += record.bytes;
+        summary.duration_secs += record.duration_secs.unwrap_or(0.0);
+        if let Some(channel) = &record.channel {
+            *channels.entry(channel).or_default() += 1;
+        }
+    }
+
+    summary.channels = channels
+        .into_iter()
+        .map(|(channel, count)| (channel.to_string(), count))
+        .collect();
+    summary
+        .channels
+        .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    summary
+}
+
+/// `1h 02min`, `3min 20s` ou `45s`.
+pub fn format_duration(secs: f64) -> String {
+    let secs = secs.round() as u64;
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes:02}min")
+    } else if minutes > 0 {
+        format!("{minutes}min {seconds:02}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+/// Segundos desde a época Unix.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Diferença do fuso local para o UTC, em segundos.
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)]
+pub(crate) fn utc_offset() -> i64 {
+    let now = now() as libc::time_t;
+    // SAFETY: `localtime_r` apenas preenche a estrutura apontada.
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    if unsafe { libc::localtime_r(&now, &mut tm) }.is_null() {
+        return 0;
+    }
+    tm.tm_gmtoff as i64
+}
+
+#[cfg(not(unix))]
+pub(crate) fn utc_offset() -> i64 {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(at: u64, ok: bool, channel: Option<&str>, bytes: u64) -> Record {
+        Record {
+            at,
+            url: "https://youtu.be/abc".into(),
+            ok,
+            channel: channel.map(str::to_string),
+            bytes,
+            duration_secs: Some(60.0),
+        }
+    }
+
+    #[test]
+    fn test_summarize_periods() {
+        // 2024-05-27 12:00 UTC
+        let now = 19_870 * 86_400 + 12 * 3600;
+        let records = vec![
+            record(now - 3600, true, Some("Canal A"), 100),
+            record(now - 3 * 86_400, true, Some("Canal B"), 200),
+            record(now - 3 * 86_400, false, None, 0),
+            record(now - 30 * 86_400, true, Some("Canal A"), 400),
+        ];
+
+        let today = summarize_at(&records, Period::Today, now, 0);
+        assert_eq!((today.downloads, today.failures, today.bytes), (1, 0, 100));
+
+        let week = summarize_at(&records, Period::Week, now, 0);
+        assert_eq!((week.downloads, week.failures, week.bytes), (2, 1, 300));
+        assert_eq!(week.duration_secs, 120.0);
+
+        let all = summarize_at(&records, Period::All, now, 0);
+        assert_eq!(all.downloads, 3);
+        assert_eq!(
+            all.channels,
+            vec![("Canal A".to_string(), 2), ("Canal B".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_today_uses_local_midnight() {
+        // 2024-05-27 01:00 UTC é 2024-05-26 22:00 em UTC-3
+        let now = 19_870 * 86_400 + 3600;
+        let records = vec![record(19_870 * 86_400 - 4 * 3600, true, None, 1)];
+        assert_eq!(summarize_at(&records, Period::Today, now, 0).downloads, 0);
+        assert_eq!(
+            summarize_at(&records, Period::Today, now, -3 * 3600).downloads,
+            1
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(45.4), "45s");
+        assert_eq!(format_duration(200.0), "3min 20s");
+        assert_eq!(format_duration(3720.0), "1h 02min");
+    }
+}
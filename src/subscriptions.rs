@@ -0,0 +1,147 @@
+//! Assinaturas de canais e playlists (`subscribe`) e a verificação de envios
+//! novos feita pelo `watch`.
+//!
+//! Cada verificação lista os vídeos mais recentes com `--flat-playlist`, sem
+//! baixar nada, e descarta os que já estão no [`archive`] ou não passam nos
+//! filtros da assinatura.
+
+use crate::access::{self, AccessOptions};
+use crate::archive;
+use crate::config::Config;
+use crate::download::{DownloadOptions, TITLE_OUTPUT};
+use crate::error::DownloadError;
+use crate::filter::{self, Filters, Selection};
+use crate::search::{self, SearchResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Um canal ou playlist acompanhado pelo `watch`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Subscription {
+    pub url: String,
+    /// Perfil de acesso usado para listar e baixar.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub audio_only: bool,
+    /// Filtros de conteúdo, gravados no mesmo nível da assinatura.
+    #[serde(flatten)]
+    pub filters: Filters,
+}
+
+impl Subscription {
+    pub fn new(url: &str) -> Self {
+        Subscription {
+            url: url.to_string(),
+            profile: None,
+            audio_only: false,
+            filters: Filters::default(),
+        }
+    }
+
+    /// Confere a URL, o perfil e os filtros antes de salvar.
+    pub fn validate(&self, config: &Config) -> Result<(), DownloadError> {
+        if !search::is_collection_url(&self.url) {
+            return Err(DownloadError::InvalidUrl(self.url.clone()));
+        }
+        self.filters.compile()?;
+        access::resolve(config, &self.access(config)?)?;
+        Ok(())
+    }
+
+    /// Opções para baixar um envio novo em `dir`.
+    pub fn download_options(
+        &self,
+        config: &Config,
+        video: &SearchResult,
+        dir: &Path,
+    ) -> Result<DownloadOptions, DownloadError> {
+        let output = dir.join(TITLE_OUTPUT).display().to_string();
+        Ok(DownloadOptions {
+            access: self.access(config)?,
+            ..DownloadOptions::new(&video.url, &output, self.audio_only)
+        })
+    }
+
+    fn access(&self, config: &Config) -> Result<AccessOptions, DownloadError> {
+        match &self.profile {
+            Some(name) => access::profile(config, name),
+            None => Ok(AccessOptions::default()),
+        }
+    }
+}
+
+/// Os `limit` vídeos mais recentes da assinatura.
+pub async fn list_uploads(
+    subscription: &Subscription,
+    limit: usize,
+) -> Result<Vec<SearchResult>, DownloadError> {
+    let access = subscription.access(&Config::load())?;
+    search::list_collection(&subscription.url, Some(limit), &access).await
+}
+
+/// Lista a assinatura e avalia os filtros nos envios ainda não baixados.
+/// Vídeos em `ignored`, como os já reprovados numa rodada anterior, nem são
+/// avaliados.
+pub async fn check(
+    subscription: &Subscription,
+    limit: usize,
+    ignored: &HashSet<String>,
+) -> Result<Selection, DownloadError> {
+    let matcher = subscription.filters.compile()?;
+    let access = subscription.access(&Config::load())?;
+    let archived = archive::ids();
+    let uploads = search::list_collection(&subscription.url, Some(limit), &access)
+        .await?
+        .into_iter()
+        .filter(|video| !archived.contains(&video.id) && !ignored.contains(&video.id))
+        .collect();
+    Ok(filter::select(&matcher, uploads, &access).await)
+}
+
+/// Salva a assinatura na configuração; `false` se a URL já estava assinada.
+pub fn add(config: &mut Config, subscription: Subscription) -> std::io::Result<bool> {
+    if config
+        .subscriptions
+        .iter()
+        .any(|s| s.url == subscription.url)
+    {
+        return Ok(false);
+    }
+    config.subscriptions.push(subscription);
+    config.save()?;
+    Ok(true)
+}
+
+/// Remove a assinatura da configuração; `false` se ela não existia.
+pub fn remove(config: &mut Config, url: &str) -> std::io::Result<bool> {
+    let before = config.subscriptions.len();
+    config.subscriptions.retain(|s| s.url != url);
+    if config.subscriptions.len() == before {
+        return Ok(false);
+    }
+    config.save()?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filters_are_stored_with_the_subscription() {
+        let json = r#"{"url": "https://www.youtube.com/@canal", "min_duration_secs": 60, "match_title": "(?i)aula"}"#;
+        let subscription: Subscription = serde_json::from_str(json).unwrap();
+        assert_eq!(subscription.filters.min_duration_secs, Some(60));
+        assert_eq!(
+            subscription.filters.match_title.as_deref(),
+            Some("(?i)aula")
+        );
+
+        let stored = serde_json::to_value(&subscription).unwrap();
+        assert_eq!(stored["min_duration_secs"], 60);
+        assert!(stored.get("skip_shorts").is_none());
+        assert!(stored.get("filters").is_none());
+    }
+}
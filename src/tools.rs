@@ -0,0 +1,305 @@
+//! Detecção das ferramentas externas (yt-dlp, ffmpeg, ffprobe, Python).
+
+use crate::config::Config;
+use crate::paths;
+use crate::provision;
+use crate::stats;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command as StdCommand;
+use std::sync::{Mutex, OnceLock};
+use std::time::UNIX_EPOCH;
+
+/// Ferramenta externa usada pelo downloader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tool {
+    YtDlp,
+    Ffmpeg,
+    Ffprobe,
+    Python,
+}
+
+impl Tool {
+    pub fn name(self) -> &'static str {
+        match self {
+            Tool::YtDlp => "yt-dlp",
+            Tool::Ffmpeg => "ffmpeg",
+            Tool::Ffprobe => "ffprobe",
+            Tool::Python => "python3",
+        }
+    }
+
+    /// Programa a executar: o yt-dlp gerenciado tem precedência sobre o do PATH.
+    pub fn program(self) -> PathBuf {
+        match self {
+            Tool::YtDlp => provision::yt_dlp_program(),
+            Tool::Python if cfg!(windows) => PathBuf::from("python"),
+            tool => PathBuf::from(tool.name()),
+        }
+    }
+
+    fn version_arg(self) -> &'static str {
+        match self {
+            Tool::YtDlp | Tool::Python => "--version",
+            Tool::Ffmpeg | Tool::Ffprobe => "-version",
+        }
+    }
+}
+
+impl fmt::Display for Tool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Ferramenta encontrada, com o programa executado e a versão reportada.
+#[derive(Debug, Clone)]
+pub struct ToolInfo {
+    pub tool: Tool,
+    pub program: PathBuf,
+    pub version: String,
+}
+
+/// Detecta a ferramenta, reaproveitando o resultado da sessão ou do cache em
+/// disco (`tools.json`, válido por `tools.cache_ttl_secs`).
+///
+/// As entradas são invalidadas quando o executável muda de caminho, tamanho ou
+/// data de modificação; ferramentas ausentes nunca são guardadas.
+pub fn detect(tool: Tool) -> Option<ToolInfo> {
+    let program = which(&tool.program())?;
+    let fingerprint = Fingerprint::of(&program)?;
+
+    if let Some(entry) = session_cache()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(tool.name())
+    {
+        if entry.matches(&program, &fingerprint) {
+            return Some(entry.info(tool));
+        }
+    }
+
+    let ttl = Config::load().tools.cache_ttl_secs;
+    let mut disk = DiskCache::load();
+    if let Some(entry) = disk.entries.get(tool.name()) {
+        if entry.matches(&program, &fingerprint)
+            && stats::now().saturating_sub(entry.checked_at) < ttl
+        {
+            let info = entry.info(tool);
+            session_cache()
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(tool.name().to_string(), entry.clone());
+            return Some(info);
+        }
+    }
+
+    let info = probe(tool, &program)?;
+    let entry = CacheEntry {
+        program,
+        fingerprint,
+        version: info.version.clone(),
+        checked_at: stats::now(),
+    };
+    session_cache()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(tool.name().to_string(), entry.clone());
+    disk.entries.insert(tool.name().to_string(), entry);
+    disk.save();
+    Some(info)
+}
+
+/// Detecta a ferramenta sempre executando-a (usado pelo `doctor`), atualizando os caches.
+pub fn detect_fresh(tool: Tool) -> Option<ToolInfo> {
+    forget(tool);
+    detect(tool)
+}
+
+/// Descarta o resultado guardado de uma ferramenta (sessão e disco).
+pub fn forget(tool: Tool) {
+    session_cache()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(tool.name());
+    let mut disk = DiskCache::load();
+    if disk.entries.remove(tool.name()).is_some() {
+        disk.save();
+    }
+}
+
+/// Esvazia o cache da sessão, como se o processo tivesse reiniciado.
+pub fn clear_session_cache() {
+    session_cache()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+}
+
+/// Executa a ferramenta pedindo a versão; `None` se ela não puder ser executada.
+fn probe(tool: Tool, program: &Path) -> Option<ToolInfo> {
+    let output = StdCommand::new(program)
+        .arg(tool.version_arg())
+        .output()
+        .ok()?;
+    // O Python 2 (e alguns wrappers) escrevem a versão no stderr
+    let text = if output.stdout.is_empty() {
+        String::from_utf8_lossy(&output.stderr).into_owned()
+    } else {
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+    Some(ToolInfo {
+        tool,
+        program: program.to_path_buf(),
+        version: parse_version(tool, &text),
+    })
+}
+
+/// Resolve um programa no PATH, como o sistema faria ao executá-lo.
+pub fn which(program: &Path) -> Option<PathBuf> {
+    if program.components().count() > 1 {
+        return program.is_file().then(|| program.to_path_buf());
+    }
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path).find_map(|dir| {
+        let candidate = dir.join(program);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        if cfg!(windows) {
+            let exe = candidate.with_extension("exe");
+            if exe.is_file() {
+                return Some(exe);
+            }
+        }
+        None
+    })
+}
+
+/// Identifica uma versão específica de um executável sem executá-lo.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Fingerprint {
+    size: u64,
+    modified_ns: u64,
+}
+
+impl Fingerprint {
+    fn of(program: &Path) -> Option<Fingerprint> {
+        let metadata = fs::metadata(program).ok()?;
+        let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(Fingerprint {
+            size: metadata.len(),
+            modified_ns: modified.as_nanos() as u64,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    program: PathBuf,
+    fingerprint: Fingerprint,
+    version: String,
+    checked_at: u64,
+}
+
+impl CacheEntry {
+    fn matches(&self, program: &Path, fingerprint: &Fingerprint) -> bool {
+        self.program == program && &self.fingerprint == fingerprint
+    }
+
+    fn info(&self, tool: Tool) -> ToolInfo {
+        ToolInfo {
+            tool,
+            program: self.program.clone(),
+            version: self.version.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DiskCache {
+    entries: HashMap<String, CacheEntry>,
+}
+
+impl DiskCache {
+    fn path() -> PathBuf {
+        paths::data_dir().join("tools.json")
+    }
+
+    fn load() -> DiskCache {
+        fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Falhas ao gravar o cache não impedem o download.
+    fn save(&self) {
+        if fs::create_dir_all(paths::data_dir()).is_ok() {
+            if let Ok(json) = serde_json::to_string_pretty(self) {
+                let _ = fs::write(Self::path(), json);
+            }
+        }
+    }
+}
+
+fn session_cache() -> &'static Mutex<HashMap<String, CacheEntry>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, CacheEntry>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Extrai a versão da saída de `--version`/`-version`.
+fn parse_version(tool: Tool, output: &str) -> String {
+    let first_line = output.lines().next().unwrap_or("").trim();
+    let version = match tool {
+        // "ffmpeg version 6.1.1-3ubuntu5 Copyright (c) ..."
+        Tool::Ffmpeg | Tool::Ffprobe => first_line
+            .split_whitespace()
+            .skip_while(|word| *word != "version")
+            .nth(1),
+        // "Python 3.12.3"
+        Tool::Python => first_line.split_whitespace().nth(1),
+        // "2024.05.27"
+        Tool::YtDlp => Some(first_line),
+    };
+    match version {
+        Some(version) if !version.is_empty() => version.to_string(),
+        _ => t!("tools.unknown_version"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(
+            parse_version(
+                Tool::Ffmpeg,
+                "ffmpeg version 6.1.1-3ubuntu5 Copyright (c) 2000-2023\nbuilt with gcc"
+            ),
+            "6.1.1-3ubuntu5"
+        );
+        assert_eq!(
+            parse_version(Tool::Ffprobe, "ffprobe version n7.0 Copyright"),
+            "n7.0"
+        );
+        assert_eq!(parse_version(Tool::Python, "Python 3.12.3\n"), "3.12.3");
+        assert_eq!(parse_version(Tool::YtDlp, "2024.05.27\n"), "2024.05.27");
+        assert_eq!(
+            parse_version(Tool::Ffmpeg, ""),
+            crate::i18n::tr("tools.unknown_version")
+        );
+    }
+
+    #[test]
+    fn test_which_with_explicit_path() {
+        assert_eq!(which(Path::new("/caminho/que/nao/existe/ffmpeg")), None);
+        let exe = std::env::current_exe().unwrap();
+        assert_eq!(which(&exe), Some(exe.clone()));
+    }
+}
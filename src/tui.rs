@@ -0,0 +1,668 @@
+//! Painel em tela cheia (`--tui`): lista de downloads com progresso, log do
+//! yt-dlp e uma barra de entrada para novas URLs.
+//!
+//! Cada download roda em uma tarefa própria e envia seus [`JobEvent`]s por um
+//! canal; a tela é redesenhada a cada evento ou tecla.
+
+use crate::shell::{self, JobSpec};
+use crossterm::event::{Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Color, Print, SetAttribute, SetForegroundColor};
+use crossterm::{cursor, execute, queue, terminal};
+use futures_util::StreamExt;
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use youtube_video_downloader::download::TITLE_OUTPUT;
+use youtube_video_downloader::{
+    download_with, is_youtube_url, t, DownloadError, DownloadOptions, Downloaded, JobControl,
+    JobEvent, Progress,
+};
+
+/// Linhas de log mantidas em memória.
+const LOG_LIMIT: usize = 500;
+/// Largura da barra de progresso de cada download.
+const BAR_WIDTH: usize = 20;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Status {
+    Running,
+    Paused,
+    Done,
+    Failed(String),
+    Cancelled,
+}
+
+impl Status {
+    fn label(&self) -> String {
+        match self {
+            Status::Running => t!("tui.status_running"),
+            Status::Paused => t!("tui.status_paused"),
+            Status::Done => t!("tui.status_done"),
+            Status::Failed(_) => t!("tui.status_failed"),
+            Status::Cancelled => t!("tui.status_cancelled"),
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Status::Running => Color::Cyan,
+            Status::Paused => Color::Yellow,
+            Status::Done => Color::Green,
+            Status::Failed(_) => Color::Red,
+            Status::Cancelled => Color::DarkGrey,
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        matches!(self, Status::Running | Status::Paused)
+    }
+}
+
+struct Job {
+    id: usize,
+    options: DownloadOptions,
+    status: Status,
+    progress: Progress,
+    control: JobControl,
+    path: Option<PathBuf>,
+}
+
+enum Message {
+    Event(usize, JobEvent),
+    Finished(usize, Result<Downloaded, DownloadError>),
+}
+
+/// O que uma tecla pede ao painel.
+#[derive(Debug, PartialEq)]
+enum Action {
+    Submit(String),
+    Pause,
+    Cancel,
+    Retry,
+    Open,
+    Quit,
+}
+
+struct App {
+    jobs: Vec<Job>,
+    logs: VecDeque<String>,
+    input: String,
+    selected: usize,
+    /// Aviso exibido no rodapé no lugar das teclas.
+    notice: Option<String>,
+    tx: UnboundedSender<Message>,
+}
+
+impl App {
+    fn new(tx: UnboundedSender<Message>) -> Self {
+        App {
+            jobs: Vec::new(),
+            logs: VecDeque::new(),
+            input: String::new(),
+            selected: 0,
+            notice: None,
+            tx,
+        }
+    }
+
+    /// Edita a entrada ou traduz a tecla em uma ação. As letras de atalho só
+    /// valem com a entrada vazia, para não atrapalhar a digitação de URLs.
+    fn on_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if key.kind != KeyEventKind::Press {
+            return None;
+        }
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return (key.code == KeyCode::Char('c')).then_some(Action::Quit);
+        }
+        self.notice = None;
+        match key.code {
+            KeyCode::Enter if !self.input.trim().is_empty() => {
+                Some(Action::Submit(std::mem::take(&mut self.input)))
+            }
+            KeyCode::Esc => {
+                self.input.clear();
+                None
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                None
+            }
+            KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                None
+            }
+            KeyCode::Down => {
+                if self.selected + 1 < self.jobs.len() {
+                    self.selected += 1;
+                }
+                None
+            }
+            KeyCode::Char(c) if self.input.is_empty() && "pcroq".contains(c) => Some(match c {
+                'p' => Action::Pause,
+                'c' => Action::Cancel,
+                'r' => Action::Retry,
+                'o' => Action::Open,
+                _ => Action::Quit,
+            }),
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn paste(&mut self, text: &str) {
+        self.input
+            .extend(text.chars().map(|c| if c.is_control() { ' ' } else { c }));
+    }
+
+    /// Executa uma ação; retorna `false` quando o painel deve fechar.
+    fn apply(&mut self, action: Action) -> bool {
+        match action {
+            Action::Submit(line) => self.submit(&line),
+            Action::Quit => return false,
+            action => {
+                let Some(job) = self.jobs.get_mut(self.selected) else {
+                    self.notice = Some(t!("tui.no_job"));
+                    return true;
+                };
+                match action {
+                    Action::Pause if job.status.is_active() => {
+                        let was_paused = job.status == Status::Paused;
+                        let paused = job.control.toggle_pause();
+                        job.status = if paused {
+                            Status::Paused
+                        } else {
+                            Status::Running
+                        };
+                        // Antes do yt-dlp iniciar não há processo para suspender
+                        if !was_paused && !paused {
+                            self.notice = Some(t!("tui.pause_unavailable"));
+                        }
+                    }
+                    Action::Cancel if job.status.is_active() => job.control.cancel(),
+                    Action::Retry if !job.status.is_active() && job.status != Status::Done => {
+                        let id = job.id;
+                        self.start(id);
+                    }
+                    Action::Open => {
+                        let dir = destination_dir(job);
+                        if let Err(e) = open_folder(&dir) {
+                            self.notice =
+                                Some(t!("tui.open_failed", path = dir.display(), error = e));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        true
+    }
+
+    fn submit(&mut self, line: &str) {
+        let spec = match shell::parse_job(line) {
+            Ok(Some(spec)) => spec,
+            Ok(None) => return,
+            Err(e) => {
+                self.notice = Some(e.to_string());
+                return;
+            }
+        };
+        if !is_youtube_url(&spec.url) {
+            self.notice = Some(t!("error.invalid_url"));
+            return;
+        }
+        let JobSpec {
+            url,
+            output,
+            audio_only,
+        } = spec;
+        // Sem `-o`, downloads simultâneos não podem dividir o mesmo nome
+        let output = output.unwrap_or_else(|| TITLE_OUTPUT.to_string());
+        let id = self.jobs.len() + 1;
+        self.jobs.push(Job {
+            id,
+            options: DownloadOptions::new(&url, &output, audio_only),
+            status: Status::Running,
+            progress: Progress::default(),
+            control: JobControl::new(),
+            path: None,
+        });
+        self.selected = self.jobs.len() - 1;
+        self.start(id);
+    }
+
+    /// Inicia (ou reinicia) o download em uma tarefa separada.
+    fn start(&mut self, id: usize) {
+        let job = &mut self.jobs[id - 1];
+        job.status = Status::Running;
+        job.progress = Progress::default();
+        job.control = JobControl::new();
+        let options = job.options.clone();
+        let control = job.control.clone();
+        let tx = self.tx.clone();
+        self.log(id, &options.url);
+        tokio::spawn(async move {
+            let events = tx.clone();
+            let result = download_with(&options, &control, move |event| {
+                let _ = events.send(Message::Event(id, event));
+            })
+            .await;
+            let _ = tx.send(Message::Finished(id, result));
+        });
+    }
+
+    fn on_message(&mut self, message: Message) {
+        match message {
+            Message::Event(id, JobEvent::Progress(progress)) => {
+                self.jobs[id - 1].progress = progress;
+            }
+            Message::Event(id, JobEvent::Status(line) | JobEvent::Log(line)) => {
+                self.log(id, &line);
+            }
+            Message::Finished(id, result) => {
+                let job = &mut self.jobs[id - 1];
+                job.status = match result {
+                    Ok(downloaded) => {
+                        job.progress.percent = 100.0;
+                        job.path = downloaded.path;
+                        Status::Done
+                    }
+                    Err(DownloadError::Cancelled) => Status::Cancelled,
+                    Err(e) => Status::Failed(e.to_string()),
+                };
+                if let Status::Failed(reason) = &job.status {
+                    let reason = reason.clone();
+                    self.log(id, &reason);
+                }
+            }
+        }
+    }
+
+    fn log(&mut self, id: usize, line: &str) {
+        for line in line.lines().filter(|line| !line.trim().is_empty()) {
+            if self.logs.len() == LOG_LIMIT {
+                self.logs.pop_front();
+            }
+            self.logs.push_back(format!("[#{id}] {line}"));
+        }
+    }
+
+    fn active_jobs(&self) -> usize {
+        self.jobs
+            .iter()
+            .filter(|job| job.status.is_active())
+            .count()
+    }
+}
+
+/// Pasta onde o download foi (ou será) salvo.
+fn destination_dir(job: &Job) -> PathBuf {
+    let file = job
+        .path
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(&job.options.output_name));
+    match file.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+    }
+}
+
+fn open_folder(dir: &Path) -> io::Result<()> {
+    let program = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(windows) {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    std::process::Command::new(program)
+        .arg(dir)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+/// Uma linha da tela: texto já cortado na largura e sua cor.
+type Line = (String, Option<Color>, bool);
+
+/// Monta o conteúdo da tela para um terminal de `width` x `height`.
+fn render(app: &App, width: usize, height: usize) -> Vec<Line> {
+    let mut lines: Vec<Line> = Vec::with_capacity(height);
+    let fit = |text: String| -> String { text.chars().take(width).collect() };
+
+    lines.push((
+        fit(format!(
+            " {}  ·  {}",
+            t!("tui.title"),
+            t!(
+                "tui.summary",
+                total = app.jobs.len(),
+                active = app.active_jobs()
+            )
+        )),
+        Some(Color::Cyan),
+        true,
+    ));
+    lines.push((
+        fit(format!(
+            "   {:>3}  {:<11} {:<width$} {:>6}  {:<12} {:<8} {}",
+            "#",
+            t!("tui.header_status"),
+            "",
+            "%",
+            t!("tui.header_speed"),
+            t!("tui.header_eta"),
+            t!("tui.header_target"),
+            width = BAR_WIDTH
+        )),
+        None,
+        true,
+    ));
+
+    // Metade do espaço livre para os downloads, o restante para o log
+    let free = height.saturating_sub(6);
+    let job_rows = (free / 2).max(1);
+    let first = (app.selected + 1).saturating_sub(job_rows);
+    if app.jobs.is_empty() {
+        lines.push((
+            fit(format!("   {}", t!("tui.empty"))),
+            Some(Color::DarkGrey),
+            false,
+        ));
+    }
+    for job in app.jobs.iter().skip(first).take(job_rows) {
+        let marker = if job.id == app.selected + 1 { '>' } else { ' ' };
+        let filled = ((job.progress.percent / 100.0) * BAR_WIDTH as f64).round() as usize;
+        let filled = filled.min(BAR_WIDTH);
+        let target = job
+            .path
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| job.options.url.clone());
+        lines.push((
+            fit(format!(
+                " {marker} {:>3}  {:<11} {}{} {:>5.1}%  {:<12} {:<8} {}",
+                job.id,
+                job.status.label(),
+                "█".repeat(filled),
+                "░".repeat(BAR_WIDTH - filled),
+                job.progress.percent,
+                job.progress.speed.as_deref().unwrap_or("-"),
+                job.progress.eta.as_deref().unwrap_or("-"),
+                target
+            )),
+            Some(job.status.color()),
+            job.id == app.selected + 1,
+        ));
+    }
+    while lines.len() < 2 + job_rows {
+        lines.push((String::new(), None, false));
+    }
+
+    lines.push((
+        fit(separator(&t!("tui.log"), width)),
+        Some(Color::DarkGrey),
+        false,
+    ));
+    let log_rows = height.saturating_sub(lines.len() + 3);
+    let skip = app.logs.len().saturating_sub(log_rows);
+    for line in app.logs.iter().skip(skip) {
+        lines.push((fit(line.clone()), None, false));
+    }
+    while lines.len() < height.saturating_sub(3) {
+        lines.push((String::new(), None, false));
+    }
+
+    lines.push((fit(separator("", width)), Some(Color::DarkGrey), false));
+    lines.push((
+        fit(format!("{}{}", t!("tui.prompt"), app.input)),
+        None,
+        true,
+    ));
+    match &app.notice {
+        Some(notice) => lines.push((fit(notice.clone()), Some(Color::Red), false)),
+        None => lines.push((fit(t!("tui.keys")), Some(Color::DarkGrey), false)),
+    }
+    lines.truncate(height);
+    lines
+}
+
+fn separator(title: &str, width: usize) -> String {
+    let title = if title.is_empty() {
+        String::new()
+    } else {
+        format!("─ {title} ")
+    };
+    let used = title.chars().count();
+    format!("{title}{}", "─".repeat(width.saturating_sub(used)))
+}
+
+fn draw(out: &mut impl Write, app: &App) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let lines = render(app, width as usize, height as usize);
+    queue!(out, cursor::Hide)?;
+    for (row, (text, color, bold)) in lines.iter().enumerate() {
+        queue!(
+            out,
+            cursor::MoveTo(0, row as u16),
+            terminal::Clear(terminal::ClearType::CurrentLine)
+        )?;
+        if let Some(color) = color {
+            queue!(out, SetForegroundColor(*color))?;
+        }
+        if *bold {
+            queue!(out, SetAttribute(Attribute::Bold))?;
+        }
+        queue!(out, Print(text), SetAttribute(Attribute::Reset))?;
+    }
+    // Cursor no fim da barra de entrada
+    let column = t!("tui.prompt").chars().count() + app.input.chars().count();
+    queue!(
+        out,
+        cursor::MoveTo(
+            column.min(width.saturating_sub(1) as usize) as u16,
+            height.saturating_sub(2)
+        ),
+        cursor::Show
+    )?;
+    out.flush()
+}
+
+/// Coloca o terminal em modo de tela cheia e o restaura ao ser descartado,
+/// inclusive em caso de pânico.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(
+            io::stdout(),
+            terminal::EnterAlternateScreen,
+            terminal::DisableLineWrap,
+            crossterm::event::EnableBracketedPaste
+        )?;
+        // As mensagens de status vêm coloridas para o terminal comum
+        colored::control::set_override(false);
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        colored::control::unset_override();
+        let _ = execute!(
+            io::stdout(),
+            crossterm::event::DisableBracketedPaste,
+            terminal::EnableLineWrap,
+            terminal::LeaveAlternateScreen,
+            cursor::Show
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Abre o painel e só retorna quando o usuário sai.
+pub async fn run() -> io::Result<()> {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut app = App::new(tx);
+    let mut events = EventStream::new();
+    let guard = TerminalGuard::enter()?;
+    let mut out = io::stdout();
+
+    loop {
+        draw(&mut out, &app)?;
+        tokio::select! {
+            event = events.next() => match event {
+                Some(Ok(Event::Key(key))) => {
+                    if let Some(action) = app.on_key(key) {
+                        if !app.apply(action) {
+                            break;
+                        }
+                    }
+                }
+                Some(Ok(Event::Paste(text))) => app.paste(&text),
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e),
+                None => break,
+            },
+            Some(message) = rx.recv() => app.on_message(message),
+        }
+    }
+
+    drop(guard);
+    shutdown(&mut app, &mut rx).await;
+    Ok(())
+}
+
+/// Cancela os downloads em andamento e espera que terminem.
+async fn shutdown(app: &mut App, rx: &mut UnboundedReceiver<Message>) {
+    if app.active_jobs() == 0 {
+        return;
+    }
+    println!("{}", t!("tui.stopping"));
+    for job in app.jobs.iter().filter(|job| job.status.is_active()) {
+        job.control.cancel();
+    }
+    let wait = async {
+        while app.active_jobs() > 0 {
+            match rx.recv().await {
+                Some(message) => app.on_message(message),
+                None => break,
+            }
+        }
+    };
+    let _ = tokio::time::timeout(Duration::from_secs(5), wait).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn app() -> App {
+        App::new(mpsc::unbounded_channel().0)
+    }
+
+    fn job(id: usize, status: Status) -> Job {
+        Job {
+            id,
+            options: DownloadOptions::new("https://youtu.be/abc", "/tmp/saida/video", false),
+            status,
+            progress: Progress {
+                percent: 50.0,
+                speed: Some("1.00MiB/s".into()),
+                eta: Some("00:05".into()),
+                total: None,
+            },
+            control: JobControl::new(),
+            path: None,
+        }
+    }
+
+    #[test]
+    fn test_shortcuts_only_apply_with_empty_input() {
+        let mut app = app();
+        assert_eq!(app.on_key(key(KeyCode::Char('p'))), Some(Action::Pause));
+        assert_eq!(app.on_key(key(KeyCode::Char('q'))), Some(Action::Quit));
+
+        for c in "https://youtu.be/p".chars() {
+            assert_eq!(app.on_key(key(KeyCode::Char(c))), None);
+        }
+        assert_eq!(app.input, "https://youtu.be/p");
+        assert_eq!(app.on_key(key(KeyCode::Backspace)), None);
+        assert_eq!(
+            app.on_key(key(KeyCode::Enter)),
+            Some(Action::Submit("https://youtu.be/".into()))
+        );
+        assert!(app.input.is_empty());
+        assert_eq!(app.on_key(key(KeyCode::Enter)), None);
+        assert_eq!(
+            app.on_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(Action::Quit)
+        );
+    }
+
+    #[test]
+    fn test_invalid_input_shows_notice() {
+        let mut app = app();
+        assert!(app.apply(Action::Submit("https://vimeo.com/123".into())));
+        assert_eq!(app.notice, Some(t!("error.invalid_url")));
+        assert!(app.apply(Action::Submit("https://youtu.be/abc -o".into())));
+        assert!(app.notice.is_some());
+        assert!(app.jobs.is_empty());
+        assert!(app.apply(Action::Pause));
+        assert_eq!(app.notice, Some(t!("tui.no_job")));
+    }
+
+    #[test]
+    fn test_finished_jobs_update_status_and_log() {
+        let mut app = app();
+        app.jobs.push(job(1, Status::Running));
+        app.jobs.push(job(2, Status::Running));
+        app.on_message(Message::Event(
+            1,
+            JobEvent::Log("[download] Destination: x".into()),
+        ));
+        app.on_message(Message::Finished(
+            1,
+            Ok(Downloaded {
+                path: Some("/tmp/saida/video.mp4".into()),
+                ..Downloaded::default()
+            }),
+        ));
+        app.on_message(Message::Finished(2, Err(DownloadError::Cancelled)));
+
+        assert_eq!(app.jobs[0].status, Status::Done);
+        assert_eq!(app.jobs[0].progress.percent, 100.0);
+        assert_eq!(destination_dir(&app.jobs[0]), PathBuf::from("/tmp/saida"));
+        assert_eq!(app.jobs[1].status, Status::Cancelled);
+        assert_eq!(app.logs, vec!["[#1] [download] Destination: x".to_string()]);
+        assert_eq!(app.active_jobs(), 0);
+    }
+
+    #[test]
+    fn test_render_fits_the_terminal() {
+        let mut app = app();
+        app.jobs.push(job(1, Status::Paused));
+        for i in 0..50 {
+            app.log(1, &format!("linha {i}"));
+        }
+        let lines = render(&app, 60, 20);
+        assert_eq!(lines.len(), 20);
+        assert!(lines.iter().all(|(text, ..)| text.chars().count() <= 60));
+        assert!(lines[2].0.contains(&t!("tui.status_paused")));
+        assert!(lines[2].0.contains("50.0%"));
+        assert!(lines.iter().any(|(text, ..)| text.ends_with("linha 49")));
+        assert!(lines[18].0.starts_with(&t!("tui.prompt")));
+    }
+}
@@ -0,0 +1,279 @@
+//! Verificação do arquivo baixado com o ffprobe.
+//!
+//! O status de saída do yt-dlp não garante um arquivo íntegro: um MP4 pode
+//! ficar truncado ou sem a faixa de áudio. Depois de cada download, o ffprobe
+//! confere se o contêiner abre, se as faixas esperadas existem e se a duração
+//! bate com a dos metadados do vídeo.
+
+use crate::config::VerifyConfig;
+use crate::tools::{self, Tool};
+use serde::Deserialize;
+use std::fmt;
+use std::path::Path;
+use tokio::process::Command;
+
+/// O que o ffprobe encontrou no arquivo.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaInfo {
+    /// Duração do contêiner.
+    pub duration_secs: Option<f64>,
+    pub video: Option<VideoStream>,
+    /// Codec da primeira faixa de áudio.
+    pub audio_codec: Option<String>,
+    /// Bitrate da primeira faixa de áudio, em bits por segundo.
+    pub audio_bitrate: Option<u64>,
+    /// Duração de cada faixa que a informa.
+    pub stream_durations: Vec<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VideoStream {
+    pub codec: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl MediaInfo {
+    /// `1920x1080 h264 + aac` ou só `mp3`, para a mensagem de sucesso.
+    pub fn summary(&self) -> String {
+        let video = self
+            .video
+            .as_ref()
+            .map(|video| format!("{}x{} {}", video.width, video.height, video.codec));
+        match (video, &self.audio_codec) {
+            (Some(video), Some(audio)) => format!("{video} + {audio}"),
+            (Some(video), None) => video,
+            (None, Some(audio)) => audio.clone(),
+            (None, None) => "-".to_string(),
+        }
+    }
+}
+
+/// Saída de `ffprobe -print_format json -show_format -show_streams`.
+#[derive(Deserialize)]
+struct Probe {
+    #[serde(default)]
+    streams: Vec<ProbeStream>,
+    #[serde(default)]
+    format: Option<ProbeFormat>,
+}
+
+#[derive(Deserialize)]
+struct ProbeStream {
+    #[serde(default)]
+    codec_type: Option<String>,
+    #[serde(default)]
+    codec_name: Option<String>,
+    #[serde(default)]
+    width: Option<u32>,
+    #[serde(default)]
+    height: Option<u32>,
+    /// O ffprobe informa números decimais como texto.
+    #[serde(default)]
+    duration: Option<String>,
+    #[serde(default)]
+    bit_rate: Option<String>,
+    #[serde(default)]
+    disposition: Option<Disposition>,
+}
+
+#[derive(Deserialize)]
+struct Disposition {
+    #[serde(default)]
+    attached_pic: u8,
+}
+
+#[derive(Deserialize)]
+struct ProbeFormat {
+    #[serde(default)]
+    duration: Option<String>,
+}
+
+/// Por que o arquivo foi recusado.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    /// O ffprobe não conseguiu ler o arquivo, como num MP4 truncado.
+    Unreadable(String),
+    MissingVideo,
+    MissingAudio,
+    Duration {
+        expected: f64,
+        actual: f64,
+    },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Problem::Unreadable(error) => t!("verify.unreadable", error = error),
+            Problem::MissingVideo => t!("verify.missing_video"),
+            Problem::MissingAudio => t!("verify.missing_audio"),
+            Problem::Duration { expected, actual } => t!(
+                "verify.duration",
+                actual = format!("{actual:.1}"),
+                expected = format!("{expected:.1}")
+            ),
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// Lê o arquivo com o ffprobe. `Ok(None)` se o ffprobe não está instalado.
+pub async fn probe(path: &Path) -> Result<Option<MediaInfo>, Problem> {
+    let Some(ffprobe) = tools::detect(Tool::Ffprobe) else {
+        return Ok(None);
+    };
+    let output = Command::new(&ffprobe.program)
+        .args([
+            "-v",
+            "error",
+            "-print_format",
+            "json",
+            "-show_format",
+            "-show_streams",
+        ])
+        .arg(path)
+        .output()
+        .await
+        .map_err(|e| Problem::Unreadable(e.to_string()))?;
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if !output.status.success() {
+        return Err(Problem::Unreadable(stderr));
+    }
+    parse_probe(&String::from_utf8_lossy(&output.stdout))
+        .map(Some)
+        .ok_or(Problem::Unreadable(stderr))
+}
+
+fn parse_probe(stdout: &str) -> Option<MediaInfo> {
+    let probe: Probe = serde_json::from_str(stdout).ok()?;
+    let seconds = |text: &Option<String>| text.as_deref().and_then(|t| t.parse::<f64>().ok());
+    // Capas embutidas no MP3 aparecem como faixas de vídeo
+    let is_cover = |stream: &ProbeStream| {
+        stream
+            .disposition
+            .as_ref()
+            .is_some_and(|d| d.attached_pic != 0)
+    };
+    let video = probe
+        .streams
+        .iter()
+        .filter(|stream| stream.codec_type.as_deref() == Some("video") && !is_cover(stream))
+        .find_map(|stream| {
+            Some(VideoStream {
+                codec: stream.codec_name.clone().unwrap_or_default(),
+                width: stream.width?,
+                height: stream.height?,
+            })
+        });
+    let audio = probe
+        .streams
+        .iter()
+        .find(|stream| stream.codec_type.as_deref() == Some("audio"));
+    let audio_codec = audio.map(|stream| stream.codec_name.clone().unwrap_or_default());
+    let audio_bitrate = audio
+        .and_then(|stream| stream.bit_rate.as_deref())
+        .and_then(|text| text.parse().ok());
+    Some(MediaInfo {
+        duration_secs: probe.format.as_ref().and_then(|f| seconds(&f.duration)),
+        stream_durations: probe
+            .streams
+            .iter()
+            .filter(|stream| !is_cover(stream))
+            .filter_map(|stream| seconds(&stream.duration))
+            .collect(),
+        video,
+        audio_codec,
+        audio_bitrate,
+    })
+}
+
+/// Confere as faixas esperadas e, com a duração dos metadados, a do
+/// contêiner e a de cada faixa.
+pub fn check(
+    info: &MediaInfo,
+    audio_only: bool,
+    expected_secs: Option<f64>,
+    config: &VerifyConfig,
+) -> Result<(), Problem> {
+    if !audio_only && info.video.is_none() {
+        return Err(Problem::MissingVideo);
+    }
+    if info.audio_codec.is_none() {
+        return Err(Problem::MissingAudio);
+    }
+    let Some(expected) = expected_secs else {
+        return Ok(());
+    };
+    let durations = info.duration_secs.iter().chain(&info.stream_durations);
+    for &actual in durations {
+        if (actual - expected).abs() > config.duration_tolerance_secs {
+            return Err(Problem::Duration { expected, actual });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROBE: &str = r#"{
+        "streams": [
+            {"codec_type": "video", "codec_name": "h264", "width": 1920, "height": 1080, "duration": "187.020000"},
+            {"codec_type": "audio", "codec_name": "aac", "duration": "186.990000"}
+        ],
+        "format": {"duration": "187.040000"}
+    }"#;
+
+    #[test]
+    fn test_parse_and_check() {
+        let info = parse_probe(PROBE).unwrap();
+        assert_eq!(info.summary(), "1920x1080 h264 + aac");
+        assert_eq!(info.duration_secs, Some(187.04));
+        let config = VerifyConfig::default();
+        assert_eq!(check(&info, false, Some(187.0), &config), Ok(()));
+        assert_eq!(check(&info, false, None, &config), Ok(()));
+        assert!(matches!(
+            check(&info, false, Some(600.0), &config),
+            Err(Problem::Duration { .. })
+        ));
+
+        // Áudio truncado: o contêiner tem a duração certa, a faixa não
+        let truncated = MediaInfo {
+            stream_durations: vec![187.0, 42.0],
+            ..info.clone()
+        };
+        assert!(check(&truncated, false, Some(187.0), &config).is_err());
+
+        let silent = MediaInfo {
+            audio_codec: None,
+            ..info
+        };
+        assert_eq!(
+            check(&silent, false, None, &config),
+            Err(Problem::MissingAudio)
+        );
+    }
+
+    #[test]
+    fn test_cover_art_is_not_video() {
+        let info = parse_probe(
+            r#"{"streams": [
+                {"codec_type": "audio", "codec_name": "mp3", "duration": "60.0"},
+                {"codec_type": "video", "codec_name": "mjpeg", "width": 600, "height": 600, "disposition": {"attached_pic": 1}}
+            ], "format": {"duration": "60.0"}}"#,
+        )
+        .unwrap();
+        assert_eq!(info.video, None);
+        assert_eq!(info.summary(), "mp3");
+        assert_eq!(
+            check(&info, false, None, &VerifyConfig::default()),
+            Err(Problem::MissingVideo)
+        );
+        assert_eq!(
+            check(&info, true, Some(60.0), &VerifyConfig::default()),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,95 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use std::fs;
+use youtube_video_downloader::access::AccessOptions;
+use youtube_video_downloader::{download, DownloadError, DownloadOptions};
+
+const URL: &str = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+const COOKIES: &str = "# Netscape HTTP Cookie File\n\
+    .youtube.com\tTRUE\t/\tTRUE\t1999999999\tSID\tsegredo-da-sessao\n\
+    #HttpOnly_.youtube.com\tTRUE\t/\tTRUE\t0\tHSID\toutro-segredo\n";
+
+#[tokio::test]
+async fn cookies_proxy_and_user_agent_reach_the_backend() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), FakeTool::ffmpeg()]);
+    let cookies = env.root().join("cookies.txt");
+    fs::write(&cookies, COOKIES).unwrap();
+    let options = DownloadOptions {
+        access: AccessOptions {
+            cookies: Some(cookies.clone()),
+            proxy: Some("socks5://127.0.0.1:1080".into()),
+            user_agent: Some("Navegador/1.0".into()),
+        },
+        ..DownloadOptions::new(URL, &env.output("x"), false)
+    };
+
+    download(&options).await.unwrap();
+
+    let args = env.last_job("yt-dlp").unwrap();
+    for pair in [
+        ["--cookies", &cookies.display().to_string()],
+        ["--proxy", "socks5://127.0.0.1:1080"],
+        ["--user-agent", "Navegador/1.0"],
+    ] {
+        assert!(args.windows(2).any(|w| w == pair), "faltou {pair:?}");
+    }
+}
+
+#[tokio::test]
+async fn invalid_cookies_fail_before_the_backend_runs() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), FakeTool::ffmpeg()]);
+    let cookies = env.root().join("cookies.txt");
+    fs::write(&cookies, "SID=segredo-da-sessao; HSID=outro\n").unwrap();
+    let options = DownloadOptions {
+        access: AccessOptions {
+            cookies: Some(cookies),
+            ..AccessOptions::default()
+        },
+        ..DownloadOptions::new(URL, &env.output("x"), false)
+    };
+
+    let err = download(&options).await.unwrap_err();
+
+    assert!(matches!(err, DownloadError::Access(_)));
+    assert!(!err.to_string().contains("segredo"));
+    assert_eq!(env.last_job("yt-dlp"), None);
+}
+
+#[tokio::test]
+async fn profile_from_config_is_applied() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), FakeTool::ffmpeg()]);
+    env.write_config(
+        r#"{"provision": {"confirm": "never"},
+            "profile": "escritorio",
+            "profiles": {"escritorio": {"proxy": "http://proxy.local:3128"}}}"#,
+    );
+
+    download(&DownloadOptions::new(URL, &env.output("x"), false))
+        .await
+        .unwrap();
+
+    let args = env.last_job("yt-dlp").unwrap();
+    assert!(args
+        .windows(2)
+        .any(|w| w == ["--proxy", "http://proxy.local:3128"]));
+}
+
+#[tokio::test]
+async fn cookie_headers_are_redacted_from_backend_errors() {
+    let env = FakeEnv::new(&[
+        FakeTool::yt_dlp()
+            .stderr("[debug] Cookie: SID=segredo-da-sessao\nERROR: Private video\n")
+            .exit_code(1),
+        FakeTool::ffmpeg(),
+    ]);
+
+    let err = download(&DownloadOptions::new(URL, &env.output("x"), false))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, DownloadError::Backend { .. }));
+    assert!(!err.to_string().contains("segredo"));
+}
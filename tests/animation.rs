@@ -0,0 +1,103 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use std::path::PathBuf;
+use youtube_video_downloader::animation::{self, AnimationOptions, Format};
+use youtube_video_downloader::clip::Section;
+use youtube_video_downloader::{archive, journal, stats};
+
+const URL: &str = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+
+/// ffmpeg falso que grava a animação, sempre o último argumento.
+fn ffmpeg() -> FakeTool {
+    FakeTool::ffmpeg().run(
+        r#"for a; do out="$a"; done; case "$out" in *.gif|*.webp) printf animacao > "$out";; esac"#,
+    )
+}
+
+#[tokio::test]
+async fn gif_downloads_only_the_section() {
+    // O ffprobe vê os 12 s do trecho, não os 10 minutos do vídeo
+    let env = FakeEnv::new(&[
+        FakeTool::yt_dlp()
+            .creates_output("mp4")
+            .metadata("Canal", 600.0),
+        ffmpeg(),
+        FakeTool::new("ffprobe").stdout(
+            r#"{"streams": [{"codec_type": "video", "codec_name": "h264", "width": 1280, "height": 720}, {"codec_type": "audio", "codec_name": "aac"}], "format": {"duration": "12.0"}}"#,
+        ),
+    ]);
+    let section = Section::new(83, 95).unwrap();
+
+    let path = animation::export(
+        URL,
+        section,
+        &env.output("demo"),
+        &AnimationOptions::default(),
+        |_| {},
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(path, PathBuf::from(env.output("demo.gif")));
+    assert!(path.exists());
+    // O trecho baixado não fica para trás
+    assert!(!env.out_dir().join("demo.clip.mp4").exists());
+
+    let download = env.last_job("yt-dlp").unwrap();
+    assert!(download
+        .windows(2)
+        .any(|w| w == ["--download-sections", "*83-95"]));
+    let render = env.last_job("ffmpeg").unwrap();
+    assert_eq!(render[render.len() - 2..], ["-an", &env.output("demo.gif")]);
+    assert!(render.iter().any(|arg| arg.contains("palettegen")));
+}
+
+#[tokio::test]
+async fn webp_is_an_alternative_format() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp().creates_output("mp4"), ffmpeg()]);
+    let options = AnimationOptions {
+        format: Format::Webp,
+        ..AnimationOptions::default()
+    };
+
+    let path = animation::export(
+        URL,
+        Section::new(0, 5).unwrap(),
+        &env.output("demo"),
+        &options,
+        |_| {},
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(path, PathBuf::from(env.output("demo.webp")));
+    let render = env.last_job("ffmpeg").unwrap();
+    assert!(render.windows(2).any(|w| w == ["-c:v", "libwebp"]));
+    assert!(!render.iter().any(|arg| arg.contains("palettegen")));
+}
+
+#[tokio::test]
+async fn clip_is_not_counted_as_a_downloaded_video() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp().creates_output("mp4"), ffmpeg()]);
+    archive::add(["aaaaaaaaaaa"]).unwrap();
+    let before = std::fs::read_to_string(archive::path()).unwrap();
+
+    animation::export(
+        URL,
+        Section::new(0, 5).unwrap(),
+        &env.output("demo"),
+        &AnimationOptions::default(),
+        |_| {},
+    )
+    .await
+    .unwrap();
+
+    // Exportar um GIF não faz o vídeo passar por baixado
+    assert_eq!(std::fs::read_to_string(archive::path()).unwrap(), before);
+    assert!(stats::load().is_empty());
+    assert!(journal::unfinished().is_empty());
+    assert!(!journal::path().exists());
+}
@@ -0,0 +1,35 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use youtube_video_downloader::bandwidth::{self, Limit};
+use youtube_video_downloader::download_video;
+
+const URL: &str = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+
+fn limit_arg(args: &[String]) -> Option<&str> {
+    args.iter()
+        .position(|arg| arg == "--limit-rate")
+        .map(|index| args[index + 1].as_str())
+}
+
+#[tokio::test]
+async fn schedule_and_throttle_set_the_rate_of_new_jobs() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), FakeTool::ffmpeg()]);
+    // Uma janela de 00:00 a 00:00 cobre o dia todo
+    env.write_config(
+        r#"{"provision": {"confirm": "never"},
+            "bandwidth": {"limit_rate": "unlimited",
+                          "schedule": [{"from": "00:00", "to": "00:00", "limit_rate": "1M"}]}}"#,
+    );
+
+    download_video(URL, &env.output("a"), false).await.unwrap();
+    assert_eq!(limit_arg(&env.last_job("yt-dlp").unwrap()), Some("1048576"));
+
+    // O `throttle` da sessão vence a configuração
+    bandwidth::set_override(Some(Limit::Unlimited));
+    download_video(URL, &env.output("b"), false).await.unwrap();
+    bandwidth::set_override(None);
+    assert_eq!(limit_arg(&env.last_job("yt-dlp").unwrap()), None);
+}
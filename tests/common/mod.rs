@@ -0,0 +1,284 @@
+//! Ambiente hermético para testes de integração: coloca scripts falsos de
+//! `yt-dlp`, `ffmpeg` e afins em um PATH isolado e registra como foram chamados.
+
+#![allow(dead_code)]
+
+use std::ffi::OsString;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use tempfile::TempDir;
+
+/// O PATH é global ao processo; testes que o alteram precisam ser serializados.
+static PATH_LOCK: Mutex<()> = Mutex::new(());
+
+/// Descrição do comportamento de uma ferramenta falsa.
+#[derive(Clone)]
+pub struct FakeTool {
+    name: String,
+    version: String,
+    progress: Vec<f32>,
+    json: Option<String>,
+    stdout: String,
+    stderr: String,
+    exit_code: i32,
+    output_ext: Option<String>,
+    channel: String,
+    duration: String,
+    run: Option<String>,
+}
+
+impl FakeTool {
+    pub fn new(name: &str) -> Self {
+        FakeTool {
+            name: name.to_string(),
+            version: "2024.01.01".to_string(),
+            progress: Vec::new(),
+            json: None,
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: 0,
+            output_ext: None,
+            channel: "NA".to_string(),
+            duration: "NA".to_string(),
+            run: None,
+        }
+    }
+
+    /// yt-dlp falso que cria o arquivo indicado por `-o` e emite progresso.
+    pub fn yt_dlp() -> Self {
+        FakeTool::new("yt-dlp")
+            .progress(&[0.0, 42.5, 100.0])
+            .creates_output("mp3")
+    }
+
+    pub fn ffmpeg() -> Self {
+        FakeTool::new("ffmpeg").version("ffmpeg version 6.1-fake")
+    }
+
+    pub fn version(mut self, version: &str) -> Self {
+        self.version = version.to_string();
+        self
+    }
+
+    /// Linhas de progresso no formato do yt-dlp (`[download]  42.5% ...`).
+    pub fn progress(mut self, percents: &[f32]) -> Self {
+        self.progress = percents.to_vec();
+        self
+    }
+
+    /// JSON impresso quando a ferramenta é chamada com `-j`/`--dump-json`.
+    pub fn json(mut self, json: &str) -> Self {
+        self.json = Some(json.to_string());
+        self
+    }
+
+    pub fn stdout(mut self, text: &str) -> Self {
+        self.stdout = text.to_string();
+        self
+    }
+
+    pub fn stderr(mut self, text: &str) -> Self {
+        self.stderr = text.to_string();
+        self
+    }
+
+    pub fn exit_code(mut self, code: i32) -> Self {
+        self.exit_code = code;
+        self
+    }
+
+    /// Cria o arquivo passado em `-o`, trocando `%(ext)s` pela extensão dada.
+    pub fn creates_output(mut self, ext: &str) -> Self {
+        self.output_ext = Some(ext.to_string());
+        self
+    }
+
+    /// Canal e duração informados na linha `--print after_move:...` do yt-dlp.
+    pub fn metadata(mut self, channel: &str, duration_secs: f64) -> Self {
+        self.channel = channel.to_string();
+        self.duration = duration_secs.to_string();
+        self
+    }
+
+    /// Trecho de shell executado antes de sair (ex.: simular uma instalação).
+    pub fn run(mut self, shell: &str) -> Self {
+        self.run = Some(shell.to_string());
+        self
+    }
+
+    fn script(&self, log: &Path) -> String {
+        let log = quote(&log.display().to_string());
+        let mut script = String::from("#!/bin/sh\n");
+        script.push_str(&format!("printf '%s\\037' \"$@\" >> {log}\n"));
+        script.push_str(&format!("printf '\\n' >> {log}\n"));
+        script.push_str(&format!(
+            "case \"$1\" in --version|-version) printf '%s\\n' {}; exit 0;; esac\n",
+            quote(&self.version)
+        ));
+        if let Some(json) = &self.json {
+            script.push_str(&format!(
+                "for a in \"$@\"; do case \"$a\" in -j|--dump-json|--dump-single-json) printf '%s\\n' {}; exit 0;; esac; done\n",
+                quote(json)
+            ));
+        }
+        for percent in &self.progress {
+            script.push_str(&format!(
+                "printf '%s\\n' '[download] {percent:>5.1}% of 10.00MiB at 1.00MiB/s ETA 00:05'\n"
+            ));
+        }
+        if !self.stdout.is_empty() {
+            script.push_str(&format!("printf '%s' {}\n", quote(&self.stdout)));
+        }
+        if !self.stderr.is_empty() {
+            script.push_str(&format!("printf '%s' {} >&2\n", quote(&self.stderr)));
+        }
+        if let (Some(ext), 0) = (&self.output_ext, self.exit_code) {
+            script.push_str("out=''; prev=''\n");
+            script.push_str(
+                "for a in \"$@\"; do [ \"$prev\" = '-o' ] && out=\"$a\"; prev=\"$a\"; done\n",
+            );
+            script.push_str(&format!(
+                "case \"$out\" in *'%(ext)s') out=\"${{out%???????}}{ext}\";; esac\n"
+            ));
+            script.push_str("[ -n \"$out\" ] && printf 'fake media' > \"$out\"\n");
+            // Emula `--print after_move:ytd-meta\t%(channel)s\t%(duration)s\t%(filepath)s`
+            script.push_str(&format!(
+                "for a in \"$@\"; do case \"$a\" in after_move:ytd-meta*) printf 'ytd-meta\\t%s\\t%s\\t%s\\n' {} {} \"$out\";; esac; done\n",
+                quote(&self.channel),
+                quote(&self.duration)
+            ));
+        }
+        if let Some(run) = &self.run {
+            script.push_str(run);
+            script.push('\n');
+        }
+        script.push_str(&format!("exit {}\n", self.exit_code));
+        script
+    }
+}
+
+/// Ambiente com PATH isolado contendo apenas as ferramentas falsas.
+///
+/// Restaura o PATH original ao ser descartado.
+pub struct FakeEnv {
+    dir: TempDir,
+    old_vars: Vec<(&'static str, Option<OsString>)>,
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl FakeEnv {
+    pub fn new(tools: &[FakeTool]) -> Self {
+        let guard = PATH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = TempDir::new().expect("falha ao criar diretório temporário");
+        let bin = dir.path().join("bin");
+        fs::create_dir_all(&bin).unwrap();
+        fs::create_dir_all(dir.path().join("out")).unwrap();
+
+        let env = FakeEnv {
+            old_vars: ["PATH", "YTD_DATA_DIR", "YTD_CONFIG_DIR", "YTD_ASSUME_YES"]
+                .into_iter()
+                .map(|var| (var, std::env::var_os(var)))
+                .collect(),
+            dir,
+            _guard: guard,
+        };
+
+        for tool in tools {
+            env.write_tool(&bin, tool);
+        }
+
+        std::env::set_var("PATH", &bin);
+        std::env::set_var("YTD_DATA_DIR", env.data_dir());
+        std::env::set_var("YTD_CONFIG_DIR", env.root().join("config"));
+        std::env::remove_var("YTD_ASSUME_YES");
+        // Por padrão nunca instalar nada: o teste decide quando provisionar.
+        env.write_config(r#"{"provision": {"confirm": "never"}}"#);
+        env
+    }
+
+    /// Grava o `config.json` usado pela aplicação durante o teste.
+    pub fn write_config(&self, json: &str) {
+        let dir = self.root().join("config");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("config.json"), json).unwrap();
+    }
+
+    /// Acrescenta uma ferramenta falsa ao PATH depois de criado o ambiente.
+    pub fn add_tool(&self, tool: &FakeTool) {
+        self.write_tool(&self.root().join("bin"), tool);
+    }
+
+    /// Cria uma ferramenta falsa fora do PATH (para ser "instalada" por outra)
+    /// e devolve o caminho do executável.
+    pub fn payload(&self, tool: &FakeTool) -> PathBuf {
+        let dir = self.root().join("payload");
+        fs::create_dir_all(&dir).unwrap();
+        self.write_tool(&dir, tool)
+    }
+
+    pub fn data_dir(&self) -> PathBuf {
+        self.root().join("data")
+    }
+
+    fn write_tool(&self, dir: &Path, tool: &FakeTool) -> PathBuf {
+        let path = dir.join(&tool.name);
+        let log = self.root().join(format!("{}.log", tool.name));
+        fs::write(&path, tool.script(&log)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    /// Caminho (sem extensão) dentro do diretório de saída do teste.
+    pub fn output(&self, name: &str) -> String {
+        self.dir.path().join("out").join(name).display().to_string()
+    }
+
+    pub fn out_dir(&self) -> PathBuf {
+        self.dir.path().join("out")
+    }
+
+    pub fn root(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Argumentos de cada chamada feita à ferramenta, em ordem.
+    pub fn invocations(&self, tool: &str) -> Vec<Vec<String>> {
+        let log = self.dir.path().join(format!("{tool}.log"));
+        let content = fs::read_to_string(log).unwrap_or_default();
+        content
+            .lines()
+            .map(|line| {
+                line.split('\x1f')
+                    .filter(|arg| !arg.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// A última chamada que não foi uma verificação de versão.
+    pub fn last_job(&self, tool: &str) -> Option<Vec<String>> {
+        self.invocations(tool)
+            .into_iter()
+            .rev()
+            .find(|args| !args.iter().any(|a| a == "--version" || a == "-version"))
+    }
+}
+
+impl Drop for FakeEnv {
+    fn drop(&mut self) {
+        for (var, value) in &self.old_vars {
+            match value {
+                Some(value) => std::env::set_var(var, value),
+                None => std::env::remove_var(var),
+            }
+        }
+    }
+}
+
+/// Envolve um texto em aspas simples para o shell.
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
@@ -0,0 +1,54 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use youtube_video_downloader::config::DoctorConfig;
+use youtube_video_downloader::doctor::{self, Severity};
+
+#[test]
+fn reports_versions_of_every_tool() {
+    let env = FakeEnv::new(&[
+        FakeTool::new("yt-dlp").version("2099.01.01"),
+        FakeTool::ffmpeg(),
+        FakeTool::new("ffprobe").version("ffprobe version 6.1-fake Copyright"),
+        FakeTool::new("python3").version("Python 3.12.3"),
+    ]);
+
+    let report = doctor::diagnose(&env.out_dir(), &DoctorConfig::default());
+
+    assert_eq!(report.check("yt-dlp").unwrap().detail, "2099.01.01");
+    assert_eq!(report.check("ffmpeg").unwrap().detail, "6.1-fake");
+    assert_eq!(report.check("ffprobe").unwrap().detail, "6.1-fake");
+    assert_eq!(report.check("python3").unwrap().detail, "3.12.3");
+    assert_eq!(report.check("output").unwrap().severity, Severity::Ok);
+    assert!(!report.has_errors());
+}
+
+#[test]
+fn old_yt_dlp_is_a_warning_with_remedy() {
+    let env = FakeEnv::new(&[
+        FakeTool::new("yt-dlp").version("2021.12.01"),
+        FakeTool::ffmpeg(),
+    ]);
+
+    let report = doctor::diagnose(&env.out_dir(), &DoctorConfig::default());
+
+    let yt_dlp = report.check("yt-dlp").unwrap();
+    assert_eq!(yt_dlp.severity, Severity::Warning);
+    assert!(yt_dlp.remedy.as_ref().unwrap().contains("atualize"));
+    assert_eq!(report.check("ffprobe").unwrap().severity, Severity::Warning);
+    assert!(!report.has_errors());
+}
+
+#[test]
+fn missing_tools_and_unwritable_dir_are_errors() {
+    let env = FakeEnv::new(&[]);
+
+    let report = doctor::diagnose(&env.root().join("nao-existe"), &DoctorConfig::default());
+
+    assert!(report.has_errors());
+    assert_eq!(report.check("yt-dlp").unwrap().severity, Severity::Error);
+    assert_eq!(report.check("ffmpeg").unwrap().severity, Severity::Error);
+    assert_eq!(report.check("output").unwrap().severity, Severity::Error);
+}
@@ -0,0 +1,161 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use youtube_video_downloader::{download_video, DownloadError};
+
+const URL: &str = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+const META_TEMPLATE: &str = "after_move:ytd-meta\t%(channel)s\t%(duration)s\t%(filepath)s";
+
+#[tokio::test]
+async fn video_download_passes_mp4_merge_args() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), FakeTool::ffmpeg()]);
+    let output = env.output("meu_video");
+
+    download_video(URL, &output, false).await.unwrap();
+
+    let args = env.last_job("yt-dlp").expect("yt-dlp não foi chamado");
+    assert_eq!(
+        args,
+        vec![
+            "-f",
+            "bestvideo[ext=mp4]+bestaudio[ext=m4a]/best[ext=mp4]/best",
+            "--merge-output-format",
+            "mp4",
+            "-o",
+            &format!("{output}.mp4"),
+            "--newline",
+            "--progress",
+            "--print",
+            META_TEMPLATE,
+            URL,
+        ]
+    );
+    assert!(env.out_dir().join("meu_video.mp4").exists());
+}
+
+#[tokio::test]
+async fn audio_download_extracts_mp3() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), FakeTool::ffmpeg()]);
+    let output = env.output("meu_audio");
+
+    download_video(URL, &output, true).await.unwrap();
+
+    let args = env.last_job("yt-dlp").unwrap();
+    assert_eq!(
+        args,
+        vec![
+            "-f",
+            "bestaudio",
+            "-x",
+            "--audio-format",
+            "mp3",
+            "-o",
+            &format!("{output}.%(ext)s"),
+            "--newline",
+            "--progress",
+            "--print",
+            META_TEMPLATE,
+            URL,
+        ]
+    );
+    assert!(env.out_dir().join("meu_audio.mp3").exists());
+}
+
+#[tokio::test]
+async fn shorts_url_is_forwarded_untouched() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), FakeTool::ffmpeg()]);
+    let url = "https://youtube.com/shorts/abc123";
+
+    download_video(url, &env.output("short"), false)
+        .await
+        .unwrap();
+
+    assert_eq!(env.last_job("yt-dlp").unwrap().last().unwrap(), url);
+}
+
+#[tokio::test]
+async fn invalid_url_never_reaches_backend() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), FakeTool::ffmpeg()]);
+
+    let err = download_video("https://vimeo.com/123", &env.output("x"), false)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, DownloadError::InvalidUrl(_)));
+    assert!(env.invocations("yt-dlp").is_empty());
+    assert!(env.invocations("ffmpeg").is_empty());
+}
+
+#[tokio::test]
+async fn missing_ffmpeg_is_reported() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp()]);
+
+    let err = download_video(URL, &env.output("x"), false)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, DownloadError::MissingDependency("ffmpeg")));
+    assert!(env.last_job("yt-dlp").is_none());
+}
+
+#[tokio::test]
+async fn missing_yt_dlp_is_not_installed_without_consent() {
+    let env = FakeEnv::new(&[FakeTool::ffmpeg(), FakeTool::new("pipx")]);
+
+    let err = download_video(URL, &env.output("x"), false)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, DownloadError::MissingDependency("yt-dlp")));
+    assert!(env.invocations("pipx").is_empty());
+}
+
+#[tokio::test]
+async fn backend_failure_carries_status_and_stderr() {
+    let yt_dlp = FakeTool::new("yt-dlp")
+        .progress(&[3.0])
+        .stderr("ERROR: [youtube] dQw4w9WgXcQ: Video unavailable")
+        .exit_code(1);
+    let env = FakeEnv::new(&[yt_dlp, FakeTool::ffmpeg()]);
+
+    let err = download_video(URL, &env.output("x"), false)
+        .await
+        .unwrap_err();
+
+    match err {
+        DownloadError::Backend { status, stderr } => {
+            assert_eq!(status, Some(1));
+            assert!(stderr.contains("Video unavailable"));
+        }
+        other => panic!("erro inesperado: {other:?}"),
+    }
+    assert!(!env.out_dir().join("x.mp4").exists());
+}
+
+#[tokio::test]
+async fn dependency_checks_run_before_download() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), FakeTool::ffmpeg()]);
+
+    download_video(URL, &env.output("x"), false).await.unwrap();
+
+    assert_eq!(env.invocations("ffmpeg"), vec![vec!["-version"]]);
+    let calls = env.invocations("yt-dlp");
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0], vec!["--version"]);
+}
+
+#[tokio::test]
+async fn extraction_failures_suggest_backend_update() {
+    let yt_dlp = FakeTool::new("yt-dlp")
+        .stderr("ERROR: [youtube] dQw4w9WgXcQ: Signature extraction failed")
+        .exit_code(1);
+    let env = FakeEnv::new(&[yt_dlp, FakeTool::ffmpeg()]);
+
+    let err = download_video(URL, &env.output("x"), false)
+        .await
+        .unwrap_err();
+
+    assert!(err.suggests_backend_update());
+}
@@ -0,0 +1,66 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use youtube_video_downloader::access::AccessOptions;
+use youtube_video_downloader::filter::{self, Filters, SkipReason, UploadDate};
+use youtube_video_downloader::search;
+
+const PLAYLIST: &str = "https://www.youtube.com/playlist?list=PLaulas";
+
+const ENTRIES: &str = concat!(
+    r#"{"id": "aaaaaaaaaaa", "title": "Aula 1", "duration": 600, "upload_date": "20240510", "live_status": "not_live"}"#,
+    "\n",
+    r#"{"id": "bbbbbbbbbbb", "title": "Aula 0", "duration": 600, "upload_date": "20231201"}"#,
+    "\n",
+    r#"{"id": "ccccccccccc", "title": "Aula ao vivo", "duration": 5400, "upload_date": "20240601", "live_status": "was_live"}"#,
+    "\n",
+    r#"{"id": "ddddddddddd", "title": "Aula 2", "duration": 620, "upload_date": "20240612"}"#,
+);
+
+#[tokio::test]
+async fn playlist_entries_are_filtered_before_downloading() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp().json(ENTRIES)]);
+    let matcher = Filters {
+        date_after: Some(UploadDate::parse("2024-01-01").unwrap()),
+        skip_live: true,
+        ..Filters::default()
+    }
+    .compile()
+    .unwrap();
+    let access = AccessOptions::default();
+
+    let videos = search::list_collection(PLAYLIST, None, &access)
+        .await
+        .unwrap();
+    assert_eq!(env.last_job("yt-dlp").unwrap().last().unwrap(), PLAYLIST);
+    let selection = filter::select(&matcher, videos, &access).await;
+
+    let accepted: Vec<&str> = selection.accepted.iter().map(|v| v.id.as_str()).collect();
+    assert_eq!(accepted, ["aaaaaaaaaaa", "ddddddddddd"]);
+    let reasons: Vec<(&str, &SkipReason)> = selection
+        .skipped
+        .iter()
+        .map(|item| (item.video.id.as_str(), &item.reason))
+        .collect();
+    assert!(matches!(
+        reasons[..],
+        [
+            ("bbbbbbbbbbb", SkipReason::PublishedBefore { .. }),
+            ("ccccccccccc", SkipReason::Live)
+        ]
+    ));
+
+    // Só o vídeo sem status de live precisou dos metadados completos
+    let details: Vec<Vec<String>> = env
+        .invocations("yt-dlp")
+        .into_iter()
+        .filter(|args| args.contains(&"--no-playlist".to_string()))
+        .collect();
+    assert_eq!(details.len(), 1);
+    assert_eq!(
+        details[0].last().unwrap(),
+        "https://www.youtube.com/watch?v=ddddddddddd"
+    );
+}
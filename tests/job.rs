@@ -0,0 +1,150 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use std::path::Path;
+use std::time::Duration;
+use youtube_video_downloader::{
+    download_with, DownloadError, DownloadOptions, JobControl, JobEvent, Progress,
+};
+
+const URL: &str = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+
+#[tokio::test]
+async fn progress_and_logs_are_streamed_as_events() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp()
+        .stdout("[youtube] dQw4w9WgXcQ: Downloading webpage\n")
+        .stderr("WARNING: formato alternativo\n")]);
+    let options = DownloadOptions {
+        audio_format: None,
+        ..DownloadOptions::new(URL, &env.output("faixa"), true)
+    };
+    let mut events = Vec::new();
+
+    download_with(&options, &JobControl::new(), |event| events.push(event))
+        .await
+        .unwrap();
+
+    let percents: Vec<f64> = events
+        .iter()
+        .filter_map(|event| match event {
+            JobEvent::Progress(Progress { percent, .. }) => Some(*percent),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(percents, vec![0.0, 42.5, 100.0]);
+    assert!(events.contains(&JobEvent::Log(
+        "[youtube] dQw4w9WgXcQ: Downloading webpage".into()
+    )));
+    assert!(events.contains(&JobEvent::Log("WARNING: formato alternativo".into())));
+    assert!(!events
+        .iter()
+        .any(|event| matches!(event, JobEvent::Log(line) if line.starts_with("ytd-meta"))));
+}
+
+#[tokio::test]
+async fn cancelled_download_stops_the_backend() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp().run("exec /bin/sleep 30")]);
+    let options = DownloadOptions {
+        audio_format: None,
+        ..DownloadOptions::new(URL, &env.output("longo"), true)
+    };
+    let control = JobControl::new();
+    let canceller = control.clone();
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(10),
+        download_with(&options, &control, move |event| {
+            if matches!(event, JobEvent::Progress(Progress { percent, .. }) if percent == 100.0) {
+                canceller.cancel();
+            }
+        }),
+    )
+    .await
+    .expect("o cancelamento não encerrou o download");
+
+    assert!(matches!(result, Err(DownloadError::Cancelled)));
+}
+
+#[tokio::test]
+async fn paused_download_finishes_after_resume() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp().run("/bin/sleep 1")]);
+    let options = DownloadOptions {
+        audio_format: None,
+        ..DownloadOptions::new(URL, &env.output("pausa"), true)
+    };
+    let control = JobControl::new();
+    let remote = control.clone();
+
+    let resumer = tokio::spawn(async move {
+        while !remote.pause() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(remote.is_paused());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(remote.resume());
+    });
+
+    let downloaded = tokio::time::timeout(
+        Duration::from_secs(10),
+        download_with(&options, &control, |_| {}),
+    )
+    .await
+    .expect("o download pausado não foi retomado")
+    .unwrap();
+    resumer.await.unwrap();
+
+    assert!(downloaded.path.is_some());
+    assert!(!control.is_paused());
+}
+
+/// Linhas anotadas pelo processo filho do yt-dlp falso.
+fn ticks(path: &Path) -> usize {
+    std::fs::read_to_string(path).map_or(0, |text| text.lines().count())
+}
+
+#[tokio::test]
+async fn pause_and_cancel_reach_the_processes_started_by_the_backend() {
+    // Como o ffmpeg de uma junção, um filho do yt-dlp trabalha por conta própria
+    // Fora do ambiente falso, cujo diretório só existe depois de criado
+    let dir = tempfile::TempDir::new().unwrap();
+    let log = dir.path().join("ticks");
+    let env = FakeEnv::new(&[FakeTool::yt_dlp().run(&format!(
+        "(while :; do echo x >> '{}'; /bin/sleep 0.02; done) & wait",
+        log.display()
+    ))]);
+    let options = DownloadOptions {
+        audio_format: None,
+        ..DownloadOptions::new(URL, &env.output("filho"), true)
+    };
+    let control = JobControl::new();
+    let remote = control.clone();
+    let watched = log.clone();
+
+    let checker = tokio::spawn(async move {
+        while ticks(&watched) == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(remote.pause());
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let paused = ticks(&watched);
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(ticks(&watched), paused, "o filho continuou durante a pausa");
+        assert!(remote.resume());
+        remote.cancel();
+    });
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(10),
+        download_with(&options, &control, |_| {}),
+    )
+    .await
+    .expect("o cancelamento não encerrou o download");
+    checker.await.unwrap();
+
+    assert!(matches!(result, Err(DownloadError::Cancelled)));
+    let cancelled = ticks(&log);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(ticks(&log), cancelled, "o filho sobreviveu ao cancelamento");
+}
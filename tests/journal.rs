@@ -0,0 +1,103 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use std::fs;
+use youtube_video_downloader::{download, download_video, journal, DownloadOptions};
+
+const URL: &str = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+
+fn single_attempt(env: &FakeEnv) {
+    env.write_config(r#"{"provision": {"confirm": "never"}, "retry": {"attempts": 1}}"#);
+}
+
+#[tokio::test]
+async fn finished_download_leaves_no_journal_entry() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp().creates_output("mp4"), FakeTool::ffmpeg()]);
+
+    download_video(URL, &env.output("x"), false).await.unwrap();
+
+    assert!(journal::unfinished().is_empty());
+}
+
+#[tokio::test]
+async fn interrupted_download_can_be_resumed() {
+    let env = FakeEnv::new(&[FakeTool::ffmpeg()]);
+    single_attempt(&env);
+    let destination = env.out_dir().join("x.mp4");
+    env.add_tool(
+        &FakeTool::yt_dlp()
+            .stdout(&format!(
+                "[download] Destination: {}\n",
+                destination.display()
+            ))
+            .stderr("ERROR: [Errno 104] Connection reset by peer\n")
+            .exit_code(1),
+    );
+    fs::write(env.out_dir().join("x.mp4.part"), "metade").unwrap();
+
+    download_video(URL, &env.output("x"), false)
+        .await
+        .unwrap_err();
+
+    let entries = journal::unfinished();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].url, URL);
+    assert_eq!(
+        entries[0].partial_files(),
+        vec![env.out_dir().join("x.mp4.part")]
+    );
+
+    // A nova execução continua o parcial e tira o job do diário
+    env.add_tool(&FakeTool::yt_dlp().creates_output("mp4"));
+    download(&entries[0].options()).await.unwrap();
+
+    let args = env.last_job("yt-dlp").unwrap();
+    assert!(args.contains(&"--continue".to_string()));
+    assert!(args.contains(&format!("{}.mp4", env.output("x"))));
+    assert!(journal::unfinished().is_empty());
+}
+
+#[tokio::test]
+async fn permanent_failure_is_not_kept_for_resume() {
+    let env = FakeEnv::new(&[
+        FakeTool::yt_dlp()
+            .stderr("ERROR: [youtube] dQw4w9WgXcQ: Private video\n")
+            .exit_code(1),
+        FakeTool::ffmpeg(),
+    ]);
+    single_attempt(&env);
+
+    download(&DownloadOptions::new(URL, &env.output("x"), false))
+        .await
+        .unwrap_err();
+
+    assert!(journal::unfinished().is_empty());
+}
+
+#[tokio::test]
+async fn discarding_removes_partial_files() {
+    let env = FakeEnv::new(&[FakeTool::ffmpeg()]);
+    single_attempt(&env);
+    let destination = env.out_dir().join("x.mp4");
+    env.add_tool(
+        &FakeTool::yt_dlp()
+            .stdout(&format!(
+                "[download] Destination: {}\n",
+                destination.display()
+            ))
+            .stderr("ERROR: HTTP Error 503: Service Unavailable\n")
+            .exit_code(1),
+    );
+    let partial = env.out_dir().join("x.mp4.part");
+    fs::write(&partial, "metade").unwrap();
+    download_video(URL, &env.output("x"), false)
+        .await
+        .unwrap_err();
+
+    assert_eq!(journal::discard_unfinished().unwrap(), 1);
+
+    assert!(!partial.exists());
+    assert!(journal::unfinished().is_empty());
+}
@@ -0,0 +1,112 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use youtube_video_downloader::live::LiveOptions;
+use youtube_video_downloader::{download_with, t, DownloadOptions, JobControl, JobEvent};
+
+const LIVE_URL: &str = "https://www.youtube.com/live/dQw4w9WgXcQ";
+
+const UPCOMING: &str = r#"{"id": "dQw4w9WgXcQ", "title": "Live", "live_status": "is_upcoming"}"#;
+
+#[tokio::test]
+async fn live_recording_stops_after_the_duration() {
+    // Grava "para sempre" até receber o SIGINT, como o yt-dlp numa live
+    let env = FakeEnv::new(&[
+        FakeTool::yt_dlp()
+            .json(UPCOMING)
+            .creates_output("mp4")
+            .run("trap 'kill $! 2>/dev/null; exit 0' INT; /bin/sleep 30 >/dev/null 2>&1 & wait"),
+        FakeTool::ffmpeg(),
+    ]);
+    let options = DownloadOptions {
+        live: Some(LiveOptions {
+            from_start: true,
+            max_duration: Some(Duration::from_millis(500)),
+            segment: None,
+        }),
+        ..DownloadOptions::new(LIVE_URL, &env.output("live"), false)
+    };
+
+    let started = Instant::now();
+    let mut statuses = Vec::new();
+    let downloaded = download_with(&options, &JobControl::new(), |event| {
+        if let JobEvent::Status(message) = event {
+            statuses.push(message);
+        }
+    })
+    .await
+    .unwrap();
+
+    assert!(started.elapsed() < Duration::from_secs(10));
+    assert!(statuses.contains(&t!("live.waiting")));
+    assert!(statuses.contains(&t!("live.limit_reached")));
+    assert_eq!(downloaded.path, Some(PathBuf::from(env.output("live.mp4"))));
+    let args = env.last_job("yt-dlp").unwrap();
+    assert!(args.contains(&"--wait-for-video".to_string()));
+    assert!(args.contains(&"--live-from-start".to_string()));
+    assert_eq!(args.last().unwrap(), LIVE_URL);
+}
+
+#[tokio::test]
+async fn long_recordings_are_split_into_parts() {
+    // O ffmpeg falso cria duas partes a partir do padrão `%03d` de saída
+    let env = FakeEnv::new(&[
+        FakeTool::yt_dlp()
+            .creates_output("mp4")
+            .metadata("Canal", 7200.0),
+        FakeTool::ffmpeg().run(
+            r#"for a; do out="$a"; done; case "$out" in *%03d*) for n in 1 2; do printf parte > "$(printf "$out" "$n")"; done;; esac"#,
+        ),
+    ]);
+    let options = DownloadOptions {
+        live: Some(LiveOptions::default()),
+        ..DownloadOptions::new(LIVE_URL, &env.output("live"), false)
+    };
+
+    let downloaded = download_with(&options, &JobControl::new(), |_| {})
+        .await
+        .unwrap();
+
+    let parts = [env.output("live.001.mp4"), env.output("live.002.mp4")].map(PathBuf::from);
+    assert_eq!(downloaded.segments, parts);
+    assert_eq!(downloaded.path.as_ref(), Some(&parts[0]));
+    assert!(!PathBuf::from(env.output("live.mp4")).exists());
+    let split = env.last_job("ffmpeg").unwrap();
+    assert!(split.windows(2).any(|w| w == ["-segment_time", "3600"]));
+}
+
+#[tokio::test]
+async fn recording_interrupted_with_an_error_is_kept_and_split() {
+    // Como o yt-dlp de verdade, sai com 130 após o SIGINT e sem a linha de
+    // metadados; só o destino informado no início indica o arquivo
+    let env = FakeEnv::new(&[
+        FakeTool::new("yt-dlp").json(UPCOMING).run(concat!(
+            r#"prev=''; for a; do [ "$prev" = '-o' ] && out="$a"; prev="$a"; done; "#,
+            r#"printf gravado > "$out"; printf '[download] Destination: %s\n' "$out"; "#,
+            "trap 'kill $! 2>/dev/null; exit 130' INT; /bin/sleep 30 >/dev/null 2>&1 & wait",
+        )),
+        FakeTool::ffmpeg().run(
+            r#"for a; do out="$a"; done; case "$out" in *%03d*) for n in 1 2; do printf parte > "$(printf "$out" "$n")"; done;; esac"#,
+        ),
+    ]);
+    let options = DownloadOptions {
+        live: Some(LiveOptions {
+            max_duration: Some(Duration::from_millis(500)),
+            ..LiveOptions::default()
+        }),
+        ..DownloadOptions::new(LIVE_URL, &env.output("live"), false)
+    };
+
+    let downloaded = download_with(&options, &JobControl::new(), |_| {})
+        .await
+        .unwrap();
+
+    let parts = [env.output("live.001.mp4"), env.output("live.002.mp4")].map(PathBuf::from);
+    assert_eq!(downloaded.segments, parts);
+    assert!(parts.iter().all(|part| part.exists()));
+    assert_eq!(downloaded.attempts, 1);
+}
@@ -0,0 +1,133 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use std::fs;
+use youtube_video_downloader::loudness::LoudnessOptions;
+use youtube_video_downloader::{download_with, DownloadOptions, JobControl};
+
+const URL: &str = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+
+/// Bloco impresso pelo `loudnorm` ao fim de cada passagem.
+const LOUDNORM: &str = r#"[Parsed_loudnorm_0 @ 0x55d5c8a0]
+{
+	"input_i" : "-23.41",
+	"input_tp" : "-4.20",
+	"input_lra" : "6.80",
+	"input_thresh" : "-33.71",
+	"output_i" : "-14.03",
+	"output_tp" : "-1.50",
+	"output_lra" : "5.90",
+	"output_thresh" : "-24.30",
+	"normalization_type" : "linear",
+	"target_offset" : "0.03"
+}
+"#;
+
+/// ffmpeg falso que mede o volume e, na segunda passagem, grava a saída.
+fn ffmpeg() -> FakeTool {
+    FakeTool::ffmpeg().stderr(LOUDNORM).run(
+        r#"for a; do out="$a"; done; case "$out" in *.loudnorm.*) printf normalizado > "$out";; esac"#,
+    )
+}
+
+fn loudnorm_calls(env: &FakeEnv) -> Vec<Vec<String>> {
+    env.invocations("ffmpeg")
+        .into_iter()
+        .filter(|args| args.iter().any(|a| a.starts_with("loudnorm=")))
+        .collect()
+}
+
+#[tokio::test]
+async fn audio_is_normalized_in_two_passes() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), ffmpeg()]);
+    let options = DownloadOptions {
+        loudness: Some(LoudnessOptions {
+            target_lufs: -14.0,
+            analyze_only: false,
+        }),
+        ..DownloadOptions::new(URL, &env.output("podcast"), true)
+    };
+
+    let downloaded = download_with(&options, &JobControl::new(), |_| {})
+        .await
+        .unwrap();
+
+    let loudness = downloaded.loudness.unwrap();
+    assert_eq!(loudness.input.integrated, -23.41);
+    assert_eq!(loudness.output.unwrap().integrated, -14.03);
+    assert_eq!(
+        fs::read_to_string(env.output("podcast.mp3")).unwrap(),
+        "normalizado"
+    );
+    assert!(!env.out_dir().join("podcast.loudnorm.mp3").exists());
+
+    let calls = loudnorm_calls(&env);
+    assert_eq!(calls.len(), 2);
+    let second_pass = calls[1]
+        .iter()
+        .find(|a| a.starts_with("loudnorm="))
+        .unwrap();
+    assert!(second_pass.starts_with("loudnorm=I=-14:"));
+    assert!(second_pass.contains("measured_I=-23.41"));
+    assert!(second_pass.contains("linear=true"));
+    // Sem o ffprobe, o bitrate do original é desconhecido: MP3 em V0
+    assert!(calls[1].windows(2).any(|w| w == ["-q:a", "0"]));
+
+    let sidecar: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(env.output("podcast.info.json")).unwrap())
+            .unwrap();
+    assert_eq!(sidecar["loudness"]["standard"], "ebu-r128");
+    assert_eq!(sidecar["loudness"]["input"]["integrated"], -23.41);
+    assert_eq!(sidecar["loudness"]["output"]["true_peak"], -1.5);
+}
+
+#[tokio::test]
+async fn analyze_only_leaves_the_file_untouched() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), ffmpeg()]);
+    let options = DownloadOptions {
+        loudness: Some(LoudnessOptions {
+            analyze_only: true,
+            ..LoudnessOptions::default()
+        }),
+        ..DownloadOptions::new(URL, &env.output("podcast"), true)
+    };
+
+    let downloaded = download_with(&options, &JobControl::new(), |_| {})
+        .await
+        .unwrap();
+
+    let loudness = downloaded.loudness.unwrap();
+    assert_eq!(loudness.input.true_peak, -4.2);
+    assert_eq!(loudness.output, None);
+    assert_eq!(
+        fs::read_to_string(env.output("podcast.mp3")).unwrap(),
+        "fake media"
+    );
+    assert_eq!(loudnorm_calls(&env).len(), 1);
+    let sidecar = fs::read_to_string(env.output("podcast.info.json")).unwrap();
+    assert!(sidecar.contains("\"target_lufs\": -16.0"));
+}
+
+#[tokio::test]
+async fn normalized_audio_keeps_the_original_bitrate() {
+    let env = FakeEnv::new(&[
+        FakeTool::yt_dlp(),
+        ffmpeg(),
+        FakeTool::new("ffprobe").stdout(
+            r#"{"streams": [{"codec_type": "audio", "codec_name": "mp3", "bit_rate": "320000"}], "format": {}}"#,
+        ),
+    ]);
+    let options = DownloadOptions {
+        loudness: Some(LoudnessOptions::default()),
+        ..DownloadOptions::new(URL, &env.output("musica"), true)
+    };
+
+    download_with(&options, &JobControl::new(), |_| {})
+        .await
+        .unwrap();
+
+    let calls = loudnorm_calls(&env);
+    assert!(calls[1].windows(2).any(|w| w == ["-b:a", "320k"]));
+}
@@ -0,0 +1,112 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use std::fs;
+use std::path::PathBuf;
+use youtube_video_downloader::archive;
+use youtube_video_downloader::preset::Preset;
+use youtube_video_downloader::{download_with, DownloadOptions, JobControl, JobEvent};
+
+const URL: &str = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+
+/// ffmpeg falso que grava a saída da conversão, sempre o último argumento.
+fn ffmpeg() -> FakeTool {
+    FakeTool::ffmpeg().run(
+        r#"for a; do out="$a"; done; case "$out" in *.mp4|*.m4a) printf convertido > "$out";; esac"#,
+    )
+}
+
+fn has_pair(args: &[String], flag: &str, value: &str) -> bool {
+    args.windows(2).any(|w| w == [flag, value])
+}
+
+#[tokio::test]
+async fn size_limited_preset_uses_two_passes() {
+    let env = FakeEnv::new(&[
+        FakeTool::yt_dlp()
+            .creates_output("mp4")
+            .metadata("Canal", 300.0),
+        ffmpeg(),
+    ]);
+    let options = DownloadOptions {
+        preset: Some(Preset::Whatsapp),
+        ..DownloadOptions::new(URL, &env.output("video"), false)
+    };
+
+    let downloaded = download_with(&options, &JobControl::new(), |_| {})
+        .await
+        .unwrap();
+
+    let output = PathBuf::from(env.output("video.mp4"));
+    assert_eq!(downloaded.path.as_ref(), Some(&output));
+    assert_eq!(fs::read_to_string(&output).unwrap(), "convertido");
+    assert!(!env.out_dir().join("video.whatsapp.mp4").exists());
+
+    let calls: Vec<_> = env
+        .invocations("ffmpeg")
+        .into_iter()
+        .filter(|args| args.iter().any(|a| a == "libx264"))
+        .collect();
+    assert_eq!(calls.len(), 2);
+    // 16 MB em 5 minutos: 405 kbps no total, 128 para o áudio
+    assert!(has_pair(&calls[0], "-pass", "1"));
+    assert!(has_pair(&calls[0], "-b:v", "277k"));
+    assert!(calls[0].contains(&"-an".to_string()));
+    assert!(has_pair(&calls[1], "-pass", "2"));
+    assert!(has_pair(&calls[1], "-b:a", "128k"));
+    assert!(has_pair(&calls[1], "-profile:v", "main"));
+}
+
+#[tokio::test]
+async fn audio_preset_produces_aac() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), ffmpeg()]);
+    let options = DownloadOptions {
+        preset: Some(Preset::AndroidLow),
+        ..DownloadOptions::new(URL, &env.output("audio"), true)
+    };
+
+    let downloaded = download_with(&options, &JobControl::new(), |_| {})
+        .await
+        .unwrap();
+
+    let output = PathBuf::from(env.output("audio.m4a"));
+    assert_eq!(downloaded.path.as_ref(), Some(&output));
+    assert!(output.exists());
+    assert!(!env.out_dir().join("audio.mp3").exists());
+    let args = env.last_job("ffmpeg").unwrap();
+    assert!(args.contains(&"-vn".to_string()));
+    assert!(has_pair(&args, "-c:a", "aac"));
+    assert!(has_pair(&args, "-b:a", "96k"));
+    assert!(!args.contains(&"-pass".to_string()));
+}
+
+#[tokio::test]
+async fn video_too_long_for_the_size_limit_keeps_the_download() {
+    let env = FakeEnv::new(&[
+        FakeTool::yt_dlp()
+            .creates_output("mp4")
+            .metadata("Canal", 7200.0),
+        ffmpeg(),
+    ]);
+    let options = DownloadOptions {
+        preset: Some(Preset::Email25mb),
+        ..DownloadOptions::new(URL, &env.output("video"), false)
+    };
+    let mut events = Vec::new();
+
+    let downloaded = download_with(&options, &JobControl::new(), |event| events.push(event))
+        .await
+        .unwrap();
+
+    // A conversão recusada só gera um aviso; o download original continua lá
+    let original = env.out_dir().join("video.mp4");
+    assert_eq!(downloaded.path, Some(original.clone()));
+    assert!(original.exists());
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, JobEvent::Status(message) if message.contains("email-25mb") && message.contains("25 MB"))));
+    // e o vídeo conta como baixado
+    assert!(archive::ids().contains("dQw4w9WgXcQ"));
+}
@@ -0,0 +1,82 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use youtube_video_downloader::provision::{self, InstallMethod, Installation};
+use youtube_video_downloader::{download_video, DownloadError};
+
+const URL: &str = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+
+/// pipx falso que "instala" o yt-dlp copiando um executável para `$PIPX_BIN_DIR`.
+fn fake_pipx(env: &FakeEnv) -> FakeTool {
+    let payload = env.payload(&FakeTool::yt_dlp().version("2024.05.27"));
+    FakeTool::new("pipx").run(&format!(
+        "/bin/mkdir -p \"$PIPX_BIN_DIR\" && /bin/cp '{}' \"$PIPX_BIN_DIR/yt-dlp\"",
+        payload.display()
+    ))
+}
+
+#[tokio::test]
+async fn pipx_install_is_private_and_recorded() {
+    let env = FakeEnv::new(&[FakeTool::ffmpeg()]);
+    env.add_tool(&fake_pipx(&env));
+    env.write_config(r#"{"provision": {"confirm": "always", "methods": ["pipx"]}}"#);
+
+    download_video(URL, &env.output("video"), false)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        env.last_job("pipx").unwrap(),
+        vec!["install", "--force", "yt-dlp"]
+    );
+    let installation = Installation::load().expect("instalação não registrada");
+    assert_eq!(installation.method, InstallMethod::Pipx);
+    assert_eq!(installation.version, "2024.05.27");
+    assert!(installation.path.starts_with(env.data_dir()));
+    assert!(env.out_dir().join("video.mp4").exists());
+}
+
+#[tokio::test]
+async fn managed_installation_takes_precedence_over_path() {
+    let env = FakeEnv::new(&[FakeTool::ffmpeg()]);
+    let payload = env.payload(&FakeTool::yt_dlp().version("2024.05.27"));
+    std::fs::create_dir_all(env.data_dir()).unwrap();
+    std::fs::write(
+        Installation::state_path(),
+        format!(
+            r#"{{"method": "binary", "path": "{}", "version": "2024.05.27", "installed_at": 0}}"#,
+            payload.display()
+        ),
+    )
+    .unwrap();
+
+    assert_eq!(provision::yt_dlp_program(), payload);
+    download_video(URL, &env.output("video"), false)
+        .await
+        .unwrap();
+    assert!(env.last_job("yt-dlp").is_some());
+}
+
+#[tokio::test]
+async fn failing_methods_fall_through_and_report() {
+    let pip_error = FakeTool::new("pipx")
+        .stderr("error: externally-managed-environment")
+        .exit_code(1);
+    let env = FakeEnv::new(&[FakeTool::ffmpeg(), pip_error]);
+    env.write_config(r#"{"provision": {"confirm": "always", "methods": ["pipx", "venv"]}}"#);
+
+    let err = download_video(URL, &env.output("x"), false)
+        .await
+        .unwrap_err();
+
+    match err {
+        DownloadError::InstallFailed(message) => {
+            assert!(message.contains("externally-managed-environment"));
+            assert!(message.contains("python3 não encontrado"));
+        }
+        other => panic!("erro inesperado: {other:?}"),
+    }
+    assert!(Installation::load().is_none());
+}
@@ -0,0 +1,113 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use youtube_video_downloader::{
+    download_video, download_with, stats, DownloadError, DownloadOptions, JobControl, JobEvent,
+};
+
+const URL: &str = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+
+/// Esperas curtas para que os testes não demorem.
+fn fast_retries(env: &FakeEnv, attempts: u32) {
+    env.write_config(&format!(
+        r#"{{"provision": {{"confirm": "never"}},
+            "retry": {{"attempts": {attempts}, "initial_delay_ms": 1, "max_delay_ms": 5}}}}"#
+    ));
+}
+
+fn jobs(env: &FakeEnv) -> usize {
+    env.invocations("yt-dlp")
+        .iter()
+        .filter(|args| !args.iter().any(|a| a == "--version"))
+        .count()
+}
+
+#[tokio::test]
+async fn transient_failure_is_retried_until_success() {
+    let env = FakeEnv::new(&[FakeTool::ffmpeg()]);
+    fast_retries(&env, 3);
+    // Só a primeira chamada falha com um erro passageiro
+    let marker = env.root().join("primeira");
+    env.add_tool(&FakeTool::yt_dlp().creates_output("mp4").run(&format!(
+        "if [ ! -e '{0}' ]; then : > '{0}'; echo 'ERROR: HTTP Error 503: Service Unavailable' >&2; exit 1; fi",
+        marker.display()
+    )));
+
+    let downloaded = download_video(URL, &env.output("x"), false).await.unwrap();
+
+    assert_eq!(downloaded.attempts, 2);
+    assert_eq!(jobs(&env), 2);
+    // Só o resultado final entra nas estatísticas
+    let records = stats::load();
+    assert_eq!(records.len(), 1);
+    assert!(records[0].ok);
+}
+
+#[tokio::test]
+async fn permanent_failure_is_not_retried() {
+    let env = FakeEnv::new(&[
+        FakeTool::yt_dlp()
+            .stderr("ERROR: [youtube] dQw4w9WgXcQ: Private video\n")
+            .exit_code(1),
+        FakeTool::ffmpeg(),
+    ]);
+    fast_retries(&env, 3);
+
+    let err = download_video(URL, &env.output("x"), false)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, DownloadError::Backend { .. }));
+    assert_eq!(jobs(&env), 1);
+}
+
+#[tokio::test]
+async fn exhausted_retries_report_attempts() {
+    let env = FakeEnv::new(&[
+        FakeTool::yt_dlp()
+            .stderr("ERROR: [Errno 104] Connection reset by peer\n")
+            .exit_code(1),
+        FakeTool::ffmpeg(),
+    ]);
+    fast_retries(&env, 3);
+
+    let err = download_video(URL, &env.output("x"), false)
+        .await
+        .unwrap_err();
+
+    match &err {
+        DownloadError::RetriesExhausted { attempts, last } => {
+            assert_eq!(*attempts, 3);
+            assert!(matches!(**last, DownloadError::Backend { .. }));
+        }
+        other => panic!("erro inesperado: {other:?}"),
+    }
+    assert!(err.to_string().contains('3'));
+    assert_eq!(jobs(&env), 3);
+}
+
+#[tokio::test]
+async fn permanent_failure_after_a_retry_is_not_reported_as_exhausted() {
+    let env = FakeEnv::new(&[FakeTool::ffmpeg()]);
+    fast_retries(&env, 3);
+    // Um 503 e depois um vídeo privado: não há por que fazer a terceira tentativa
+    let marker = env.root().join("primeira");
+    env.add_tool(&FakeTool::yt_dlp().run(&format!(
+        "if [ ! -e '{0}' ]; then : > '{0}'; echo 'ERROR: HTTP Error 503: Service Unavailable' >&2; exit 1; fi; echo 'ERROR: Private video' >&2; exit 1",
+        marker.display()
+    )));
+    let options = DownloadOptions::new(URL, &env.output("x"), false);
+    let mut events = Vec::new();
+
+    let err = download_with(&options, &JobControl::new(), |event| events.push(event))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, DownloadError::Backend { .. }), "{err:?}");
+    assert_eq!(jobs(&env), 2);
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, JobEvent::Status(message) if message.contains("2/3"))));
+}
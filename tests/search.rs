@@ -0,0 +1,45 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use youtube_video_downloader::search::{search, select};
+use youtube_video_downloader::DownloadError;
+
+#[tokio::test]
+async fn search_lists_results_from_the_backend() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp().json(concat!(
+        r#"{"id": "abc", "title": "Lofi 1", "channel": "Canal", "duration": 3600.0, "url": "https://www.youtube.com/watch?v=abc"}"#,
+        "\n",
+        r#"{"id": "def", "title": "Lofi 2", "uploader": "Outro", "duration": null}"#,
+    ))]);
+
+    let results = search("lofi hip hop", 3).await.unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].title, "Lofi 1");
+    assert_eq!(results[1].url, "https://www.youtube.com/watch?v=def");
+    let picked = select(&results, &[2]).unwrap();
+    assert_eq!(picked[0].channel.as_deref(), Some("Outro"));
+
+    let args = env.last_job("yt-dlp").unwrap();
+    assert!(args.contains(&"--flat-playlist".to_string()));
+    assert_eq!(args.last().unwrap(), "ytsearch3:lofi hip hop");
+}
+
+#[tokio::test]
+async fn failed_search_reports_backend_error() {
+    let _env = FakeEnv::new(&[FakeTool::yt_dlp()
+        .stderr("ERROR: Unable to download API page\n")
+        .exit_code(1)]);
+
+    let error = search("lofi", 5).await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        DownloadError::Backend {
+            status: Some(1),
+            ..
+        }
+    ));
+}
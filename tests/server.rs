@@ -0,0 +1,352 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use std::net::TcpListener;
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use youtube_video_downloader::server::{self, ServerOptions};
+
+const URL: &str = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+const CODE: &str = "ABCD-EFGH";
+
+struct TestServer {
+    base: String,
+    stop: Option<oneshot::Sender<()>>,
+    task: JoinHandle<std::io::Result<()>>,
+}
+
+impl TestServer {
+    fn start(env: &FakeEnv, token: Option<&str>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let options = ServerOptions {
+            dir: env.out_dir(),
+            token: token.map(str::to_string),
+            allowed_origins: vec!["https://www.youtube.com".to_string()],
+            pairing_code: Some(CODE.to_string()),
+        };
+        let (stop, stopped) = oneshot::channel::<()>();
+        let task = tokio::spawn(server::serve(listener, options, async {
+            let _ = stopped.await;
+        }));
+        TestServer {
+            base,
+            stop: Some(stop),
+            task,
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base)
+    }
+
+    async fn stop(mut self) {
+        let _ = self.stop.take().unwrap().send(());
+        tokio::time::timeout(Duration::from_secs(10), self.task)
+            .await
+            .expect("o servidor não encerrou")
+            .unwrap()
+            .unwrap();
+    }
+}
+
+/// Consulta o job até ele sair de `running`.
+async fn wait_finished(client: &reqwest::Client, server: &TestServer, id: u64) -> Value {
+    for _ in 0..100 {
+        let job: Value = client
+            .get(server.url(&format!("/jobs/{id}")))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        if job["status"] != "running" {
+            return job;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("o job {id} não terminou");
+}
+
+#[tokio::test]
+async fn jobs_run_through_the_download_pipeline() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp().creates_output("mp4"), FakeTool::ffmpeg()]);
+    let server = TestServer::start(&env, None);
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(server.url("/jobs"))
+        .json(&json!({"url": URL, "output": "aula"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let job: Value = response.json().await.unwrap();
+    let id = job["id"].as_u64().unwrap();
+
+    let job = wait_finished(&client, &server, id).await;
+    assert_eq!(job["status"], "done");
+    assert!(env.out_dir().join("aula.mp4").exists());
+    let listed: Value = client
+        .get(server.url("/jobs"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+
+    // Um job que já terminou é esquecido
+    let deleted = client
+        .delete(server.url(&format!("/jobs/{id}")))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(deleted.status(), StatusCode::NO_CONTENT);
+    let missing = client
+        .get(server.url(&format!("/jobs/{id}")))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+    let invalid = client
+        .post(server.url("/jobs"))
+        .json(&json!({"url": "https://example.com/video"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+    let body: Value = invalid.json().await.unwrap();
+    assert!(body["error"].is_string());
+
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn event_stream_follows_a_job_until_it_ends() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp().creates_output("mp4"), FakeTool::ffmpeg()]);
+    let server = TestServer::start(&env, None);
+    let client = reqwest::Client::new();
+
+    let job: Value = client
+        .post(server.url("/jobs"))
+        .json(&json!({"url": URL, "output": "eventos"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let id = job["id"].as_u64().unwrap();
+
+    let events = tokio::time::timeout(
+        Duration::from_secs(10),
+        client
+            .get(server.url(&format!("/jobs/{id}/events")))
+            .send()
+            .await
+            .unwrap()
+            .text(),
+    )
+    .await
+    .expect("o stream não terminou com o job")
+    .unwrap();
+
+    assert!(events.contains("event:job"));
+    assert!(events.contains(r#""status":"done""#));
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn running_job_is_cancelled_on_delete() {
+    let env = FakeEnv::new(&[
+        FakeTool::yt_dlp().run("exec /bin/sleep 30"),
+        FakeTool::ffmpeg(),
+    ]);
+    let server = TestServer::start(&env, None);
+    let client = reqwest::Client::new();
+
+    let job: Value = client
+        .post(server.url("/jobs"))
+        .json(&json!({"url": URL, "output": "longo"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let id = job["id"].as_u64().unwrap();
+
+    let response = client
+        .delete(server.url(&format!("/jobs/{id}")))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    assert_eq!(
+        wait_finished(&client, &server, id).await["status"],
+        "cancelled"
+    );
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn token_is_required_when_configured() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), FakeTool::ffmpeg()]);
+    let server = TestServer::start(&env, Some("segredo"));
+    let client = reqwest::Client::new();
+
+    let anonymous = client.get(server.url("/jobs")).send().await.unwrap();
+    assert_eq!(anonymous.status(), StatusCode::UNAUTHORIZED);
+    let wrong = client
+        .get(server.url("/jobs"))
+        .bearer_auth("outro")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(wrong.status(), StatusCode::UNAUTHORIZED);
+
+    let authorized = client
+        .get(server.url("/jobs"))
+        .bearer_auth("segredo")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(authorized.status(), StatusCode::OK);
+    // O EventSource do navegador não manda cabeçalhos
+    let query = client
+        .get(server.url("/jobs?token=segredo"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(query.status(), StatusCode::OK);
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn paired_browser_can_enqueue_with_a_profile() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp().creates_output("mp4"), FakeTool::ffmpeg()]);
+    env.write_config(
+        r#"{"provision": {"confirm": "never"},
+            "profiles": {"membros": {"user_agent": "Navegador de teste"}}}"#,
+    );
+    let server = TestServer::start(&env, Some("segredo"));
+    let client = reqwest::Client::new();
+    let enqueue = |token: &str| {
+        client
+            .post(server.url("/enqueue"))
+            .query(&[("url", URL), ("profile", "membros"), ("token", token)])
+            .header("Origin", "https://www.youtube.com")
+    };
+
+    assert_eq!(
+        enqueue("qualquer").send().await.unwrap().status(),
+        StatusCode::UNAUTHORIZED
+    );
+    let wrong = client
+        .post(server.url("/pair"))
+        .query(&[("code", "ZZZZ-ZZZZ")])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(wrong.status(), StatusCode::UNAUTHORIZED);
+
+    // O código é aceito sem hífen e em minúsculas
+    let paired: Value = client
+        .post(server.url("/pair"))
+        .query(&[("code", "abcdefgh"), ("name", "Firefox")])
+        .header("Origin", "https://www.youtube.com")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let token = paired["token"].as_str().unwrap().to_string();
+    assert!(
+        !std::fs::read_to_string(env.data_dir().join("clients.json"))
+            .unwrap()
+            .contains(&token)
+    );
+
+    let response = enqueue(&token).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        "https://www.youtube.com"
+    );
+    let id = response.json::<Value>().await.unwrap()["id"]
+        .as_u64()
+        .unwrap();
+
+    // O token de pareamento não abre o resto da API
+    let forbidden = client
+        .get(server.url(&format!("/jobs/{id}")))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(forbidden.status(), StatusCode::UNAUTHORIZED);
+
+    let authed = reqwest::Client::builder()
+        .default_headers(
+            [(
+                reqwest::header::AUTHORIZATION,
+                "Bearer segredo".parse().unwrap(),
+            )]
+            .into_iter()
+            .collect(),
+        )
+        .build()
+        .unwrap();
+    assert_eq!(wait_finished(&authed, &server, id).await["status"], "done");
+    let args = env.last_job("yt-dlp").unwrap();
+    assert!(args.contains(&"Navegador de teste".to_string()));
+    server.stop().await;
+}
+
+#[tokio::test]
+async fn browser_routes_only_answer_allowed_origins() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), FakeTool::ffmpeg()]);
+    let server = TestServer::start(&env, None);
+    let client = reqwest::Client::new();
+
+    let preflight = client
+        .request(reqwest::Method::OPTIONS, server.url("/enqueue"))
+        .header("Origin", "https://www.youtube.com")
+        .header("Access-Control-Request-Method", "POST")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(preflight.status(), StatusCode::NO_CONTENT);
+    assert_eq!(
+        preflight.headers()["access-control-allow-origin"],
+        "https://www.youtube.com"
+    );
+    assert!(preflight.headers()["access-control-allow-methods"]
+        .to_str()
+        .unwrap()
+        .contains("POST"));
+
+    let foreign = client
+        .post(server.url("/pair"))
+        .query(&[("code", CODE)])
+        .header("Origin", "https://example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(foreign.status(), StatusCode::FORBIDDEN);
+    assert!(foreign
+        .headers()
+        .get("access-control-allow-origin")
+        .is_none());
+    server.stop().await;
+}
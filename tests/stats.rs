@@ -0,0 +1,67 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use youtube_video_downloader::download_video;
+use youtube_video_downloader::stats::{self, Period};
+
+const URL: &str = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+
+#[tokio::test]
+async fn completed_download_is_recorded_with_metadata() {
+    let yt_dlp = FakeTool::yt_dlp().metadata("Canal Teste", 212.0);
+    let env = FakeEnv::new(&[yt_dlp, FakeTool::ffmpeg()]);
+    let output = env.output("video");
+
+    let downloaded = download_video(URL, &output, false).await.unwrap();
+
+    let path = env.out_dir().join("video.mp4");
+    assert_eq!(downloaded.path.as_deref(), Some(path.as_path()));
+    assert_eq!(downloaded.bytes, "fake media".len() as u64);
+
+    let records = stats::load();
+    assert_eq!(records.len(), 1);
+    assert!(records[0].ok);
+    assert_eq!(records[0].url, URL);
+    assert_eq!(records[0].channel.as_deref(), Some("Canal Teste"));
+    assert_eq!(records[0].duration_secs, Some(212.0));
+    assert_eq!(records[0].bytes, "fake media".len() as u64);
+}
+
+#[tokio::test]
+async fn failures_are_counted_across_runs() {
+    let env = FakeEnv::new(&[
+        FakeTool::yt_dlp().metadata("Canal A", 60.0),
+        FakeTool::ffmpeg(),
+    ]);
+    download_video(URL, &env.output("a"), false).await.unwrap();
+    download_video(URL, &env.output("b"), false).await.unwrap();
+
+    env.add_tool(
+        &FakeTool::new("yt-dlp")
+            .stderr("ERROR: Video unavailable")
+            .exit_code(1),
+    );
+    download_video(URL, &env.output("c"), false)
+        .await
+        .unwrap_err();
+
+    let summary = stats::summarize(&stats::load(), Period::Today);
+    assert_eq!(summary.downloads, 2);
+    assert_eq!(summary.failures, 1);
+    assert_eq!(summary.duration_secs, 120.0);
+    assert_eq!(summary.channels, vec![("Canal A".to_string(), 2)]);
+    assert_eq!(stats::summarize(&stats::load(), Period::All).downloads, 2);
+}
+
+#[tokio::test]
+async fn invalid_url_is_not_recorded() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), FakeTool::ffmpeg()]);
+
+    download_video("https://vimeo.com/123", &env.output("x"), false)
+        .await
+        .unwrap_err();
+
+    assert!(stats::load().is_empty());
+}
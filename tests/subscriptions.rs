@@ -0,0 +1,94 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use std::collections::HashSet;
+use youtube_video_downloader::filter::Filters;
+use youtube_video_downloader::subscriptions::{self, Subscription};
+use youtube_video_downloader::{archive, download, Config};
+
+const CHANNEL: &str = "https://www.youtube.com/@canal";
+
+const UPLOADS: &str = concat!(
+    r#"{"id": "aaaaaaaaaaa", "title": "Aula 2", "duration": 600, "url": "https://www.youtube.com/watch?v=aaaaaaaaaaa"}"#,
+    "\n",
+    r#"{"id": "bbbbbbbbbbb", "title": "Aula 1", "duration": 580, "url": "https://www.youtube.com/watch?v=bbbbbbbbbbb"}"#,
+    "\n",
+    r#"{"id": "ccccccccccc", "title": "Teaser", "duration": 20, "url": "https://www.youtube.com/shorts/ccccccccccc"}"#,
+);
+
+#[tokio::test]
+async fn watch_downloads_only_new_uploads() {
+    let env = FakeEnv::new(&[
+        FakeTool::yt_dlp().json(UPLOADS).creates_output("mp4"),
+        FakeTool::ffmpeg(),
+    ]);
+    let subscription = Subscription {
+        filters: Filters {
+            min_duration_secs: Some(60),
+            ..Filters::default()
+        },
+        ..Subscription::new(CHANNEL)
+    };
+
+    let selection = subscriptions::check(&subscription, 15, &HashSet::new())
+        .await
+        .unwrap();
+    assert_eq!(selection.skipped.len(), 1);
+    assert_eq!(selection.skipped[0].video.id, "ccccccccccc");
+    let pending = selection.accepted;
+    let ids: Vec<&str> = pending.iter().map(|video| video.id.as_str()).collect();
+    assert_eq!(ids, ["aaaaaaaaaaa", "bbbbbbbbbbb"]);
+    let listing = env.last_job("yt-dlp").unwrap();
+    assert!(listing.contains(&"--flat-playlist".to_string()));
+    assert!(listing.contains(&format!("{CHANNEL}/videos")));
+
+    // Um download concluído entra no arquivo e não volta na próxima rodada
+    let options = subscription
+        .download_options(&Config::load(), &pending[0], &env.out_dir())
+        .unwrap();
+    download(&options).await.unwrap();
+    assert!(archive::ids().contains("aaaaaaaaaaa"));
+
+    let ignored = HashSet::from(["ccccccccccc".to_string()]);
+    let selection = subscriptions::check(&subscription, 15, &ignored)
+        .await
+        .unwrap();
+    assert!(selection.skipped.is_empty());
+    let pending = selection.accepted;
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, "bbbbbbbbbbb");
+}
+
+#[tokio::test]
+async fn subscriptions_are_stored_in_the_config() {
+    let _env = FakeEnv::new(&[FakeTool::yt_dlp()]);
+    let mut config = Config::load();
+    let subscription = Subscription {
+        profile: Some("membros".to_string()),
+        ..Subscription::new(CHANNEL)
+    };
+
+    assert!(subscriptions::add(&mut config, subscription.clone()).unwrap());
+    assert!(!subscriptions::add(&mut config, subscription.clone()).unwrap());
+    assert_eq!(Config::load().subscriptions, vec![subscription]);
+
+    assert!(subscriptions::remove(&mut config, CHANNEL).unwrap());
+    assert!(Config::load().subscriptions.is_empty());
+}
+
+#[test]
+fn invalid_config_is_never_overwritten() {
+    let env = FakeEnv::new(&[]);
+    let broken = r#"{"profiles": {"membros": {"cookies": "/tmp/c.txt"}}, "subscriptions": ["#;
+    env.write_config(broken);
+    let path = Config::path();
+
+    // `load` cai nos valores padrão, mas salvá-los apagaria o arquivo do usuário
+    let mut config = Config::load();
+    assert!(subscriptions::add(&mut config, Subscription::new(CHANNEL)).is_err());
+    assert!(subscriptions::remove(&mut config, CHANNEL).is_err());
+    assert!(Config::load_for_update().is_err());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), broken);
+}
@@ -0,0 +1,83 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use youtube_video_downloader::tools::{self, Tool};
+use youtube_video_downloader::{download, download_video, DownloadOptions};
+
+const URL: &str = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+
+fn version_checks(env: &FakeEnv, tool: &str) -> usize {
+    env.invocations(tool)
+        .iter()
+        .filter(|args| args.iter().any(|a| a == "--version" || a == "-version"))
+        .count()
+}
+
+#[tokio::test]
+async fn second_download_reuses_session_detection() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), FakeTool::ffmpeg()]);
+
+    download_video(URL, &env.output("um"), false).await.unwrap();
+    download_video(URL, &env.output("dois"), false)
+        .await
+        .unwrap();
+
+    assert_eq!(version_checks(&env, "ffmpeg"), 1);
+    assert_eq!(version_checks(&env, "yt-dlp"), 1);
+    assert_eq!(env.invocations("yt-dlp").len(), 3);
+}
+
+#[tokio::test]
+async fn disk_cache_survives_a_new_session() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), FakeTool::ffmpeg()]);
+
+    download_video(URL, &env.output("um"), false).await.unwrap();
+    tools::clear_session_cache();
+    download_video(URL, &env.output("dois"), false)
+        .await
+        .unwrap();
+
+    assert_eq!(version_checks(&env, "ffmpeg"), 1);
+    assert!(env.data_dir().join("tools.json").exists());
+}
+
+#[tokio::test]
+async fn expired_disk_cache_is_ignored() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp(), FakeTool::ffmpeg()]);
+    env.write_config(r#"{"provision": {"confirm": "never"}, "tools": {"cache_ttl_secs": 0}}"#);
+
+    download_video(URL, &env.output("um"), false).await.unwrap();
+    tools::clear_session_cache();
+    download_video(URL, &env.output("dois"), false)
+        .await
+        .unwrap();
+
+    assert_eq!(version_checks(&env, "ffmpeg"), 2);
+}
+
+#[tokio::test]
+async fn replaced_executable_is_detected_again() {
+    let env = FakeEnv::new(&[FakeTool::ffmpeg()]);
+    assert_eq!(tools::detect(Tool::Ffmpeg).unwrap().version, "6.1-fake");
+
+    env.add_tool(&FakeTool::new("ffmpeg").version("ffmpeg version 7.0-nova Copyright"));
+
+    assert_eq!(tools::detect(Tool::Ffmpeg).unwrap().version, "7.0-nova");
+}
+
+#[tokio::test]
+async fn original_audio_does_not_require_ffmpeg() {
+    let env = FakeEnv::new(&[FakeTool::yt_dlp()]);
+    let options = DownloadOptions {
+        audio_format: None,
+        ..DownloadOptions::new(URL, &env.output("podcast"), true)
+    };
+
+    download(&options).await.unwrap();
+
+    let args = env.last_job("yt-dlp").unwrap();
+    assert!(!args.contains(&"-x".to_string()));
+    assert!(env.out_dir().join("podcast.mp3").exists());
+}
@@ -0,0 +1,129 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use youtube_video_downloader::config::ProvisionConfig;
+use youtube_video_downloader::provision::{update_backend, Installation, UpdateAction};
+use youtube_video_downloader::DownloadError;
+
+/// pipx falso: `install --force yt-dlp==V` troca o yt-dlp gerenciado por um que
+/// reporta a versão V; sem versão, instala "2099.01.01".
+fn fake_pipx() -> FakeTool {
+    FakeTool::new("pipx").run(
+        r#"v=2099.01.01
+for a in "$@"; do case "$a" in yt-dlp==*) v="${a#yt-dlp==}";; esac; done
+printf '#!/bin/sh\necho %s\n' "$v" > "$PIPX_BIN_DIR/yt-dlp""#,
+    )
+}
+
+/// Registra uma instalação gerenciada via pipx na versão indicada.
+fn managed_install(env: &FakeEnv, version: &str, pinned: Option<&str>, previous: Option<&str>) {
+    let bin_dir = env.data_dir().join("pipx").join("bin");
+    std::fs::create_dir_all(&bin_dir).unwrap();
+    let payload = env.payload(&FakeTool::new("yt-dlp").version(version));
+    std::fs::copy(&payload, bin_dir.join("yt-dlp")).unwrap();
+
+    let state = serde_json::json!({
+        "method": "pipx",
+        "path": bin_dir.join("yt-dlp"),
+        "version": version,
+        "installed_at": 0,
+        "pinned": pinned,
+        "previous": previous,
+    });
+    std::fs::write(Installation::state_path(), state.to_string()).unwrap();
+}
+
+#[tokio::test]
+async fn latest_upgrades_and_remembers_previous() {
+    let env = FakeEnv::new(&[fake_pipx()]);
+    managed_install(&env, "2024.01.01", None, None);
+
+    let outcome = update_backend(&ProvisionConfig::default(), &UpdateAction::Latest)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        (outcome.from.as_str(), outcome.to.as_str()),
+        ("2024.01.01", "2099.01.01")
+    );
+    assert_eq!(
+        env.last_job("pipx").unwrap(),
+        vec!["install", "--force", "yt-dlp"]
+    );
+    let installation = Installation::load().unwrap();
+    assert_eq!(installation.version, "2099.01.01");
+    assert_eq!(installation.previous.as_deref(), Some("2024.01.01"));
+}
+
+#[tokio::test]
+async fn pin_installs_exact_version_and_blocks_upgrades() {
+    let env = FakeEnv::new(&[fake_pipx()]);
+    managed_install(&env, "2024.01.01", None, None);
+
+    update_backend(
+        &ProvisionConfig::default(),
+        &UpdateAction::Pin("2023.12.30".into()),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        env.last_job("pipx").unwrap(),
+        vec!["install", "--force", "yt-dlp==2023.12.30"]
+    );
+    let installation = Installation::load().unwrap();
+    assert_eq!(installation.pinned.as_deref(), Some("2023.12.30"));
+
+    let err = update_backend(&ProvisionConfig::default(), &UpdateAction::Latest)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DownloadError::UpdateFailed(_)));
+    assert_eq!(env.invocations("pipx").len(), 1);
+}
+
+#[tokio::test]
+async fn rollback_reinstalls_previous_version() {
+    let env = FakeEnv::new(&[fake_pipx()]);
+    managed_install(&env, "2024.02.02", None, Some("2024.01.01"));
+
+    let outcome = update_backend(&ProvisionConfig::default(), &UpdateAction::Rollback)
+        .await
+        .unwrap();
+
+    assert_eq!(outcome.to, "2024.01.01");
+    let installation = Installation::load().unwrap();
+    assert_eq!(installation.version, "2024.01.01");
+    assert_eq!(installation.previous.as_deref(), Some("2024.02.02"));
+}
+
+#[tokio::test]
+async fn rollback_without_history_fails() {
+    let env = FakeEnv::new(&[fake_pipx()]);
+    managed_install(&env, "2024.02.02", None, None);
+
+    let err = update_backend(&ProvisionConfig::default(), &UpdateAction::Rollback)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, DownloadError::UpdateFailed(_)));
+    assert!(env.invocations("pipx").is_empty());
+}
+
+#[tokio::test]
+async fn system_yt_dlp_uses_self_update() {
+    let env = FakeEnv::new(&[FakeTool::new("yt-dlp").version("2024.01.01")]);
+
+    update_backend(
+        &ProvisionConfig::default(),
+        &UpdateAction::Pin("2024.03.10".into()),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        env.last_job("yt-dlp").unwrap(),
+        vec!["--update-to", "2024.03.10"]
+    );
+}
@@ -0,0 +1,86 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{FakeEnv, FakeTool};
+use std::path::Path;
+use youtube_video_downloader::{
+    download_with, DownloadError, DownloadOptions, JobControl, JobEvent,
+};
+
+const URL: &str = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+
+fn ffprobe(streams: &str) -> FakeTool {
+    FakeTool::new("ffprobe")
+        .version("ffprobe version 6.1-fake")
+        .stdout(&format!(
+            r#"{{"streams": [{streams}], "format": {{"duration": "187.04"}}}}"#
+        ))
+}
+
+const VIDEO: &str =
+    r#"{"codec_type": "video", "codec_name": "h264", "width": 1280, "height": 720}"#;
+const AUDIO: &str = r#"{"codec_type": "audio", "codec_name": "aac"}"#;
+
+#[tokio::test]
+async fn verified_download_reports_resolution_and_codecs() {
+    let env = FakeEnv::new(&[
+        FakeTool::yt_dlp()
+            .creates_output("mp4")
+            .metadata("Canal", 187.0),
+        FakeTool::ffmpeg(),
+        ffprobe(&format!("{VIDEO}, {AUDIO}")),
+    ]);
+    let options = DownloadOptions::new(URL, &env.output("video"), false);
+
+    let mut statuses = Vec::new();
+    let downloaded = download_with(&options, &JobControl::new(), |event| {
+        if let JobEvent::Status(message) = event {
+            statuses.push(message);
+        }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(downloaded.media.unwrap().summary(), "1280x720 h264 + aac");
+    assert!(statuses.iter().any(|s| s.contains("1280x720 h264 + aac")));
+    let probe = env.last_job("ffprobe").unwrap();
+    assert_eq!(probe.last().unwrap(), &env.output("video.mp4"));
+}
+
+#[tokio::test]
+async fn file_without_audio_fails_and_is_retried() {
+    let env = FakeEnv::new(&[
+        FakeTool::yt_dlp()
+            .creates_output("mp4")
+            .metadata("Canal", 187.0),
+        FakeTool::ffmpeg(),
+        ffprobe(VIDEO),
+    ]);
+    env.write_config(
+        r#"{"provision": {"confirm": "never"},
+            "retry": {"attempts": 2, "initial_delay_ms": 1, "max_delay_ms": 5}}"#,
+    );
+    let options = DownloadOptions::new(URL, &env.output("video"), false);
+
+    let error = download_with(&options, &JobControl::new(), |_| {})
+        .await
+        .unwrap_err();
+
+    let DownloadError::RetriesExhausted { attempts, last } = error else {
+        panic!("esperava RetriesExhausted, veio {error:?}");
+    };
+    assert_eq!(attempts, 2);
+    let DownloadError::Verification(message) = *last else {
+        panic!("esperava Verification, veio {last:?}");
+    };
+    // A última tentativa deixa o arquivo recusado e diz onde ele está
+    assert!(message.contains(&env.output("video.mp4")));
+    let downloads = env
+        .invocations("yt-dlp")
+        .into_iter()
+        .filter(|args| !args.contains(&"--version".to_string()))
+        .count();
+    assert_eq!(downloads, 2);
+    assert!(Path::new(&env.output("video.mp4")).exists());
+}